@@ -0,0 +1,98 @@
+//! # ASCII numbers
+//!
+//! Parses decimal and hex numbers from the front of a byte buffer into endian types, for text
+//! fields embedded in binary or line-based protocols: HTTP chunk sizes, SIP `Content-Length`,
+//! NMEA sentences. Parsing stops at the first byte that is not a digit and reports how many bytes
+//! were consumed, so the caller can carry on from there. Signed types accept a leading `-`; there
+//! is no `+`, no `0x` prefix and no whitespace skipping.
+//! ## Example
+//! ```
+//! use endiantype::ascii::Error;
+//! use endiantype::*;
+//!
+//! let (size, used) = u32_be::parse_ascii_hex(b"1a4;ext=1\r\n").unwrap();
+//! assert!(size == 0x1a4 && used == 3);
+//! let (length, used) = u16_le::parse_ascii_decimal(b"349\r\n").unwrap();
+//! assert!(length == 349 && used == 3);
+//! let (offset, _) = i8_be::parse_ascii_decimal(b"-128").unwrap();
+//! assert!(offset == -128);
+//! assert!(u8_be::parse_ascii_decimal(b"256") == Err(Error::Overflow));
+//! assert!(u8_be::parse_ascii_decimal(b"-1") == Err(Error::NoDigits));
+//! ```
+use crate::{BigEndian, LittleEndian};
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer does not start with a digit.
+    NoDigits,
+    /// The number does not fit in the type.
+    Overflow,
+}
+
+fn digit_value(c: u8, radix: u8) -> Option<u8> {
+    let value = match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => return None,
+    };
+    if value < radix {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+macro_rules! impl_ascii {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// Parse a decimal number from the front of `bytes`, returning it and the number of
+            /// bytes it took up.
+            pub fn parse_ascii_decimal(bytes: &[u8]) -> Result<(Self, usize), Error> {
+                Self::parse_ascii_radix(bytes, 10)
+            }
+
+            /// Parse a hex number of either case from the front of `bytes`, returning it and the
+            /// number of bytes it took up.
+            pub fn parse_ascii_hex(bytes: &[u8]) -> Result<(Self, usize), Error> {
+                Self::parse_ascii_radix(bytes, 16)
+            }
+
+            fn parse_ascii_radix(bytes: &[u8], radix: u8) -> Result<(Self, usize), Error> {
+                #[allow(unused_comparisons)]
+                let negative = $type_name::MIN < 0 && bytes.first() == Some(&b'-');
+                let start = usize::from(negative);
+                let mut value: $type_name = 0;
+                let mut end = start;
+                while let Some(digit) = bytes.get(end).and_then(|&c| digit_value(c, radix)) {
+                    let digit = digit as $type_name;
+                    value = value
+                        .checked_mul(radix as $type_name)
+                        .and_then(|value| {
+                            if negative {
+                                value.checked_sub(digit)
+                            } else {
+                                value.checked_add(digit)
+                            }
+                        })
+                        .ok_or(Error::Overflow)?;
+                    end += 1;
+                }
+                if end == start {
+                    return Err(Error::NoDigits);
+                }
+                Ok((Self::from_native(value), end))
+            }
+        }
+    };
+}
+
+macro_rules! impl_ascii_both {
+    ($type_name: ident) => {
+        impl_ascii!($type_name, LittleEndian);
+        impl_ascii!($type_name, BigEndian);
+    };
+}
+
+for_each_int!(impl_ascii_both);
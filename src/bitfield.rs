@@ -0,0 +1,96 @@
+/// # Bitfield declarations
+/// Declares a wrapper around an endian integer with typed getters and setters for named bit
+/// ranges, so packed header words don't have to be taken apart with shifts by hand.
+///
+/// Each field is written as `getter, setter: type = start, width;`, where `start` is counted in
+/// the bit order (`Msb0` or `Lsb0`) given after the word type. The struct derives `Copy`,
+/// `Clone`, `Debug`, `Default`, `Hash`, `PartialEq` and `Eq`, so it can be used as a map key.
+/// ## Example
+/// ```
+/// use endiantype::bitorder::Msb0;
+/// use endiantype::*;
+///
+/// bitfield! {
+///     /// First byte of an IPv4 header.
+///     pub struct VersionIhl(u8_be, Msb0) {
+///         pub version, set_version: u8 = 0, 4;
+///         pub ihl, set_ihl: u8 = 4, 4;
+///     }
+/// }
+///
+/// bitfield! {
+///     pub struct FlagsFragment(u16_be, Msb0) {
+///         pub flags, set_flags: u8 = 0, 3;
+///         pub fragment_offset, set_fragment_offset: u16 = 3, 13;
+///     }
+/// }
+///
+/// let mut first = VersionIhl(u8_be::from_native(0x45));
+/// assert!(first.version() == 4 && first.ihl() == 5);
+/// first.set_ihl(6);
+/// assert!(first.0 == 0x46);
+///
+/// let mut word = FlagsFragment::default();
+/// word.set_flags(0b010);
+/// word.set_fragment_offset(185);
+/// assert!(word.0 == 0x40b9);
+///
+/// let mut seen = std::collections::HashSet::new();
+/// assert!(seen.insert(word) && !seen.insert(word));
+/// ```
+#[macro_export]
+macro_rules! bitfield {
+    (
+        $(#[$attr: meta])*
+        $vis: vis struct $name: ident($word: ty, $order: ty) {
+            $(
+                $(#[$field_attr: meta])*
+                $field_vis: vis $getter: ident, $setter: ident: $field_type: ty = $start: expr, $width: expr;
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+        #[repr(transparent)]
+        $vis struct $name(pub $word);
+
+        impl $name {
+            $(
+                $(#[$field_attr])*
+                #[inline]
+                $field_vis fn $getter(&self) -> $field_type {
+                    self.0.get_bits::<$order>($start, $width) as $field_type
+                }
+
+                $(#[$field_attr])*
+                #[inline]
+                $field_vis fn $setter(&mut self, value: $field_type) {
+                    self.0.set_bits::<$order>($start, $width, value as _);
+                }
+            )*
+        }
+
+        impl $crate::decoder::Decode for $name {
+            const SIZE: usize = <$word as $crate::decoder::Decode>::SIZE;
+
+            #[inline]
+            fn decode(bytes: &[u8]) -> Self {
+                Self(<$word as $crate::decoder::Decode>::decode(bytes))
+            }
+        }
+
+        impl $crate::io::Encode for $name {
+            const SIZE: usize = <$word as $crate::io::Encode>::SIZE;
+
+            #[inline]
+            fn encode(&self, bytes: &mut [u8]) {
+                $crate::io::Encode::encode(&self.0, bytes)
+            }
+        }
+
+        impl $crate::wire::Wire for $name {
+            const ENDIANNESS: Option<$crate::Endianness> = <$word as $crate::wire::Wire>::ENDIANNESS;
+            const C_TYPE: &'static str = <$word as $crate::wire::Wire>::C_TYPE;
+        }
+    };
+}
@@ -0,0 +1,159 @@
+//! # Bit order
+//!
+//! Byte order alone does not say how bits are numbered inside a field. IETF-style diagrams number
+//! bit 0 as the most significant bit (`Msb0`), while most hardware register manuals number bit 0
+//! as the least significant bit (`Lsb0`).
+//!
+//! The markers are used by the `get_bits`/`set_bits` accessors of the endian types and by
+//! `BitReader`.
+//! ## Example
+//! ```
+//! use endiantype::bitorder::*;
+//! use endiantype::*;
+//! // IPv4 flags and fragment offset: flags are bits 0..3 in the RFC diagram.
+//! let word = u16_be::from_native(0x4000);
+//! assert!(word.get_bits::<Msb0>(0, 3) == 0b010);
+//! assert!(word.get_bits::<Lsb0>(13, 3) == 0b010);
+//!
+//! let mut reader = BitReader::<Msb0>::new(&[0x45, 0x00]);
+//! assert!(reader.read_bits(4) == Some(4));
+//! assert!(reader.read_bits(4) == Some(5));
+//! ```
+use crate::{BigEndian, LittleEndian};
+use core::marker::PhantomData;
+
+/// # Bit numbering
+/// Maps bit positions counted in a given order to shifts within a word.
+pub trait BitOrder {
+    /// Shift of the least significant bit of the field `start..start + width`, with positions
+    /// counted in this order, inside a `bits`-wide word.
+    fn field_shift(bits: u32, start: u32, width: u32) -> u32;
+}
+
+/// # Most significant bit first
+/// Bit 0 is the most significant bit, as in IETF packet diagrams.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Msb0 {}
+
+/// # Least significant bit first
+/// Bit 0 is the least significant bit, as in most register manuals.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Lsb0 {}
+
+impl BitOrder for Msb0 {
+    #[inline]
+    fn field_shift(bits: u32, start: u32, width: u32) -> u32 {
+        bits - start - width
+    }
+}
+
+impl BitOrder for Lsb0 {
+    #[inline]
+    fn field_shift(_bits: u32, start: u32, _width: u32) -> u32 {
+        start
+    }
+}
+
+macro_rules! impl_endian_bits {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// Read the `width` bits starting at bit `start`, counted in bit order `O`.
+            ///
+            /// Panics if the field does not fit in the integer.
+            #[inline]
+            pub fn get_bits<O: BitOrder>(&self, start: u32, width: u32) -> $type_name {
+                assert!(start + width <= $type_name::BITS);
+                if width == 0 {
+                    return 0;
+                }
+                let shift = O::field_shift($type_name::BITS, start, width);
+                (self.to_native() >> shift) & (!0 >> ($type_name::BITS - width))
+            }
+
+            /// Replace the `width` bits starting at bit `start`, counted in bit order `O`, with
+            /// the low bits of `value`.
+            ///
+            /// Panics if the field does not fit in the integer.
+            #[inline]
+            pub fn set_bits<O: BitOrder>(&mut self, start: u32, width: u32, value: $type_name) {
+                assert!(start + width <= $type_name::BITS);
+                if width == 0 {
+                    return;
+                }
+                let shift = O::field_shift($type_name::BITS, start, width);
+                let mask = ((!0 as $type_name) >> ($type_name::BITS - width)) << shift;
+                *self = Self::from_native((self.to_native() & !mask) | ((value << shift) & mask));
+            }
+        }
+    };
+}
+
+macro_rules! impl_bits {
+    ($type_name: ident) => {
+        impl_endian_bits!($type_name, LittleEndian);
+        impl_endian_bits!($type_name, BigEndian);
+    };
+}
+
+impl_bits!(u8);
+impl_bits!(u16);
+impl_bits!(u32);
+impl_bits!(u64);
+impl_bits!(u128);
+impl_bits!(usize);
+
+/// # Bit reader
+/// Reads bit fields of up to 64 bits from a byte buffer, taking bits of each byte in order `O`.
+///
+/// With `Msb0` the first bit read becomes the most significant bit of the result, as in network
+/// protocols; with `Lsb0` it becomes the least significant, as in DEFLATE.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a, O> {
+    data: &'a [u8],
+    pos: usize,
+    _order: PhantomData<O>,
+}
+
+impl<'a, O: BitOrder> BitReader<'a, O> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            _order: PhantomData,
+        }
+    }
+
+    /// Number of bits consumed so far.
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bits left in the buffer.
+    pub const fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.pos
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        let byte = *self.data.get(self.pos / 8)?;
+        let shift = O::field_shift(8, (self.pos % 8) as u32, 1);
+        self.pos += 1;
+        Some((byte >> shift) & 1 == 1)
+    }
+
+    /// Read `count` bits, or `None` if fewer are left.
+    ///
+    /// Panics if `count` is larger than 64.
+    pub fn read_bits(&mut self, count: u32) -> Option<u64> {
+        assert!(count <= 64);
+        if self.remaining() < count as usize {
+            return None;
+        }
+        let mut value = 0;
+        for i in 0..count {
+            if self.read_bit()? {
+                value |= 1 << O::field_shift(count, i, 1);
+            }
+        }
+        Some(value)
+    }
+}
@@ -0,0 +1,184 @@
+//! # Endian bitsets
+//!
+//! On-disk allocation bitmaps are arrays of words in a fixed byte order: bit `i` is bit
+//! `i % BITS` of word `i / BITS`, counted from the least significant bit of the word's value.
+//! `EndianBitSet` keeps the words as stored so it can be used in place, e.g. over a mapped ext4
+//! block bitmap (little-endian, so `u32_le` or `u64_le` words), and converts a single word on
+//! each access.
+//! ## Example
+//! ```
+//! use endiantype::bitset::EndianBitSet;
+//! use endiantype::*;
+//!
+//! let mut words = [u32_le::default(); 2];
+//! let bitmap = EndianBitSet::from_words_mut(&mut words);
+//! bitmap.set(0);
+//! bitmap.set(9);
+//! bitmap.set(33);
+//! assert!(bitmap.test(9) && !bitmap.test(10));
+//! bitmap.clear(0);
+//! let ones: Vec<usize> = bitmap.iter_ones().collect();
+//! assert!(ones == [9, 33]);
+//! // The bytes are exactly those of an ext4 bitmap.
+//! assert!(words[0].as_ref() == [0x00, 0x02, 0x00, 0x00]);
+//! assert!(words[1].as_ref() == [0x02, 0x00, 0x00, 0x00]);
+//! ```
+use crate::{BigEndian, LittleEndian};
+
+/// # Bitset word
+/// Endian unsigned integers usable as the words of an `EndianBitSet`.
+pub trait BitWord: Copy + Default {
+    const BITS: usize;
+
+    fn to_bits(self) -> u64;
+
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! impl_bit_word {
+    ($($type_name: ident),*) => {
+        $(
+            impl BitWord for LittleEndian<$type_name> {
+                const BITS: usize = $type_name::BITS as usize;
+
+                #[inline]
+                fn to_bits(self) -> u64 {
+                    u64::from(self.to_native())
+                }
+
+                #[inline]
+                fn from_bits(bits: u64) -> Self {
+                    Self::from_native(bits as $type_name)
+                }
+            }
+
+            impl BitWord for BigEndian<$type_name> {
+                const BITS: usize = $type_name::BITS as usize;
+
+                #[inline]
+                fn to_bits(self) -> u64 {
+                    u64::from(self.to_native())
+                }
+
+                #[inline]
+                fn from_bits(bits: u64) -> Self {
+                    Self::from_native(bits as $type_name)
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_word!(u8, u16, u32, u64);
+
+/// # Endian bitset
+/// A set of `N * W::BITS` bits stored as `N` words. Indices out of range panic, like slice
+/// indexing.
+#[derive(Copy, Clone, Debug, Hash)]
+#[repr(transparent)]
+pub struct EndianBitSet<W, const N: usize> {
+    words: [W; N],
+}
+
+impl<W: BitWord, const N: usize> EndianBitSet<W, N> {
+    /// An empty bitset.
+    pub fn new() -> Self {
+        Self {
+            words: [W::default(); N],
+        }
+    }
+
+    pub const fn from_words(words: [W; N]) -> Self {
+        Self { words }
+    }
+
+    /// View words in place, e.g. part of a mapped buffer, as a bitset.
+    pub fn from_words_mut(words: &mut [W; N]) -> &mut Self {
+        // SAFETY: `EndianBitSet` is a transparent wrapper around `[W; N]`.
+        unsafe { &mut *(words as *mut [W; N] as *mut Self) }
+    }
+
+    pub fn words(&self) -> &[W; N] {
+        &self.words
+    }
+
+    pub fn into_words(self) -> [W; N] {
+        self.words
+    }
+
+    /// Number of bits in the set.
+    pub const fn len(&self) -> usize {
+        N * W::BITS
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    pub fn test(&self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word].to_bits() & mask != 0
+    }
+
+    pub fn set(&mut self, index: usize) {
+        let (word, mask) = Self::locate(index);
+        self.words[word] = W::from_bits(self.words[word].to_bits() | mask);
+    }
+
+    pub fn clear(&mut self, index: usize) {
+        let (word, mask) = Self::locate(index);
+        self.words[word] = W::from_bits(self.words[word].to_bits() & !mask);
+    }
+
+    /// Number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.to_bits().count_ones() as usize)
+            .sum()
+    }
+
+    /// Indices of the set bits, in increasing order.
+    pub fn iter_ones(&self) -> Ones<'_, W> {
+        Ones {
+            words: &self.words,
+            end: 0,
+            current: 0,
+        }
+    }
+
+    fn locate(index: usize) -> (usize, u64) {
+        (index / W::BITS, 1 << (index % W::BITS))
+    }
+}
+
+impl<W: BitWord, const N: usize> Default for EndianBitSet<W, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # Set bit iterator
+pub struct Ones<'a, W> {
+    words: &'a [W],
+    /// Index one past the last bit of the word in `current`.
+    end: usize,
+    /// The bits of the current word not yet returned.
+    current: u64,
+}
+
+impl<W: BitWord> Iterator for Ones<'_, W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            let (&word, rest) = self.words.split_first()?;
+            self.current = word.to_bits();
+            self.words = rest;
+            self.end += W::BITS;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.end - W::BITS + bit)
+    }
+}
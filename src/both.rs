@@ -0,0 +1,143 @@
+//! # Both-endian values
+//!
+//! ISO 9660 and a few other legacy formats store numbers twice, first little endian and then big
+//! endian, so readers of either byte order can use them directly. `BothEndian<T>` is such a pair:
+//! it occupies twice the size of `T`, writes both halves, and `to_native` only returns a value
+//! when the halves agree. It implements `Wire`, so it can be used in `endian_struct!`.
+//! ## Example
+//! ```
+//! use endiantype::both::BothEndian;
+//! use endiantype::io::*;
+//!
+//! let mut buf = [0u8; 8];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! writer.write(BothEndian::<u32>::from_native(0x1234)).unwrap();
+//! assert!(buf == [0x34, 0x12, 0, 0, 0, 0, 0x12, 0x34]);
+//!
+//! let blocks = EndianReader::new(&buf).read::<BothEndian<u32>>().unwrap();
+//! assert!(blocks.to_native() == Some(0x1234));
+//! buf[0] = 0x35;
+//! let blocks = EndianReader::new(&buf).read::<BothEndian<u32>>().unwrap();
+//! assert!(blocks.to_native().is_none() && blocks.big() == 0x1234);
+//! ```
+use crate::decoder::Decode;
+use crate::endianness::Endianness;
+use crate::io::Encode;
+use crate::wire::Wire;
+use crate::{BigEndian, LittleEndian};
+
+/// # Both-endian value
+/// A little-endian `T` followed by the same value in big endian.
+#[derive(Copy, Clone, Debug, Default, Hash)]
+#[repr(C)]
+pub struct BothEndian<T> {
+    little: LittleEndian<T>,
+    big: BigEndian<T>,
+}
+
+impl<T> BothEndian<T> {
+    /// A pair from separately decoded halves, which may disagree.
+    pub const fn from_halves(little: LittleEndian<T>, big: BigEndian<T>) -> Self {
+        Self { little, big }
+    }
+}
+
+impl<T: Copy> BothEndian<T> {
+    pub fn little(&self) -> LittleEndian<T> {
+        self.little
+    }
+
+    pub fn big(&self) -> BigEndian<T> {
+        self.big
+    }
+}
+
+impl<T> Decode for BothEndian<T>
+where
+    LittleEndian<T>: Decode,
+    BigEndian<T>: Decode,
+{
+    const SIZE: usize = <LittleEndian<T> as Decode>::SIZE + <BigEndian<T> as Decode>::SIZE;
+
+    #[inline]
+    fn decode(bytes: &[u8]) -> Self {
+        let (little, big) = bytes.split_at(<LittleEndian<T> as Decode>::SIZE);
+        Self {
+            little: Decode::decode(little),
+            big: Decode::decode(big),
+        }
+    }
+}
+
+impl<T> Encode for BothEndian<T>
+where
+    LittleEndian<T>: Encode,
+    BigEndian<T>: Encode,
+{
+    const SIZE: usize = <LittleEndian<T> as Encode>::SIZE + <BigEndian<T> as Encode>::SIZE;
+
+    #[inline]
+    fn encode(&self, bytes: &mut [u8]) {
+        let (little, big) = bytes.split_at_mut(<LittleEndian<T> as Encode>::SIZE);
+        self.little.encode(little);
+        self.big.encode(big);
+    }
+}
+
+macro_rules! impl_both_endian {
+    ($type_name: ident, $c_type: expr) => {
+        impl BothEndian<$type_name> {
+            pub const fn from_native(data: $type_name) -> Self {
+                Self {
+                    little: LittleEndian::<$type_name>::from_native(data),
+                    big: BigEndian::<$type_name>::from_native(data),
+                }
+            }
+
+            /// The value, or `None` if the two halves disagree.
+            pub fn to_native(&self) -> Option<$type_name> {
+                let value = self.little.to_native();
+                if value == self.big.to_native() {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+
+            /// The half in byte order `endianness`, ignoring the other one.
+            pub fn to_native_in(&self, endianness: Endianness) -> $type_name {
+                match endianness {
+                    Endianness::Little => self.little.to_native(),
+                    Endianness::Big => self.big.to_native(),
+                }
+            }
+        }
+
+        impl PartialEq for BothEndian<$type_name> {
+            /// Both halves are equal; pairs that disagree are only equal to identical pairs.
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.little == other.little && self.big == other.big
+            }
+        }
+
+        impl From<$type_name> for BothEndian<$type_name> {
+            #[inline]
+            fn from(data: $type_name) -> Self {
+                Self::from_native(data)
+            }
+        }
+
+        impl Wire for BothEndian<$type_name> {
+            const ENDIANNESS: Option<Endianness> = None;
+            const C_TYPE: &'static str = concat!("struct { ", $c_type, " le; ", $c_type, " be; }");
+        }
+    };
+}
+
+impl_both_endian!(u16, "uint16_t");
+impl_both_endian!(u32, "uint32_t");
+impl_both_endian!(u64, "uint64_t");
+impl_both_endian!(i16, "int16_t");
+impl_both_endian!(i32, "int32_t");
+impl_both_endian!(i64, "int64_t");
@@ -0,0 +1,119 @@
+//! # Bounded integers
+//!
+//! `Bounded<E, MIN, MAX>` wraps an endian integer `E` whose native value is known to lie in
+//! `MIN..=MAX`. The constructors check the range, so a header field with a documented valid
+//! range is rejected when it is parsed rather than deep in the code that uses it. The wrapper is
+//! transparent and is encoded exactly like `E`; it is read with `Bounded::read` rather than
+//! `Decode`, which cannot fail.
+//! ## Example
+//! ```
+//! use endiantype::bounded::*;
+//! use endiantype::io::EndianReader;
+//! use endiantype::*;
+//!
+//! type Version = Bounded<u8_be, 1, 3>;
+//! type Port = Bounded<u16_be, 1024, 65535>;
+//!
+//! let mut reader = EndianReader::new(&[0x02, 0x1f, 0x90, 0x07]);
+//! let version = Version::read(&mut reader).unwrap();
+//! let port = Port::read(&mut reader).unwrap();
+//! assert!(version.to_native() == 2 && port.get() == 8080);
+//! assert!(Version::read(&mut reader) == Err(Error::OutOfRange));
+//! assert!(Port::from_native(80).is_err());
+//! ```
+use crate::io::{Encode, EndianReader};
+use crate::{BigEndian, LittleEndian};
+use core::convert::TryFrom;
+
+/// # Errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended before the value.
+    UnexpectedEof,
+    /// The value is outside the bounds.
+    OutOfRange,
+}
+
+/// # Bounded integer
+/// An endian integer `E` whose native value is in `MIN..=MAX`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct Bounded<E, const MIN: i128, const MAX: i128>(E);
+
+impl<E: Copy, const MIN: i128, const MAX: i128> Bounded<E, MIN, MAX> {
+    /// The endian value.
+    pub fn get(&self) -> E {
+        self.0
+    }
+}
+
+impl<E, const MIN: i128, const MAX: i128> Encode for Bounded<E, MIN, MAX>
+where
+    E: Encode,
+{
+    const SIZE: usize = E::SIZE;
+
+    #[inline]
+    fn encode(&self, bytes: &mut [u8]) {
+        self.0.encode(bytes)
+    }
+}
+
+macro_rules! impl_bounded {
+    ($type_name: ident, $endian_name: ident) => {
+        impl<const MIN: i128, const MAX: i128> Bounded<$endian_name<$type_name>, MIN, MAX> {
+            /// Wrap `value` if it is in range.
+            pub fn new(value: $endian_name<$type_name>) -> Result<Self, Error> {
+                match i128::try_from(value.to_native()) {
+                    Ok(native) if MIN <= native && native <= MAX => Ok(Self(value)),
+                    _ => Err(Error::OutOfRange),
+                }
+            }
+
+            pub fn from_native(value: $type_name) -> Result<Self, Error> {
+                Self::new($endian_name::<$type_name>::from_native(value))
+            }
+
+            pub fn to_native(&self) -> $type_name {
+                self.0.to_native()
+            }
+
+            /// Read a value and check that it is in range.
+            pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+                let value = reader.read().ok_or(Error::UnexpectedEof)?;
+                Self::new(value)
+            }
+        }
+
+        impl<const MIN: i128, const MAX: i128> TryFrom<$endian_name<$type_name>>
+            for Bounded<$endian_name<$type_name>, MIN, MAX>
+        {
+            type Error = Error;
+
+            #[inline]
+            fn try_from(value: $endian_name<$type_name>) -> Result<Self, Error> {
+                Self::new(value)
+            }
+        }
+
+        impl<const MIN: i128, const MAX: i128> From<Bounded<$endian_name<$type_name>, MIN, MAX>>
+            for $type_name
+        {
+            #[inline]
+            fn from(value: Bounded<$endian_name<$type_name>, MIN, MAX>) -> Self {
+                value.to_native()
+            }
+        }
+    };
+}
+
+macro_rules! impl_bounded_each {
+    ($($type_name: ident),*) => {
+        $(
+            impl_bounded!($type_name, LittleEndian);
+            impl_bounded!($type_name, BigEndian);
+        )*
+    };
+}
+
+for_each_int!(impl_bounded_each);
@@ -0,0 +1,72 @@
+//! # Byte order markers
+//!
+//! An alternative naming scheme where the byte order is a type parameter: `U16<BE>`, `U64<LE>`,
+//! `I32<NE>`. The aliases resolve to the same `BigEndian<T>`/`LittleEndian<T>` types as `u16_be`
+//! and friends, so both spellings can be mixed freely. This matches zerocopy's byteorder types and
+//! is exported as one set by `endiantype::prelude`.
+//! ## Example
+//! ```
+//! use endiantype::prelude::*;
+//! let a: U32<BE> = U32::<BE>::from_native(7);
+//! let b: endiantype::u32_be = a;
+//! assert!(b == 7);
+//! let c: U16<NetworkEndian> = 0x1234.into();
+//! assert!(c.as_ref() == [0x12, 0x34]);
+//! assert!(<LE as ByteOrder>::ENDIANNESS == endiantype::Endianness::Little);
+//! ```
+use crate::{BigEndian, Endianness, LittleEndian};
+
+/// # Byte order marker
+/// Maps a byte order marker type to the endian wrapper storing values in that order.
+pub trait ByteOrder {
+    /// The endian wrapper for this order.
+    type Endian<T>;
+
+    const ENDIANNESS: Endianness;
+}
+
+/// Big endian byte order marker.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum BE {}
+
+/// Little endian byte order marker.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum LE {}
+
+/// Network byte order, which is big endian.
+pub type NetworkEndian = BE;
+
+/// Byte order of the target.
+#[cfg(target_endian = "big")]
+pub type NE = BE;
+
+/// Byte order of the target.
+#[cfg(target_endian = "little")]
+pub type NE = LE;
+
+impl ByteOrder for BE {
+    type Endian<T> = BigEndian<T>;
+
+    const ENDIANNESS: Endianness = Endianness::Big;
+}
+
+impl ByteOrder for LE {
+    type Endian<T> = LittleEndian<T>;
+
+    const ENDIANNESS: Endianness = Endianness::Little;
+}
+
+pub type U8<O> = <O as ByteOrder>::Endian<u8>;
+pub type U16<O> = <O as ByteOrder>::Endian<u16>;
+pub type U32<O> = <O as ByteOrder>::Endian<u32>;
+pub type U64<O> = <O as ByteOrder>::Endian<u64>;
+pub type U128<O> = <O as ByteOrder>::Endian<u128>;
+pub type Usize<O> = <O as ByteOrder>::Endian<usize>;
+pub type I8<O> = <O as ByteOrder>::Endian<i8>;
+pub type I16<O> = <O as ByteOrder>::Endian<i16>;
+pub type I32<O> = <O as ByteOrder>::Endian<i32>;
+pub type I64<O> = <O as ByteOrder>::Endian<i64>;
+pub type I128<O> = <O as ByteOrder>::Endian<i128>;
+pub type Isize<O> = <O as ByteOrder>::Endian<isize>;
+pub type F32<O> = <O as ByteOrder>::Endian<f32>;
+pub type F64<O> = <O as ByteOrder>::Endian<f64>;
@@ -0,0 +1,150 @@
+//! # Fixed-size byte conversions
+//!
+//! `EndianBytes` is implemented by every endian type with its stored bytes as `Bytes`, an array
+//! of the type's size. Generic serializers can be written once over "anything with a fixed wire
+//! size" by bounding on it; `Decode` and `Encode` are implemented through it. `from_bytes_ref`
+//! and `from_slice` view a field of a buffer in place when it is suitably aligned, and
+//! `TryFrom<&[u8]>` copies one out of a slice of any alignment.
+//! ## Example
+//! ```
+//! use endiantype::bytes::EndianBytes;
+//! use endiantype::*;
+//!
+//! fn put<T: EndianBytes>(out: &mut Vec<u8>, value: T) {
+//!     out.extend_from_slice(value.to_bytes().as_ref());
+//! }
+//!
+//! let mut out = Vec::new();
+//! put(&mut out, u16_be::from_native(0x0102));
+//! put(&mut out, u32_le::from_native(0x0a0b0c0d));
+//! assert!(out == [0x01, 0x02, 0x0d, 0x0c, 0x0b, 0x0a]);
+//! assert!(u16_be::from_bytes([0x01, 0x02]) == 0x0102);
+//!
+//! #[repr(align(8))]
+//! struct Aligned([u8; 8]);
+//! let buf = Aligned([0, 0, 0, 0, 0, 0, 0, 7]);
+//! assert!(*u64_be::ref_from_bytes(&buf.0).unwrap() == 7);
+//!
+//! // A field of a received buffer, viewed without copying it out.
+//! let header = u32_le::from_slice(&buf.0[4..]).unwrap();
+//! assert!(header.to_native() == 0x0700_0000);
+//! assert!(u32_le::from_slice(&buf.0[2..6]).is_none()); // misaligned
+//! assert!(u32_le::from_slice(&buf.0[..3]).is_none()); // too short
+//!
+//! use core::convert::TryFrom;
+//! use endiantype::bytes::EndianError;
+//! assert!(u32_be::try_from(&buf.0[4..]) == Ok(u32_be::from_native(7)));
+//! assert!(u32_be::try_from(&buf.0[5..]) == Err(EndianError::InvalidLength(3)));
+//! ```
+use crate::{BigEndian, LittleEndian};
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+
+/// # Conversion errors
+/// The error of `TryFrom<&[u8]>` for the endian types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EndianError {
+    /// The slice is not the size of the type; holds the length of the slice.
+    InvalidLength(usize),
+}
+
+impl fmt::Display for EndianError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndianError::InvalidLength(len) => {
+                write!(
+                    f,
+                    "slice of {} bytes does not match the size of the type",
+                    len
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EndianError {}
+
+/// # Byte array conversions
+pub trait EndianBytes: Copy {
+    /// `[u8; N]` where `N` is the size of the type.
+    type Bytes: Copy + Default + AsRef<[u8]> + AsMut<[u8]>;
+
+    /// The stored bytes.
+    fn to_bytes(self) -> Self::Bytes;
+
+    /// A value with `bytes` as its stored bytes.
+    fn from_bytes(bytes: Self::Bytes) -> Self;
+
+    /// View `bytes` as a value in place, or `None` if they are not aligned for `Self`.
+    fn ref_from_bytes(bytes: &Self::Bytes) -> Option<&Self>;
+}
+
+macro_rules! impl_endian_bytes {
+    ($type_name: ident, $endian_name: ident) => {
+        impl EndianBytes for $endian_name<$type_name> {
+            type Bytes = [u8; core::mem::size_of::<$type_name>()];
+
+            #[inline]
+            fn to_bytes(self) -> Self::Bytes {
+                self.0.to_ne_bytes()
+            }
+
+            #[inline]
+            fn from_bytes(bytes: Self::Bytes) -> Self {
+                Self::new($type_name::from_ne_bytes(bytes))
+            }
+
+            #[inline]
+            fn ref_from_bytes(bytes: &Self::Bytes) -> Option<&Self> {
+                let ptr = bytes as *const Self::Bytes as *const Self;
+                if ptr as usize % core::mem::align_of::<Self>() != 0 {
+                    return None;
+                }
+                // SAFETY: the wrapper is `repr(transparent)` over a primitive number of the same
+                // size as `bytes`, every bit pattern is valid, and `ptr` is aligned.
+                Some(unsafe { &*ptr })
+            }
+        }
+
+        impl $endian_name<$type_name> {
+            /// View `bytes` as a value in place, or `None` if they are not aligned for `Self`;
+            /// only the single-byte types are always aligned. Use `from_bytes` to copy instead.
+            #[inline]
+            pub fn from_bytes_ref(
+                bytes: &[u8; core::mem::size_of::<$type_name>()],
+            ) -> Option<&Self> {
+                Self::ref_from_bytes(bytes)
+            }
+
+            /// View `bytes` as a value in place, or `None` if it is not exactly the size of
+            /// `Self` or not aligned for it.
+            #[inline]
+            pub fn from_slice(bytes: &[u8]) -> Option<&Self> {
+                Self::ref_from_bytes(bytes.try_into().ok()?)
+            }
+        }
+
+        impl<'a> TryFrom<&'a [u8]> for $endian_name<$type_name> {
+            type Error = EndianError;
+
+            /// Copy the stored bytes from a slice of exactly the size of the type.
+            #[inline]
+            fn try_from(bytes: &'a [u8]) -> Result<Self, EndianError> {
+                bytes
+                    .try_into()
+                    .map(Self::from_bytes)
+                    .map_err(|_| EndianError::InvalidLength(bytes.len()))
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_bytes_both {
+    ($type_name: ident) => {
+        impl_endian_bytes!($type_name, LittleEndian);
+        impl_endian_bytes!($type_name, BigEndian);
+    };
+}
+
+for_each_primitive!(impl_endian_bytes_both);
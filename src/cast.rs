@@ -0,0 +1,78 @@
+//! # Lossy casts
+//!
+//! `cast::<U>()` converts an endian integer to any other integer type, endian or native, with the
+//! semantics of `as`: truncation when narrowing, sign or zero extension when widening according to
+//! the source type, and reinterpretation between signed and unsigned. Translating between two wire
+//! formats then takes one call per field instead of `to_native`, `as` and `from_native`.
+//! ## Example
+//! ```
+//! use endiantype::*;
+//!
+//! let length = u64_be::from_native(0x1_ffff_fffe);
+//! assert!(length.cast::<i32_le>() == -2);
+//! assert!(length.cast::<u16>() == 0xfffe);
+//! let delta = i8_le::from_native(-1);
+//! assert!(delta.cast::<u32_be>() == u32::MAX && delta.cast::<i64_be>() == -1);
+//! assert!(u8_be::from_native(0xff).cast::<i16_be>() == 255);
+//! ```
+use crate::{BigEndian, LittleEndian};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// # Cast target
+/// Integer types, native or endian, that `cast` can produce.
+pub trait Cast: Copy + sealed::Sealed {
+    /// The value sign or zero extended to 128 bits, as `as i128` would.
+    fn to_i128(self) -> i128;
+
+    /// The low bits of `value`, as `value as Self` would.
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_cast {
+    ($type_name: ident) => {
+        impl sealed::Sealed for $type_name {}
+
+        impl Cast for $type_name {
+            #[inline]
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            #[inline]
+            fn from_i128(value: i128) -> Self {
+                value as $type_name
+            }
+        }
+
+        impl_cast!($type_name, LittleEndian);
+        impl_cast!($type_name, BigEndian);
+    };
+    ($type_name: ident, $endian_name: ident) => {
+        impl sealed::Sealed for $endian_name<$type_name> {}
+
+        impl Cast for $endian_name<$type_name> {
+            #[inline]
+            fn to_i128(self) -> i128 {
+                self.to_native() as i128
+            }
+
+            #[inline]
+            fn from_i128(value: i128) -> Self {
+                Self::from_native(value as $type_name)
+            }
+        }
+
+        impl $endian_name<$type_name> {
+            /// Convert to another integer type as `as` would.
+            #[inline]
+            pub fn cast<U: Cast>(self) -> U {
+                U::from_i128(Cast::to_i128(self))
+            }
+        }
+    };
+}
+
+for_each_int!(impl_cast);
@@ -0,0 +1,117 @@
+//! # Endian cells
+//!
+//! `EndianCell<T, O>` is a `Cell` holding a `T` stored in byte order `O`, for state shared between
+//! single-threaded contexts, such as a main loop and a handler running with interrupts disabled,
+//! that must stay in wire order, e.g. because its bytes are handed to a device or copied into
+//! packets as they are. Like `Cell`, it is not `Sync`; use atomics to share across threads.
+//! ## Example
+//! ```
+//! use endiantype::cell::EndianCell;
+//! use endiantype::prelude::*;
+//!
+//! struct Link {
+//!     sequence: EndianCell<u32, BE>,
+//! }
+//!
+//! let link = Link { sequence: EndianCell::new(0xffff_fffe) };
+//! let next = |link: &Link| link.sequence.update(|seq| seq.wrapping_add(1));
+//! next(&link);
+//! next(&link);
+//! assert!(link.sequence.get() == 0);
+//! link.sequence.set(0x0102_0304);
+//! assert!(link.sequence.get_endian().as_ref() == [1, 2, 3, 4]);
+//! ```
+use crate::byteorder::ByteOrder;
+use core::cell::Cell;
+use core::fmt;
+
+/// # Endian cell
+#[repr(transparent)]
+pub struct EndianCell<T, O: ByteOrder> {
+    value: Cell<O::Endian<T>>,
+}
+
+impl<T, O> EndianCell<T, O>
+where
+    T: Copy,
+    O: ByteOrder,
+    O::Endian<T>: Copy + From<T> + Into<T>,
+{
+    pub fn new(value: T) -> Self {
+        Self::from_endian(value.into())
+    }
+
+    pub const fn from_endian(value: O::Endian<T>) -> Self {
+        Self {
+            value: Cell::new(value),
+        }
+    }
+
+    /// The value, converted to native order.
+    pub fn get(&self) -> T {
+        self.value.get().into()
+    }
+
+    pub fn set(&self, value: T) {
+        self.value.set(value.into())
+    }
+
+    /// Replace the value with `f` of the current one.
+    pub fn update<F: FnOnce(T) -> T>(&self, f: F) {
+        self.set(f(self.get()))
+    }
+
+    /// Store `value` and return the previous one.
+    pub fn replace(&self, value: T) -> T {
+        self.value.replace(value.into()).into()
+    }
+
+    /// The value as stored, in byte order `O`.
+    pub fn get_endian(&self) -> O::Endian<T> {
+        self.value.get()
+    }
+
+    pub fn set_endian(&self, value: O::Endian<T>) {
+        self.value.set(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner().into()
+    }
+}
+
+impl<T, O> Default for EndianCell<T, O>
+where
+    O: ByteOrder,
+    O::Endian<T>: Default,
+{
+    fn default() -> Self {
+        Self {
+            value: Cell::new(Default::default()),
+        }
+    }
+}
+
+impl<T, O> Clone for EndianCell<T, O>
+where
+    O: ByteOrder,
+    O::Endian<T>: Copy,
+{
+    fn clone(&self) -> Self {
+        Self {
+            value: Cell::new(self.value.get()),
+        }
+    }
+}
+
+impl<T, O> fmt::Debug for EndianCell<T, O>
+where
+    O: ByteOrder,
+    O::Endian<T>: Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("EndianCell")
+            .field(&self.value.get())
+            .finish()
+    }
+}
@@ -0,0 +1,248 @@
+//! # Checksummed structs
+//!
+//! Many headers carry a checksum of themselves, computed with the checksum field set to zero.
+//! `checksummed!` designates one field of an `endian_struct!` as such a checksum, with the
+//! algorithm that computes it. `Checksummed::write_to` then fills the field in while writing the
+//! struct, and `Checksummed::read_from` rejects structs whose field does not match, so the
+//! checksum can no longer be forgotten.
+//!
+//! The field is found by name in the struct's `Wire::layout`, and the checksum is stored in the
+//! field's own width and byte order. The RFC 1071 internet checksum and CRC-32 are provided;
+//! other algorithms implement `Checksum`.
+//! ## Example
+//! ```
+//! use endiantype::checksum::*;
+//! use endiantype::io::*;
+//! use endiantype::*;
+//!
+//! endian_struct! {
+//!     pub struct Ipv4Header {
+//!         pub version_ihl: u8_be,
+//!         pub tos: u8_be,
+//!         pub total_length: u16_be,
+//!         pub identification: u16_be,
+//!         pub flags_fragment: u16_be,
+//!         pub ttl: u8_be,
+//!         pub protocol: u8_be,
+//!         pub checksum: u16_be,
+//!         pub src: [u8; 4],
+//!         pub dst: [u8; 4],
+//!     }
+//! }
+//! checksummed!(Ipv4Header: checksum, InternetChecksum);
+//!
+//! let header = Ipv4Header {
+//!     version_ihl: 0x45.into(),
+//!     tos: 0.into(),
+//!     total_length: 0x73.into(),
+//!     identification: 0.into(),
+//!     flags_fragment: 0x4000.into(),
+//!     ttl: 0x40.into(),
+//!     protocol: 0x11.into(),
+//!     checksum: 0.into(),
+//!     src: [192, 168, 0, 1],
+//!     dst: [192, 168, 0, 199],
+//! };
+//! let mut buf = [0u8; 20];
+//! header.write_to(&mut EndianWriter::new(&mut buf)).unwrap();
+//! assert!(buf[10..12] == [0xb8, 0x61]);
+//!
+//! let read = Ipv4Header::read_from(&mut EndianReader::new(&buf)).unwrap();
+//! assert!(read.checksum == 0xb861);
+//! buf[8] -= 1;
+//! assert!(Ipv4Header::read_from(&mut EndianReader::new(&buf)) == Err(Error::Mismatch));
+//! ```
+use crate::endianness::Endianness;
+use crate::io::{EndianReader, EndianWriter};
+use crate::wire::{FieldDesc, Wire};
+
+/// # Verification errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer is shorter than the struct.
+    UnexpectedEof,
+    /// The stored checksum does not match the contents.
+    Mismatch,
+}
+
+/// # Checksum algorithm
+/// A checksum computed over bytes fed in one or more pieces.
+pub trait Checksum: Default {
+    fn update(&mut self, bytes: &[u8]);
+
+    /// The checksum of everything fed so far; only the low bits that fit the field are stored.
+    fn finish(&self) -> u64;
+}
+
+/// # Internet checksum
+/// The 16-bit one's complement checksum of RFC 1071, used by IPv4, ICMP, UDP and TCP.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct InternetChecksum {
+    sum: u32,
+    /// The first byte of a 16-bit word split between pieces.
+    pending: Option<u8>,
+}
+
+impl Checksum for InternetChecksum {
+    fn update(&mut self, mut bytes: &[u8]) {
+        if let (Some(high), Some((&low, rest))) = (self.pending, bytes.split_first()) {
+            self.add(u16::from_be_bytes([high, low]));
+            self.pending = None;
+            bytes = rest;
+        }
+        let mut words = bytes.chunks_exact(2);
+        for word in &mut words {
+            self.add(u16::from_be_bytes([word[0], word[1]]));
+        }
+        if let [last] = words.remainder() {
+            self.pending = Some(*last);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut folded = *self;
+        if let Some(high) = folded.pending {
+            folded.add(u16::from_be_bytes([high, 0]));
+        }
+        u64::from(!(folded.sum as u16))
+    }
+}
+
+impl InternetChecksum {
+    fn add(&mut self, word: u16) {
+        let sum = self.sum + u32::from(word);
+        self.sum = (sum & 0xffff) + (sum >> 16);
+    }
+}
+
+/// # CRC-32
+/// The CRC-32 of Ethernet, zlib and PNG (reflected polynomial `0xedb88320`).
+#[derive(Copy, Clone, Debug)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self { crc: !0 }
+    }
+}
+
+impl Checksum for Crc32 {
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.crc ^= u32::from(byte);
+            for _ in 0..8 {
+                self.crc = (self.crc >> 1) ^ (0xedb8_8320 & (self.crc & 1).wrapping_neg());
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        u64::from(!self.crc)
+    }
+}
+
+/// # Checksummed struct
+/// A wire struct with a checksum field, implemented by `checksummed!`.
+pub trait Checksummed: Wire {
+    type Algorithm: Checksum;
+
+    /// Name of the checksum field.
+    const FIELD: &'static str;
+
+    /// Write the struct with its checksum field filled in; the field's current value is ignored.
+    fn write_to(&self, writer: &mut EndianWriter) -> Option<()> {
+        let field = checksum_field::<Self>();
+        let bytes = writer.reserve(<Self as crate::decoder::Decode>::SIZE)?;
+        self.encode(bytes);
+        let range = field.offset..field.offset + field.width;
+        bytes[range.clone()].iter_mut().for_each(|b| *b = 0);
+        let mut checksum = Self::Algorithm::default();
+        checksum.update(bytes);
+        store(&field, checksum.finish(), &mut bytes[range]);
+        Some(())
+    }
+
+    /// Read the struct, checking its checksum field.
+    fn read_from(reader: &mut EndianReader) -> Result<Self, Error> {
+        let bytes = reader
+            .read_bytes(<Self as crate::decoder::Decode>::SIZE)
+            .ok_or(Error::UnexpectedEof)?;
+        if !Self::verify(bytes) {
+            return Err(Error::Mismatch);
+        }
+        Ok(Self::decode(bytes))
+    }
+
+    /// Whether the encoded struct at the start of `bytes` has a correct checksum.
+    fn verify(bytes: &[u8]) -> bool {
+        let field = checksum_field::<Self>();
+        let bytes = match bytes.get(..<Self as crate::decoder::Decode>::SIZE) {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+        let (before, rest) = bytes.split_at(field.offset);
+        let (stored, after) = rest.split_at(field.width);
+        let mut checksum = Self::Algorithm::default();
+        checksum.update(before);
+        checksum.update(&[0; 8][..field.width]);
+        checksum.update(after);
+        let mut expected = [0; 8];
+        store(&field, checksum.finish(), &mut expected[..field.width]);
+        stored == &expected[..field.width]
+    }
+}
+
+fn checksum_field<T: Checksummed>() -> FieldDesc {
+    *T::layout()
+        .iter()
+        .find(|field| field.name == T::FIELD)
+        .expect("checksum field not in the struct layout")
+}
+
+/// Store the low bits of `value` that fit the field in its byte order.
+fn store(field: &FieldDesc, value: u64, bytes: &mut [u8]) {
+    let width = bytes.len();
+    match field.endianness.unwrap_or(Endianness::Big) {
+        Endianness::Big => bytes.copy_from_slice(&value.to_be_bytes()[8 - width..]),
+        Endianness::Little => bytes.copy_from_slice(&value.to_le_bytes()[..width]),
+    }
+}
+
+/// # Checksummed struct declarations
+/// Implements `Checksummed` for an `endian_struct!`, written as `Struct: field, Algorithm`. The
+/// field must be at most 8 bytes wide; a wider one fails to compile.
+/// ```compile_fail
+/// use endiantype::checksum::Crc32;
+/// use endiantype::*;
+///
+/// endian_struct! {
+///     pub struct Record {
+///         pub data: [u8; 16],
+///         pub digest: [u8; 16],
+///     }
+/// }
+/// checksummed!(Record: digest, Crc32);
+/// ```
+#[macro_export]
+macro_rules! checksummed {
+    ($name: ty: $field: ident, $algorithm: ty) => {
+        // Fails to compile if the struct has no such field, or if it is wider than 8 bytes.
+        const _: () = {
+            const fn width<T>(_: fn(&$name) -> &T) -> usize {
+                ::core::mem::size_of::<T>()
+            }
+            assert!(
+                width(|value: &$name| &value.$field) <= 8,
+                "checksum field must be at most 8 bytes wide"
+            );
+        };
+
+        impl $crate::checksum::Checksummed for $name {
+            type Algorithm = $algorithm;
+
+            const FIELD: &'static str = stringify!($field);
+        }
+    };
+}
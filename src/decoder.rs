@@ -0,0 +1,108 @@
+//! # Streaming decoder
+//!
+//! A push-based decoder that accumulates bytes across calls until a complete value is available,
+//! for non-blocking sockets and UART streams where fields straddle read boundaries.
+//! ## Example
+//! ```
+//! use endiantype::decoder::Decoder;
+//! use endiantype::*;
+//! let mut decoder = Decoder::<u32_be>::new();
+//! let mut first: &[u8] = &[0xde, 0xad];
+//! assert!(decoder.feed(&mut first).is_none());
+//! let mut second: &[u8] = &[0xbe, 0xef, 0x00, 0x00, 0x00, 0x2a];
+//! assert!(decoder.feed(&mut second).unwrap() == 0xdeadbeef);
+//! assert!(decoder.feed(&mut second).unwrap() == 42);
+//! assert!(second.is_empty());
+//! ```
+use crate::bytes::EndianBytes;
+
+/// # Fixed-size decoding
+/// Values that can be decoded from exactly `SIZE` bytes.
+///
+/// Implemented by all endian types; implement it for your own structs to decode them with
+/// `Decoder`.
+pub trait Decode: Sized {
+    const SIZE: usize;
+
+    /// Decode from `bytes`, which is exactly `SIZE` bytes long.
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+/// The endian types, through their stored bytes.
+impl<T: EndianBytes> Decode for T {
+    const SIZE: usize = core::mem::size_of::<T::Bytes>();
+
+    #[inline]
+    fn decode(bytes: &[u8]) -> Self {
+        let mut raw = T::Bytes::default();
+        raw.as_mut().copy_from_slice(bytes);
+        T::from_bytes(raw)
+    }
+}
+
+/// Raw bytes, e.g. addresses and reserved fields of a struct.
+impl<const N: usize> Decode for [u8; N] {
+    const SIZE: usize = N;
+
+    #[inline]
+    fn decode(bytes: &[u8]) -> Self {
+        let mut array = [0; N];
+        array.copy_from_slice(bytes);
+        array
+    }
+}
+
+/// # Push-based decoder
+/// Buffers up to `N` bytes of a partially received `T`.
+///
+/// `N` defaults to 16, enough for every endian integer; use a larger buffer for bigger structs.
+#[derive(Clone, Debug)]
+pub struct Decoder<T, const N: usize = 16> {
+    buf: [u8; N],
+    len: usize,
+    _value: core::marker::PhantomData<T>,
+}
+
+impl<T: Decode, const N: usize> Decoder<T, N> {
+    /// Panics if `T` does not fit in the `N`-byte buffer.
+    pub const fn new() -> Self {
+        assert!(T::SIZE <= N);
+        Self {
+            buf: [0; N],
+            len: 0,
+            _value: core::marker::PhantomData,
+        }
+    }
+
+    /// Take bytes from the front of `input` until a value is complete.
+    ///
+    /// Returns the value once all its bytes have arrived; `input` is advanced past the consumed
+    /// bytes, so call it again to decode any values left in the same chunk.
+    pub fn feed(&mut self, input: &mut &[u8]) -> Option<T> {
+        let take = (T::SIZE - self.len).min(input.len());
+        self.buf[self.len..self.len + take].copy_from_slice(&input[..take]);
+        self.len += take;
+        *input = &input[take..];
+        if self.len < T::SIZE {
+            return None;
+        }
+        self.len = 0;
+        Some(T::decode(&self.buf[..T::SIZE]))
+    }
+
+    /// Number of bytes buffered towards the next value.
+    pub const fn pending(&self) -> usize {
+        self.len
+    }
+
+    /// Drop any partially received value.
+    pub fn reset(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<T: Decode, const N: usize> Default for Decoder<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,123 @@
+//! # Runtime endianness
+//!
+//! Some formats choose their byte order at runtime with a byte order mark or a magic number.
+//! `Endianness` carries that choice, and the `detect_*` helpers derive it from the first bytes of
+//! a buffer.
+//! ## Example
+//! ```
+//! use endiantype::endianness::*;
+//! assert!(detect_utf16_bom(b"\xff\xfeh\x00i\x00") == Some(Endianness::Little));
+//! assert!(detect_utf32_bom(b"\x00\x00\xfe\xff") == Some(Endianness::Big));
+//! // TIFF files start with 0x002a in their own byte order after the "II"/"MM" marker.
+//! assert!(detect_magic(b"\x2a\x00", &[0x00, 0x2a]) == Some(Endianness::Little));
+//! assert!(detect_magic(b"\x00\x00", &[0x00, 0x2a]).is_none());
+//! // Fields are then read in the detected order.
+//! assert!(Endianness::Little.u16_from_bytes([0x2a, 0x00]) == 42);
+//! assert!(Endianness::Big.u32_to_bytes(8) == [0, 0, 0, 8]);
+//! // Code can branch on the target's byte order without `cfg(target_endian)`.
+//! if Endianness::NATIVE.is_little() {
+//!     assert!(Endianness::Little.is_native() && !Endianness::Big.is_native());
+//! }
+//! assert!(Endianness::NETWORK.reverse() == Endianness::Little);
+//! ```
+
+/// # Byte order
+/// A byte order chosen at runtime.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Endianness {
+    /// Byte order of the target.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endianness = Endianness::Little;
+    /// Byte order of the target.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endianness = Endianness::Big;
+
+    /// Byte order of network protocols.
+    pub const NETWORK: Endianness = Endianness::Big;
+
+    pub const fn is_big(self) -> bool {
+        matches!(self, Endianness::Big)
+    }
+
+    pub const fn is_little(self) -> bool {
+        matches!(self, Endianness::Little)
+    }
+
+    /// Whether values in this byte order can be used without swapping.
+    pub const fn is_native(self) -> bool {
+        matches!(
+            (self, Self::NATIVE),
+            (Endianness::Little, Endianness::Little) | (Endianness::Big, Endianness::Big)
+        )
+    }
+
+    /// The other byte order.
+    pub const fn reverse(self) -> Self {
+        match self {
+            Endianness::Little => Endianness::Big,
+            Endianness::Big => Endianness::Little,
+        }
+    }
+}
+
+/// Byte order of a UTF-16 byte order mark at the start of `bytes`.
+///
+/// Note that a UTF-32LE mark also starts with a UTF-16LE mark; check `detect_utf32_bom` first
+/// if both encodings are possible.
+pub fn detect_utf16_bom(bytes: &[u8]) -> Option<Endianness> {
+    detect_magic(bytes, &[0xfe, 0xff])
+}
+
+/// Byte order of a UTF-32 byte order mark at the start of `bytes`.
+pub fn detect_utf32_bom(bytes: &[u8]) -> Option<Endianness> {
+    detect_magic(bytes, &[0x00, 0x00, 0xfe, 0xff])
+}
+
+/// Byte order in which `bytes` starts with `magic`, where `magic` is given most significant byte
+/// first.
+///
+/// A magic number that reads the same in both orders is reported as big endian.
+pub fn detect_magic(bytes: &[u8], magic: &[u8]) -> Option<Endianness> {
+    let prefix = bytes.get(..magic.len())?;
+    if prefix == magic {
+        Some(Endianness::Big)
+    } else if prefix.iter().eq(magic.iter().rev()) {
+        Some(Endianness::Little)
+    } else {
+        None
+    }
+}
+
+macro_rules! impl_endianness_bytes {
+    ($type_name: ident, $from_name: ident, $to_name: ident) => {
+        impl Endianness {
+            #[inline]
+            pub fn $from_name(self, bytes: [u8; core::mem::size_of::<$type_name>()]) -> $type_name {
+                match self {
+                    Endianness::Little => $type_name::from_le_bytes(bytes),
+                    Endianness::Big => $type_name::from_be_bytes(bytes),
+                }
+            }
+
+            #[inline]
+            pub fn $to_name(self, value: $type_name) -> [u8; core::mem::size_of::<$type_name>()] {
+                match self {
+                    Endianness::Little => value.to_le_bytes(),
+                    Endianness::Big => value.to_be_bytes(),
+                }
+            }
+        }
+    };
+}
+
+impl_endianness_bytes!(u16, u16_from_bytes, u16_to_bytes);
+impl_endianness_bytes!(u32, u32_from_bytes, u32_to_bytes);
+impl_endianness_bytes!(u64, u64_from_bytes, u64_to_bytes);
+impl_endianness_bytes!(i16, i16_from_bytes, i16_to_bytes);
+impl_endianness_bytes!(i32, i32_from_bytes, i32_to_bytes);
+impl_endianness_bytes!(i64, i64_from_bytes, i64_to_bytes);
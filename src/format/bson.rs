@@ -0,0 +1,270 @@
+//! # BSON documents
+//!
+//! Length-prefix and element-header readers for BSON, as used by the MongoDB wire protocol.
+//! Documents, strings and binaries carry little-endian `i32` lengths; every length is checked
+//! against the enclosing buffer before it is used.
+//! ## Example
+//! ```
+//! use endiantype::format::bson::*;
+//! // {"a": 1i32, "s": "hi"}
+//! let doc = [
+//!     0x16, 0x00, 0x00, 0x00,
+//!     0x10, b'a', 0x00, 0x01, 0x00, 0x00, 0x00,
+//!     0x02, b's', 0x00, 0x03, 0x00, 0x00, 0x00, b'h', b'i', 0x00,
+//!     0x00,
+//! ];
+//! let (document, used) = Document::parse(&doc).unwrap();
+//! assert!(used == doc.len());
+//! let a = document.get(b"a").unwrap().unwrap();
+//! assert!(a.element_type == TYPE_INT32 && a.as_i32() == Some(1));
+//! let s = document.get(b"s").unwrap().unwrap();
+//! assert!(s.as_str() == Some("hi"));
+//! // A truncated document is rejected before any element is read.
+//! assert!(Document::parse(&doc[..10]) == Err(Error::InvalidLength(0x16)));
+//! ```
+use crate::io::EndianReader;
+use crate::types::{i32_le, i64_le, u64_le};
+use core::convert::TryFrom;
+
+pub const TYPE_DOUBLE: u8 = 0x01;
+pub const TYPE_STRING: u8 = 0x02;
+pub const TYPE_DOCUMENT: u8 = 0x03;
+pub const TYPE_ARRAY: u8 = 0x04;
+pub const TYPE_BINARY: u8 = 0x05;
+pub const TYPE_UNDEFINED: u8 = 0x06;
+pub const TYPE_OBJECT_ID: u8 = 0x07;
+pub const TYPE_BOOL: u8 = 0x08;
+pub const TYPE_DATETIME: u8 = 0x09;
+pub const TYPE_NULL: u8 = 0x0a;
+pub const TYPE_REGEX: u8 = 0x0b;
+pub const TYPE_DB_POINTER: u8 = 0x0c;
+pub const TYPE_JAVASCRIPT: u8 = 0x0d;
+pub const TYPE_SYMBOL: u8 = 0x0e;
+pub const TYPE_JAVASCRIPT_WITH_SCOPE: u8 = 0x0f;
+pub const TYPE_INT32: u8 = 0x10;
+pub const TYPE_TIMESTAMP: u8 = 0x11;
+pub const TYPE_INT64: u8 = 0x12;
+pub const TYPE_DECIMAL128: u8 = 0x13;
+pub const TYPE_MIN_KEY: u8 = 0xff;
+pub const TYPE_MAX_KEY: u8 = 0x7f;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of an element.
+    UnexpectedEof,
+    /// A length prefix is negative, too small, or larger than the enclosing buffer.
+    InvalidLength(i32),
+    /// A document or string is missing its terminating zero byte.
+    MissingTerminator,
+    /// An element has a type this module does not know the size of.
+    UnknownType(u8),
+}
+
+/// Read an `i32` length prefix of at least `min`, checking that `extra` bytes plus that length
+/// fit in the bytes left in `reader`; returns the whole size `extra + len`.
+fn read_len(reader: &EndianReader, min: i32, extra: usize) -> Result<usize, Error> {
+    let len = reader
+        .peek::<i32_le>()
+        .ok_or(Error::UnexpectedEof)?
+        .to_native();
+    match usize::try_from(len) {
+        Ok(size) if len >= min && size + extra <= reader.remaining() => Ok(size + extra),
+        _ => Err(Error::InvalidLength(len)),
+    }
+}
+
+/// # Document
+/// A validated document: its length prefix matches the buffer and it ends with a zero byte.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Document<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Document<'a> {
+    /// Parse the document at the start of `bytes`, returning it with its length.
+    pub fn parse(bytes: &'a [u8]) -> Result<(Self, usize), Error> {
+        let len = read_len(&EndianReader::new(bytes), 5, 0)?;
+        if bytes[len - 1] != 0 {
+            return Err(Error::MissingTerminator);
+        }
+        Ok((
+            Self {
+                bytes: &bytes[..len],
+            },
+            len,
+        ))
+    }
+
+    /// The whole document, including its length prefix and terminator.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn elements(&self) -> Elements<'a> {
+        Elements {
+            reader: EndianReader::new(&self.bytes[4..self.bytes.len() - 1]),
+        }
+    }
+
+    /// The first element named `name`, or `Ok(None)` if there is none.
+    pub fn get(&self, name: &[u8]) -> Result<Option<Element<'a>>, Error> {
+        for element in self.elements() {
+            let element = element?;
+            if element.name == name {
+                return Ok(Some(element));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// # Element
+/// An element header (type and name) with the raw bytes of its value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Element<'a> {
+    pub element_type: u8,
+    /// Name without its terminating zero byte.
+    pub name: &'a [u8],
+    pub value: &'a [u8],
+}
+
+impl<'a> Element<'a> {
+    pub fn as_i32(&self) -> Option<i32> {
+        self.scalar::<i32_le>(TYPE_INT32).map(|v| v.to_native())
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.element_type {
+            TYPE_INT64 | TYPE_DATETIME => EndianReader::new(self.value)
+                .read::<i64_le>()
+                .map(|v| v.to_native()),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        self.scalar::<u64_le>(TYPE_DOUBLE)
+            .map(|v| f64::from_bits(v.to_native()))
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match (self.element_type, self.value) {
+            (TYPE_BOOL, [b]) => Some(*b != 0),
+            _ => None,
+        }
+    }
+
+    /// The value of a string, JavaScript or symbol element.
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.element_type {
+            TYPE_STRING | TYPE_JAVASCRIPT | TYPE_SYMBOL => {
+                core::str::from_utf8(&self.value[4..self.value.len() - 1]).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// The value of an embedded document or array element.
+    pub fn as_document(&self) -> Option<Document<'a>> {
+        match self.element_type {
+            TYPE_DOCUMENT | TYPE_ARRAY => Some(Document { bytes: self.value }),
+            _ => None,
+        }
+    }
+
+    /// The subtype and data of a binary element.
+    pub fn as_binary(&self) -> Option<(u8, &'a [u8])> {
+        match self.element_type {
+            TYPE_BINARY => Some((self.value[4], &self.value[5..])),
+            _ => None,
+        }
+    }
+
+    fn scalar<T: crate::decoder::Decode>(&self, element_type: u8) -> Option<T> {
+        if self.element_type != element_type {
+            return None;
+        }
+        EndianReader::new(self.value).read()
+    }
+}
+
+/// # Element iterator
+/// Yields the elements of a document; iteration stops after the first error.
+#[derive(Clone, Debug)]
+pub struct Elements<'a> {
+    reader: EndianReader<'a>,
+}
+
+impl<'a> Elements<'a> {
+    fn read_cstr(&mut self) -> Result<&'a [u8], Error> {
+        let len = self
+            .reader
+            .rest()
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(Error::MissingTerminator)?;
+        let s = self.reader.read_bytes(len).unwrap();
+        self.reader.skip(1);
+        Ok(s)
+    }
+
+    /// Size of a string value, which must end with a zero byte.
+    fn string_len(&self) -> Result<usize, Error> {
+        let len = read_len(&self.reader, 1, 4)?;
+        if self.reader.rest()[len - 1] != 0 {
+            return Err(Error::MissingTerminator);
+        }
+        Ok(len)
+    }
+
+    fn read_element(&mut self) -> Result<Element<'a>, Error> {
+        let element_type = self.reader.read_u8().unwrap();
+        let name = self.read_cstr()?;
+        let start = self.reader.rest();
+        let len = match element_type {
+            TYPE_UNDEFINED | TYPE_NULL | TYPE_MIN_KEY | TYPE_MAX_KEY => 0,
+            TYPE_BOOL => 1,
+            TYPE_INT32 => 4,
+            TYPE_DOUBLE | TYPE_DATETIME | TYPE_TIMESTAMP | TYPE_INT64 => 8,
+            TYPE_OBJECT_ID => 12,
+            TYPE_DECIMAL128 => 16,
+            TYPE_STRING | TYPE_JAVASCRIPT | TYPE_SYMBOL => self.string_len()?,
+            TYPE_DOCUMENT | TYPE_ARRAY => Document::parse(start)?.1,
+            TYPE_JAVASCRIPT_WITH_SCOPE => read_len(&self.reader, 14, 0)?,
+            TYPE_BINARY => read_len(&self.reader, 0, 5)?,
+            TYPE_DB_POINTER => self.string_len()? + 12,
+            TYPE_REGEX => {
+                let pattern = self.read_cstr()?.len() + 1;
+                let options = self.read_cstr()?.len() + 1;
+                return Ok(Element {
+                    element_type,
+                    name,
+                    value: &start[..pattern + options],
+                });
+            }
+            _ => return Err(Error::UnknownType(element_type)),
+        };
+        let value = self.reader.read_bytes(len).ok_or(Error::UnexpectedEof)?;
+        Ok(Element {
+            element_type,
+            name,
+            value,
+        })
+    }
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = Result<Element<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.is_empty() {
+            return None;
+        }
+        let item = self.read_element();
+        if item.is_err() {
+            self.reader = EndianReader::new(&[]);
+        }
+        Some(item)
+    }
+}
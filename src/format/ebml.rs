@@ -0,0 +1,135 @@
+//! # EBML variable-size integers
+//!
+//! EBML (Matroska, WebM) encodes element IDs and sizes as VINTs: the number of leading zero bits
+//! in the first byte gives the length, a marker bit follows, and the remaining bits form a
+//! big-endian value. Element IDs keep their marker bit; data sizes drop it, and a size with all
+//! value bits set means "unknown".
+//! ## Example
+//! ```
+//! use endiantype::format::ebml::*;
+//! use endiantype::io::EndianWriter;
+//! // EBML header element: ID 0x1a45dfa3, size 0x23
+//! let (header, used) = ElementHeader::parse(&[0x1a, 0x45, 0xdf, 0xa3, 0xa3]).unwrap();
+//! assert!(header.id == 0x1a45dfa3 && header.size == Some(0x23) && used == 5);
+//! // Segment of unknown size
+//! let (size, _) = read_data_size(&[0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).unwrap();
+//! assert!(size.is_none());
+//!
+//! let mut buf = [0u8; 8];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! write_vint(&mut writer, 500).unwrap();
+//! assert!(writer.written() == [0x41, 0xf4]);
+//! assert!(read_vint(&[0x41, 0xf4]).unwrap() == (500, 2));
+//! ```
+use crate::io::EndianWriter;
+
+/// Largest value a VINT can carry; the all-ones 8-byte data size is reserved for "unknown".
+pub const MAX_VALUE: u64 = (1 << 56) - 2;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a VINT.
+    UnexpectedEof,
+    /// The first byte is zero, which would mean a VINT longer than 8 bytes.
+    InvalidMarker,
+    /// An element ID is longer than 4 bytes.
+    IdTooLong,
+}
+
+/// Read the raw bytes of a VINT: the value with its marker bit, and the length.
+fn read_raw(bytes: &[u8]) -> Result<(u64, usize), Error> {
+    let first = *bytes.first().ok_or(Error::UnexpectedEof)?;
+    if first == 0 {
+        return Err(Error::InvalidMarker);
+    }
+    let len = first.leading_zeros() as usize + 1;
+    let octets = bytes.get(..len).ok_or(Error::UnexpectedEof)?;
+    let raw = octets
+        .iter()
+        .fold(0u64, |acc, &byte| (acc << 8) | u64::from(byte));
+    Ok((raw, len))
+}
+
+/// Read a VINT, returning its value without the marker bit and the number of bytes used.
+pub fn read_vint(bytes: &[u8]) -> Result<(u64, usize), Error> {
+    let (raw, len) = read_raw(bytes)?;
+    Ok((raw & !(1 << (7 * len)), len))
+}
+
+/// Read an element ID, which keeps its marker bit, e.g. `0x1a45dfa3`.
+pub fn read_element_id(bytes: &[u8]) -> Result<(u32, usize), Error> {
+    let (raw, len) = read_raw(bytes)?;
+    if len > 4 {
+        return Err(Error::IdTooLong);
+    }
+    Ok((raw as u32, len))
+}
+
+/// Read an element data size, or `None` for the reserved "unknown size" value.
+pub fn read_data_size(bytes: &[u8]) -> Result<(Option<u64>, usize), Error> {
+    let (value, len) = read_vint(bytes)?;
+    let unknown = (1 << (7 * len)) - 1;
+    Ok((if value == unknown { None } else { Some(value) }, len))
+}
+
+/// Number of bytes of the shortest VINT holding `value`.
+pub fn encoded_len(value: u64) -> usize {
+    // All-ones values are reserved, so each length holds values up to 2^(7 * len) - 2.
+    (1..8).find(|len| value < (1 << (7 * len)) - 1).unwrap_or(8)
+}
+
+/// Write `value` as the shortest VINT, or `None` if it exceeds `MAX_VALUE`.
+pub fn write_vint(writer: &mut EndianWriter, value: u64) -> Option<()> {
+    write_vint_with_len(writer, value, encoded_len(value))
+}
+
+/// Write `value` as a VINT of exactly `len` bytes, as used to reserve space for sizes that are
+/// filled in later; `None` if it does not fit.
+pub fn write_vint_with_len(writer: &mut EndianWriter, value: u64, len: usize) -> Option<()> {
+    if !(1..=8).contains(&len) || value >= (1 << (7 * len)) - 1 {
+        return None;
+    }
+    let raw = value | (1 << (7 * len));
+    writer.write_bytes(&raw.to_be_bytes()[8 - len..])
+}
+
+/// Write the "unknown size" data size in `len` bytes.
+pub fn write_unknown_size(writer: &mut EndianWriter, len: usize) -> Option<()> {
+    if !(1..=8).contains(&len) {
+        return None;
+    }
+    let raw: u64 = (2 << (7 * len)) - 1;
+    writer.write_bytes(&raw.to_be_bytes()[8 - len..])
+}
+
+/// # Element header
+/// The ID and data size at the start of every EBML element.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ElementHeader {
+    pub id: u32,
+    /// `None` for elements of unknown size.
+    pub size: Option<u64>,
+}
+
+impl ElementHeader {
+    pub fn parse(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let (id, id_len) = read_element_id(bytes)?;
+        let (size, size_len) = read_data_size(&bytes[id_len..])?;
+        Ok((Self { id, size }, id_len + size_len))
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        let id = self.id.to_be_bytes();
+        let start = self.id.leading_zeros() as usize / 8;
+        // The marker bit of a valid ID accounts for exactly its significant bytes.
+        if read_raw(&id[start..]).ok()?.1 != 4 - start {
+            return None;
+        }
+        writer.write_bytes(&id[start..])?;
+        match self.size {
+            Some(size) => write_vint(writer, size),
+            None => write_unknown_size(writer, 8),
+        }
+    }
+}
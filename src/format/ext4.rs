@@ -0,0 +1,478 @@
+//! # ext4 superblock and group descriptors
+//!
+//! The ext2/3/4 superblock (1024 bytes at byte offset 1024 of the volume) and block group
+//! descriptors, all little endian, declared with `endian_struct!` so field offsets come from the
+//! declaration rather than copied offset tables. Field names follow the kernel's
+//! `struct ext4_super_block` and `struct ext4_group_desc` without the `s_`/`bg_` prefixes;
+//! arrays of `__le32` are kept as raw bytes, with accessors where they are useful.
+//!
+//! With the `metadata_csum` feature, the superblock and group descriptors carry CRC32C
+//! checksums. The crate has no CRC implementation, so the verification methods take the
+//! checksum as a hook: `crc32c(crc, data)` must continue the raw CRC32C `crc` over `data`,
+//! without the initial or final inversion, as the kernel's `crc32c()` does.
+//! ## Example
+//! ```
+//! use endiantype::format::ext4::*;
+//! use endiantype::io::*;
+//!
+//! fn crc32c(mut crc: u32, data: &[u8]) -> u32 {
+//!     for &byte in data {
+//!         crc ^= u32::from(byte);
+//!         for _ in 0..8 {
+//!             crc = (crc >> 1) ^ (0x82f6_3b78 & (crc & 1).wrapping_neg());
+//!         }
+//!     }
+//!     crc
+//! }
+//!
+//! assert!(Superblock::parse(&[0; Superblock::SIZE]) == Err(Error::BadMagic));
+//! let mut bytes = [0; Superblock::SIZE];
+//! bytes[0x38..0x3a].copy_from_slice(&MAGIC.to_le_bytes());
+//! let mut sb = Superblock::parse(&bytes).unwrap();
+//! sb.log_block_size = 2.into();
+//! sb.blocks_count_lo = 0x8000.into();
+//! sb.blocks_per_group = 0x8000.into();
+//! sb.feature_ro_compat = RO_COMPAT_METADATA_CSUM.into();
+//! sb.uuid = [7; 16];
+//! assert!(sb.block_size() == Some(4096) && sb.group_count() == 1 && sb.desc_size() == 32);
+//! assert!(!sb.verify_checksum(crc32c));
+//! sb.checksum = sb.compute_checksum(crc32c).into();
+//! assert!(sb.verify_checksum(crc32c));
+//!
+//! let mut raw = [0; 32];
+//! let mut desc = GroupDesc::parse(&raw, sb.desc_size()).unwrap();
+//! desc.block_bitmap_lo = 0x401.into();
+//! desc.write(&mut raw, sb.desc_size()).unwrap();
+//! desc.checksum = sb.compute_group_checksum(0, &raw, crc32c).unwrap().into();
+//! desc.write(&mut raw, sb.desc_size()).unwrap();
+//! assert!(desc.block_bitmap() == 0x401);
+//! assert!(sb.verify_group_checksum(0, &raw, crc32c));
+//! assert!(!sb.verify_group_checksum(1, &raw, crc32c));
+//! ```
+use crate::decoder::Decode;
+use crate::io::Encode;
+use crate::types::{u16_le, u32_le, u64_le, u8_le};
+
+/// Byte offset of the primary superblock in the volume.
+pub const SUPERBLOCK_OFFSET: usize = 1024;
+
+pub const MAGIC: u16 = 0xef53;
+
+pub const COMPAT_HAS_JOURNAL: u32 = 0x0004;
+pub const COMPAT_RESIZE_INODE: u32 = 0x0010;
+pub const COMPAT_DIR_INDEX: u32 = 0x0020;
+
+pub const INCOMPAT_FILETYPE: u32 = 0x0002;
+pub const INCOMPAT_RECOVER: u32 = 0x0004;
+pub const INCOMPAT_META_BG: u32 = 0x0010;
+pub const INCOMPAT_EXTENTS: u32 = 0x0040;
+pub const INCOMPAT_64BIT: u32 = 0x0080;
+pub const INCOMPAT_FLEX_BG: u32 = 0x0200;
+pub const INCOMPAT_CSUM_SEED: u32 = 0x2000;
+
+pub const RO_COMPAT_SPARSE_SUPER: u32 = 0x0001;
+pub const RO_COMPAT_LARGE_FILE: u32 = 0x0002;
+pub const RO_COMPAT_HUGE_FILE: u32 = 0x0008;
+pub const RO_COMPAT_GDT_CSUM: u32 = 0x0010;
+pub const RO_COMPAT_METADATA_CSUM: u32 = 0x0400;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer is shorter than the structure.
+    UnexpectedEof,
+    /// The superblock magic is not `MAGIC`.
+    BadMagic,
+    /// The descriptor size is neither 32 nor between 64 and 1024.
+    BadDescSize(u16),
+}
+
+crate::endian_struct! {
+    /// # Superblock
+    pub struct Superblock {
+        pub inodes_count: u32_le,
+        pub blocks_count_lo: u32_le,
+        pub r_blocks_count_lo: u32_le,
+        pub free_blocks_count_lo: u32_le,
+        pub free_inodes_count: u32_le,
+        pub first_data_block: u32_le,
+        /// Block size is `1024 << log_block_size`.
+        pub log_block_size: u32_le,
+        pub log_cluster_size: u32_le,
+        pub blocks_per_group: u32_le,
+        pub clusters_per_group: u32_le,
+        pub inodes_per_group: u32_le,
+        pub mtime: u32_le,
+        pub wtime: u32_le,
+        pub mnt_count: u16_le,
+        pub max_mnt_count: u16_le,
+        pub magic: u16_le,
+        pub state: u16_le,
+        pub errors: u16_le,
+        pub minor_rev_level: u16_le,
+        pub lastcheck: u32_le,
+        pub checkinterval: u32_le,
+        pub creator_os: u32_le,
+        pub rev_level: u32_le,
+        pub def_resuid: u16_le,
+        pub def_resgid: u16_le,
+        pub first_ino: u32_le,
+        pub inode_size: u16_le,
+        pub block_group_nr: u16_le,
+        pub feature_compat: u32_le,
+        pub feature_incompat: u32_le,
+        pub feature_ro_compat: u32_le,
+        pub uuid: [u8; 16],
+        pub volume_name: [u8; 16],
+        pub last_mounted: [u8; 64],
+        pub algorithm_usage_bitmap: u32_le,
+        pub prealloc_blocks: u8_le,
+        pub prealloc_dir_blocks: u8_le,
+        pub reserved_gdt_blocks: u16_le,
+        pub journal_uuid: [u8; 16],
+        pub journal_inum: u32_le,
+        pub journal_dev: u32_le,
+        pub last_orphan: u32_le,
+        /// Four `__le32` words; see `hash_seed`.
+        pub hash_seed: [u8; 16],
+        pub def_hash_version: u8_le,
+        pub jnl_backup_type: u8_le,
+        /// Size of a group descriptor when `INCOMPAT_64BIT` is set.
+        pub desc_size: u16_le,
+        pub default_mount_opts: u32_le,
+        pub first_meta_bg: u32_le,
+        pub mkfs_time: u32_le,
+        /// Seventeen `__le32` words: a backup of the journal inode's block map and size.
+        pub jnl_blocks: [u8; 68],
+        pub blocks_count_hi: u32_le,
+        pub r_blocks_count_hi: u32_le,
+        pub free_blocks_count_hi: u32_le,
+        pub min_extra_isize: u16_le,
+        pub want_extra_isize: u16_le,
+        pub flags: u32_le,
+        pub raid_stride: u16_le,
+        pub mmp_update_interval: u16_le,
+        pub mmp_block: u64_le,
+        pub raid_stripe_width: u32_le,
+        pub log_groups_per_flex: u8_le,
+        pub checksum_type: u8_le,
+        pub encryption_level: u8_le,
+        pub reserved_pad: u8_le,
+        pub kbytes_written: u64_le,
+        pub snapshot_inum: u32_le,
+        pub snapshot_id: u32_le,
+        pub snapshot_r_blocks_count: u64_le,
+        pub snapshot_list: u32_le,
+        pub error_count: u32_le,
+        pub first_error_time: u32_le,
+        pub first_error_ino: u32_le,
+        pub first_error_block: u64_le,
+        pub first_error_func: [u8; 32],
+        pub first_error_line: u32_le,
+        pub last_error_time: u32_le,
+        pub last_error_ino: u32_le,
+        pub last_error_line: u32_le,
+        pub last_error_block: u64_le,
+        pub last_error_func: [u8; 32],
+        pub mount_opts: [u8; 64],
+        pub usr_quota_inum: u32_le,
+        pub grp_quota_inum: u32_le,
+        pub overhead_clusters: u32_le,
+        /// Two `__le32` group numbers of the sparse_super2 backups.
+        pub backup_bgs: [u8; 8],
+        pub encrypt_algos: [u8; 4],
+        pub encrypt_pw_salt: [u8; 16],
+        pub lpf_ino: u32_le,
+        pub prj_quota_inum: u32_le,
+        /// Seed of the metadata checksums when `INCOMPAT_CSUM_SEED` is set.
+        pub checksum_seed: u32_le,
+        pub wtime_hi: u8_le,
+        pub mtime_hi: u8_le,
+        pub mkfs_time_hi: u8_le,
+        pub lastcheck_hi: u8_le,
+        pub first_error_time_hi: u8_le,
+        pub last_error_time_hi: u8_le,
+        pub first_error_errcode: u8_le,
+        pub last_error_errcode: u8_le,
+        pub encoding: u16_le,
+        pub encoding_flags: u16_le,
+        pub orphan_file_inum: u32_le,
+        pub reserved: [u8; 376],
+        /// CRC32C of the preceding bytes.
+        pub checksum: u32_le,
+    }
+}
+
+impl Superblock {
+    pub const SIZE: usize = <Self as Decode>::SIZE;
+
+    /// Parse a superblock from its 1024 bytes, starting at `SUPERBLOCK_OFFSET` of the volume.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = bytes.get(..Self::SIZE).ok_or(Error::UnexpectedEof)?;
+        let superblock = Self::decode(bytes);
+        if superblock.magic != MAGIC {
+            return Err(Error::BadMagic);
+        }
+        Ok(superblock)
+    }
+
+    pub fn has_compat(&self, feature: u32) -> bool {
+        self.feature_compat.to_native() & feature != 0
+    }
+
+    pub fn has_incompat(&self, feature: u32) -> bool {
+        self.feature_incompat.to_native() & feature != 0
+    }
+
+    pub fn has_ro_compat(&self, feature: u32) -> bool {
+        self.feature_ro_compat.to_native() & feature != 0
+    }
+
+    /// Block size in bytes, `1024 << log_block_size`, or `None` if a corrupted `log_block_size`
+    /// makes it overflow a `u64`.
+    pub fn block_size(&self) -> Option<u64> {
+        let shift = self.log_block_size.to_native().checked_add(10)?;
+        1u64.checked_shl(shift)
+    }
+
+    pub fn blocks_count(&self) -> u64 {
+        self.join(self.blocks_count_lo, self.blocks_count_hi)
+    }
+
+    pub fn r_blocks_count(&self) -> u64 {
+        self.join(self.r_blocks_count_lo, self.r_blocks_count_hi)
+    }
+
+    pub fn free_blocks_count(&self) -> u64 {
+        self.join(self.free_blocks_count_lo, self.free_blocks_count_hi)
+    }
+
+    /// Number of block groups, or 0 if `blocks_per_group` is 0 or `first_data_block` is past
+    /// the end of the file system.
+    pub fn group_count(&self) -> u64 {
+        let first_data_block = u64::from(self.first_data_block.to_native());
+        let blocks = match self.blocks_count().checked_sub(first_data_block) {
+            Some(blocks) => blocks,
+            None => return 0,
+        };
+        match u64::from(self.blocks_per_group.to_native()) {
+            0 => 0,
+            per_group => blocks.div_ceil(per_group),
+        }
+    }
+
+    /// Size of a group descriptor in bytes: 32 without `INCOMPAT_64BIT`.
+    pub fn desc_size(&self) -> u16 {
+        if self.has_incompat(INCOMPAT_64BIT) {
+            self.desc_size.to_native()
+        } else {
+            32
+        }
+    }
+
+    pub fn hash_seed(&self) -> [u32; 4] {
+        let mut seed = [0; 4];
+        for (word, bytes) in seed.iter_mut().zip(self.hash_seed.chunks_exact(4)) {
+            *word = u32_le::decode(bytes).to_native();
+        }
+        seed
+    }
+
+    /// Raw CRC32C of the superblock up to the checksum field, as stored in `checksum`.
+    pub fn compute_checksum<F: FnMut(u32, &[u8]) -> u32>(&self, mut crc32c: F) -> u32 {
+        let mut bytes = [0; Self::SIZE];
+        self.encode(&mut bytes);
+        crc32c(!0, &bytes[..Self::SIZE - 4])
+    }
+
+    /// Whether `checksum` is correct; always true without `RO_COMPAT_METADATA_CSUM`.
+    pub fn verify_checksum<F: FnMut(u32, &[u8]) -> u32>(&self, crc32c: F) -> bool {
+        !self.has_ro_compat(RO_COMPAT_METADATA_CSUM)
+            || self.compute_checksum(crc32c) == self.checksum
+    }
+
+    /// Seed of all metadata checksums: `checksum_seed`, or the CRC32C of the UUID.
+    pub fn csum_seed<F: FnMut(u32, &[u8]) -> u32>(&self, mut crc32c: F) -> u32 {
+        if self.has_incompat(INCOMPAT_CSUM_SEED) {
+            self.checksum_seed.to_native()
+        } else {
+            crc32c(!0, &self.uuid)
+        }
+    }
+
+    /// Checksum of descriptor `group`, as stored in its `checksum` field with `metadata_csum`.
+    /// `desc` holds the raw descriptor, which is checksummed over all `desc_size` bytes with the
+    /// checksum field taken as zero, including any bytes past the fields of `GroupDesc`. Returns
+    /// `None` if `desc` is shorter than `desc_size`.
+    /// ## Example
+    /// ```
+    /// use endiantype::format::ext4::*;
+    /// # fn crc32c(mut crc: u32, data: &[u8]) -> u32 {
+    /// #     for &byte in data {
+    /// #         crc ^= u32::from(byte);
+    /// #         for _ in 0..8 {
+    /// #             crc = (crc >> 1) ^ (0x82f6_3b78 & (crc & 1).wrapping_neg());
+    /// #         }
+    /// #     }
+    /// #     crc
+    /// # }
+    /// let mut bytes = [0; Superblock::SIZE];
+    /// bytes[0x38..0x3a].copy_from_slice(&MAGIC.to_le_bytes());
+    /// let mut sb = Superblock::parse(&bytes).unwrap();
+    /// sb.feature_incompat = INCOMPAT_64BIT.into();
+    /// sb.feature_ro_compat = RO_COMPAT_METADATA_CSUM.into();
+    /// sb.desc_size = 128.into();
+    ///
+    /// let mut raw = [0; 128];
+    /// raw[100] = 0x5a;
+    /// let mut desc = GroupDesc::parse(&raw, sb.desc_size()).unwrap();
+    /// desc.checksum = sb.compute_group_checksum(3, &raw, crc32c).unwrap().into();
+    /// desc.write(&mut raw, sb.desc_size()).unwrap();
+    /// assert!(raw[100] == 0x5a && sb.verify_group_checksum(3, &raw, crc32c));
+    /// raw[100] = 0;
+    /// assert!(!sb.verify_group_checksum(3, &raw, crc32c));
+    /// assert!(sb.compute_group_checksum(3, &raw[..64], crc32c).is_none());
+    /// ```
+    pub fn compute_group_checksum<F: FnMut(u32, &[u8]) -> u32>(
+        &self,
+        group: u32,
+        desc: &[u8],
+        mut crc32c: F,
+    ) -> Option<u16> {
+        let desc = desc.get(..usize::from(self.desc_size()))?;
+        let head = desc.get(..GroupDesc::CHECKSUM_OFFSET)?;
+        let tail = desc.get(GroupDesc::CHECKSUM_OFFSET + 2..)?;
+        let seed = self.csum_seed(&mut crc32c);
+        let crc = crc32c(seed, &group.to_le_bytes());
+        let crc = crc32c(crc, head);
+        let crc = crc32c(crc, &[0; 2]);
+        Some(crc32c(crc, tail) as u16)
+    }
+
+    /// Whether the checksum stored in the raw descriptor `desc` of group `group` is correct;
+    /// always true without `RO_COMPAT_METADATA_CSUM`, and false if `desc` is shorter than
+    /// `desc_size`. The older `RO_COMPAT_GDT_CSUM` CRC16 is not checked.
+    pub fn verify_group_checksum<F: FnMut(u32, &[u8]) -> u32>(
+        &self,
+        group: u32,
+        desc: &[u8],
+        crc32c: F,
+    ) -> bool {
+        let offset = GroupDesc::CHECKSUM_OFFSET;
+        !self.has_ro_compat(RO_COMPAT_METADATA_CSUM)
+            || self
+                .compute_group_checksum(group, desc, crc32c)
+                .is_some_and(|crc| crc == u16_le::decode(&desc[offset..offset + 2]))
+    }
+
+    fn join(&self, lo: u32_le, hi: u32_le) -> u64 {
+        let hi = if self.has_incompat(INCOMPAT_64BIT) {
+            hi.to_native()
+        } else {
+            0
+        };
+        u64::from(hi) << 32 | u64::from(lo.to_native())
+    }
+}
+
+crate::endian_struct! {
+    /// # Block group descriptor
+    /// The 64-byte descriptor of `INCOMPAT_64BIT` volumes; the `_hi` fields are zero when parsed
+    /// from a 32-byte descriptor.
+    #[derive(Default)]
+    pub struct GroupDesc {
+        pub block_bitmap_lo: u32_le,
+        pub inode_bitmap_lo: u32_le,
+        pub inode_table_lo: u32_le,
+        pub free_blocks_count_lo: u16_le,
+        pub free_inodes_count_lo: u16_le,
+        pub used_dirs_count_lo: u16_le,
+        pub flags: u16_le,
+        pub exclude_bitmap_lo: u32_le,
+        pub block_bitmap_csum_lo: u16_le,
+        pub inode_bitmap_csum_lo: u16_le,
+        pub itable_unused_lo: u16_le,
+        pub checksum: u16_le,
+        pub block_bitmap_hi: u32_le,
+        pub inode_bitmap_hi: u32_le,
+        pub inode_table_hi: u32_le,
+        pub free_blocks_count_hi: u16_le,
+        pub free_inodes_count_hi: u16_le,
+        pub used_dirs_count_hi: u16_le,
+        pub itable_unused_hi: u16_le,
+        pub exclude_bitmap_hi: u32_le,
+        pub block_bitmap_csum_hi: u16_le,
+        pub inode_bitmap_csum_hi: u16_le,
+        pub reserved: u32_le,
+    }
+}
+
+impl GroupDesc {
+    pub const SIZE: usize = <Self as Decode>::SIZE;
+
+    const CHECKSUM_OFFSET: usize = 0x1e;
+
+    /// Parse a descriptor of `desc_size` bytes, as given by `Superblock::desc_size`. Bytes past
+    /// the first 64 of larger descriptors are ignored, but still count towards the checksum.
+    pub fn parse(bytes: &[u8], desc_size: u16) -> Result<Self, Error> {
+        let len = match desc_size {
+            32 => 32,
+            64..=1024 => Self::SIZE,
+            _ => return Err(Error::BadDescSize(desc_size)),
+        };
+        let bytes = bytes.get(..len).ok_or(Error::UnexpectedEof)?;
+        let mut padded = [0; Self::SIZE];
+        padded[..len].copy_from_slice(bytes);
+        Ok(Self::decode(&padded))
+    }
+
+    /// Write the descriptor back into the first `desc_size` bytes of `bytes`: only the first 32
+    /// bytes for 32-byte descriptors, and only the first 64 of larger ones, leaving the rest as
+    /// they are.
+    pub fn write(&self, bytes: &mut [u8], desc_size: u16) -> Result<(), Error> {
+        let len = match desc_size {
+            32 => 32,
+            64..=1024 => Self::SIZE,
+            _ => return Err(Error::BadDescSize(desc_size)),
+        };
+        let bytes = bytes.get_mut(..len).ok_or(Error::UnexpectedEof)?;
+        let mut padded = [0; Self::SIZE];
+        self.encode(&mut padded);
+        bytes.copy_from_slice(&padded[..len]);
+        Ok(())
+    }
+
+    pub fn block_bitmap(&self) -> u64 {
+        join(self.block_bitmap_lo, self.block_bitmap_hi)
+    }
+
+    pub fn inode_bitmap(&self) -> u64 {
+        join(self.inode_bitmap_lo, self.inode_bitmap_hi)
+    }
+
+    pub fn inode_table(&self) -> u64 {
+        join(self.inode_table_lo, self.inode_table_hi)
+    }
+
+    pub fn free_blocks_count(&self) -> u32 {
+        join16(self.free_blocks_count_lo, self.free_blocks_count_hi)
+    }
+
+    pub fn free_inodes_count(&self) -> u32 {
+        join16(self.free_inodes_count_lo, self.free_inodes_count_hi)
+    }
+
+    pub fn used_dirs_count(&self) -> u32 {
+        join16(self.used_dirs_count_lo, self.used_dirs_count_hi)
+    }
+}
+
+fn join(lo: u32_le, hi: u32_le) -> u64 {
+    u64::from(hi.to_native()) << 32 | u64::from(lo.to_native())
+}
+
+fn join16(lo: u16_le, hi: u16_le) -> u32 {
+    u32::from(hi.to_native()) << 16 | u32::from(lo.to_native())
+}
+
+const _: () = assert!(Superblock::SIZE == 1024 && GroupDesc::SIZE == 64);
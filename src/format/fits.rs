@@ -0,0 +1,219 @@
+//! # FITS blocks and data arrays
+//!
+//! A FITS file is a sequence of 2880-byte blocks. Each header is a run of 80-character ASCII
+//! cards ending with `END`, padded to a whole block; the data array that follows is stored big
+//! endian, with the sample type given by `BITPIX` (8, 16, 32 or 64 for integers, -32 or -64 for
+//! IEEE floats), and is also padded to a whole block. `to_native` converts a data array into a
+//! native slice in one pass.
+//! ## Example
+//! ```
+//! use endiantype::format::fits::*;
+//! let mut file = vec![b' '; 2 * BLOCK_SIZE];
+//! for (i, card) in ["SIMPLE  =                    T", "BITPIX  =                  -32",
+//!                   "NAXIS   =                    1", "NAXIS1  =                    2", "END"]
+//!     .iter()
+//!     .enumerate()
+//! {
+//!     file[i * CARD_SIZE..][..card.len()].copy_from_slice(card.as_bytes());
+//! }
+//! file[BLOCK_SIZE..][..8].copy_from_slice(&[0x3f, 0xc0, 0, 0, 0xc0, 0x20, 0, 0]);
+//!
+//! let header = Header::parse(&file).unwrap();
+//! assert!(header.bitpix == -32 && header.axes() == [2] && header.data_len() == Some(8));
+//! assert!(header.padded_data_len() == Some(BLOCK_SIZE as u64));
+//! assert!(header.header_len == BLOCK_SIZE && blocks(&file).count() == 2);
+//!
+//! let mut data = [0f32; 2];
+//! assert!(to_native(&file[header.header_len..], &mut data) == 2);
+//! assert!(data == [1.5, -2.5]);
+//! ```
+use core::convert::TryFrom;
+
+pub const BLOCK_SIZE: usize = 2880;
+pub const CARD_SIZE: usize = 80;
+
+/// Most axes `Header::parse` accepts. The standard allows up to 999, but data arrays with more
+/// than 8 are not supported.
+pub const MAX_AXES: usize = 8;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The header has no `END` card.
+    MissingEnd,
+    /// A mandatory keyword is missing or its value is not a valid integer.
+    BadKeyword(&'static str),
+    /// `BITPIX` is not one of the six valid values.
+    BadBitpix(i64),
+    /// `NAXIS` is negative or above `MAX_AXES`.
+    TooManyAxes(i64),
+}
+
+/// Iterate over the whole blocks of a file.
+pub fn blocks(file: &[u8]) -> impl Iterator<Item = &[u8; BLOCK_SIZE]> {
+    file.chunks_exact(BLOCK_SIZE)
+        .map(|block| <&[u8; BLOCK_SIZE]>::try_from(block).unwrap())
+}
+
+/// # Header card
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Card<'a> {
+    /// The keyword, without trailing spaces.
+    pub keyword: &'a str,
+    /// The text after `= ` with the comment still attached, or `None` for commentary cards.
+    pub value: Option<&'a str>,
+}
+
+impl<'a> Card<'a> {
+    /// Parse an 80-byte card; non-ASCII bytes give an empty keyword.
+    pub fn parse(card: &'a [u8; CARD_SIZE]) -> Self {
+        let text = core::str::from_utf8(card).unwrap_or("");
+        let keyword = text.get(..8).unwrap_or("").trim_end();
+        let value = match text.get(8..10) {
+            Some("= ") => text.get(10..),
+            _ => None,
+        };
+        Self { keyword, value }
+    }
+
+    /// The value as an integer, ignoring any comment after `/`.
+    pub fn int_value(&self) -> Option<i64> {
+        let value = self.value?;
+        let value = value.split('/').next().unwrap_or(value).trim();
+        value.parse().ok()
+    }
+}
+
+/// Iterate over the cards of a header, up to but excluding `END`.
+pub fn cards(header: &[u8]) -> impl Iterator<Item = Card<'_>> {
+    header
+        .chunks_exact(CARD_SIZE)
+        .map(|card| Card::parse(<&[u8; CARD_SIZE]>::try_from(card).unwrap()))
+        .take_while(|card| card.keyword != "END")
+}
+
+/// # Header summary
+/// The mandatory keywords describing the data array of a header and data unit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub bitpix: i64,
+    /// Number of axes, the first `naxis` entries of `axes`.
+    pub naxis: usize,
+    pub axes: [u64; MAX_AXES],
+    /// Length of the header in bytes, a multiple of `BLOCK_SIZE`.
+    pub header_len: usize,
+}
+
+impl Header {
+    /// Parse the header at the start of `bytes`. Data arrays with more than `MAX_AXES` axes are
+    /// rejected.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let end = bytes
+            .chunks_exact(CARD_SIZE)
+            .position(|card| card.starts_with(b"END "))
+            .ok_or(Error::MissingEnd)?;
+        let header_len = ((end + 1) * CARD_SIZE).div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+        let int = |keyword: &'static str| {
+            cards(bytes)
+                .find(|card| card.keyword == keyword)
+                .and_then(|card| card.int_value())
+                .ok_or(Error::BadKeyword(keyword))
+        };
+        let bitpix = int("BITPIX")?;
+        if ![8, 16, 32, 64, -32, -64].contains(&bitpix) {
+            return Err(Error::BadBitpix(bitpix));
+        }
+        let naxis = int("NAXIS")?;
+        let mut axes = [0; MAX_AXES];
+        if naxis < 0 || naxis as usize > MAX_AXES {
+            return Err(Error::TooManyAxes(naxis));
+        }
+        const NAMES: [&str; MAX_AXES] = [
+            "NAXIS1", "NAXIS2", "NAXIS3", "NAXIS4", "NAXIS5", "NAXIS6", "NAXIS7", "NAXIS8",
+        ];
+        for (axis, name) in axes.iter_mut().zip(&NAMES).take(naxis as usize) {
+            *axis = u64::try_from(int(name)?).map_err(|_| Error::BadKeyword(name))?;
+        }
+        Ok(Self {
+            bitpix,
+            naxis: naxis as usize,
+            axes,
+            header_len,
+        })
+    }
+
+    pub fn axes(&self) -> &[u64] {
+        &self.axes[..self.naxis]
+    }
+
+    /// Size of one sample in bytes.
+    pub fn sample_size(&self) -> usize {
+        (self.bitpix.unsigned_abs() / 8) as usize
+    }
+
+    /// Size of the data array in bytes, without padding; 0 if there are no axes, and `None` if
+    /// the axes of the header multiply out to more than `u64::MAX` bytes.
+    pub fn data_len(&self) -> Option<u64> {
+        if self.naxis == 0 {
+            return Some(0);
+        }
+        self.axes()
+            .iter()
+            .try_fold(self.sample_size() as u64, |len, &axis| {
+                len.checked_mul(axis)
+            })
+    }
+
+    /// Size of the data array padded to whole blocks, or `None` if it does not fit in a `u64`.
+    pub fn padded_data_len(&self) -> Option<u64> {
+        self.data_len()?
+            .div_ceil(BLOCK_SIZE as u64)
+            .checked_mul(BLOCK_SIZE as u64)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// # Sample type
+/// The native types of FITS data arrays: `u8`, `i16`, `i32`, `i64`, `f32` and `f64`.
+pub trait Sample: Copy + sealed::Sealed {
+    /// The matching `BITPIX` value.
+    const BITPIX: i64;
+
+    #[doc(hidden)]
+    fn from_be_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_sample {
+    ($($type_name: ident = $bitpix: expr),*) => {
+        $(
+            impl sealed::Sealed for $type_name {}
+
+            impl Sample for $type_name {
+                const BITPIX: i64 = $bitpix;
+
+                #[inline]
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    let mut raw = [0; core::mem::size_of::<$type_name>()];
+                    raw.copy_from_slice(bytes);
+                    $type_name::from_be_bytes(raw)
+                }
+            }
+        )*
+    };
+}
+
+impl_sample!(u8 = 8, i16 = 16, i32 = 32, i64 = 64, f32 = -32, f64 = -64);
+
+/// Convert big-endian samples to native; returns the number converted, limited by both
+/// buffers.
+pub fn to_native<T: Sample>(data: &[u8], dst: &mut [T]) -> usize {
+    let size = core::mem::size_of::<T>();
+    let len = (data.len() / size).min(dst.len());
+    for (dst, bytes) in dst[..len].iter_mut().zip(data.chunks_exact(size)) {
+        *dst = T::from_be_slice(bytes);
+    }
+    len
+}
@@ -0,0 +1,252 @@
+//! # Git pack files
+//!
+//! Readers for git's version 2 pack index (`.idx`) and the headers of pack files (`.pack`). The
+//! index is a 256-entry big-endian fan-out table followed by sorted object IDs, CRC-32s and
+//! offsets, where offsets of 2 GiB and above move to a table of big-endian `u64`s.
+//! Object IDs are 20-byte SHA-1 hashes.
+//! ## Example
+//! ```
+//! use endiantype::format::git::*;
+//! let mut idx = Vec::new();
+//! idx.extend_from_slice(b"\xfftOc\x00\x00\x00\x02");
+//! for i in 0..256 {
+//!     idx.extend_from_slice(&(if i < 0xab { 0u32 } else { 1 }).to_be_bytes());
+//! }
+//! idx.extend_from_slice(&[0xab; 20]); // object ID
+//! idx.extend_from_slice(&0x1234_5678u32.to_be_bytes()); // CRC-32
+//! idx.extend_from_slice(&12u32.to_be_bytes()); // offset
+//! idx.extend_from_slice(&[0; 40]); // checksums
+//!
+//! let index = PackIndex::parse(&idx).unwrap();
+//! assert!(index.len() == 1);
+//! let i = index.find(&[0xab; 20]).unwrap();
+//! assert!(index.offset(i) == Some(12) && index.crc32(i) == Some(0x1234_5678));
+//! assert!(index.find(&[0xac; 20]).is_none());
+//!
+//! let header = PackHeader::parse(b"PACK\x00\x00\x00\x02\x00\x00\x00\x03").unwrap();
+//! assert!(header.version == 2 && header.objects == 3);
+//! // A blob of 300 bytes: type 3, size split into 4 + 7 bits
+//! let (object, used) = ObjectHeader::parse(&[0xbc, 0x12]).unwrap();
+//! assert!(object.kind == ObjectType::Blob && object.size == 300 && used == 2);
+//! ```
+use crate::io::EndianReader;
+use crate::types::{u32_be, u64_be};
+
+/// Size of a SHA-1 object ID.
+pub const OID_LEN: usize = 20;
+
+const INDEX_MAGIC: &[u8; 4] = b"\xfftOc";
+const FANOUT_LEN: usize = 256 * 4;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The magic number or version is not supported.
+    BadHeader,
+    /// The buffer is shorter than its tables require.
+    UnexpectedEof,
+    /// An object header uses the reserved type 0 or 5.
+    BadObjectType(u8),
+    /// An object size or delta offset does not fit in a `u64`.
+    Overflow,
+}
+
+/// # Pack index
+/// A version 2 pack index in memory.
+#[derive(Copy, Clone, Debug)]
+pub struct PackIndex<'a> {
+    fanout: &'a [u8],
+    oids: &'a [u8],
+    crcs: &'a [u8],
+    offsets: &'a [u8],
+    large_offsets: &'a [u8],
+}
+
+impl<'a> PackIndex<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let mut reader = EndianReader::new(data);
+        if reader.read_bytes(4) != Some(&INDEX_MAGIC[..])
+            || reader.read::<u32_be>().map(|v| v.to_native()) != Some(2)
+        {
+            return Err(Error::BadHeader);
+        }
+        let fanout = reader.read_bytes(FANOUT_LEN).ok_or(Error::UnexpectedEof)?;
+        let count = u32_at(fanout, FANOUT_LEN - 4).unwrap() as usize;
+        let table = |size: usize| count.checked_mul(size).ok_or(Error::UnexpectedEof);
+        let oids = reader
+            .read_bytes(table(OID_LEN)?)
+            .ok_or(Error::UnexpectedEof)?;
+        let crcs = reader.read_bytes(table(4)?).ok_or(Error::UnexpectedEof)?;
+        let offsets = reader.read_bytes(table(4)?).ok_or(Error::UnexpectedEof)?;
+        // What is left is the large offset table followed by two checksums.
+        let rest = reader.rest();
+        let large_len = rest
+            .len()
+            .checked_sub(2 * OID_LEN)
+            .ok_or(Error::UnexpectedEof)?;
+        Ok(Self {
+            fanout,
+            oids,
+            crcs,
+            offsets,
+            large_offsets: &rest[..large_len],
+        })
+    }
+
+    /// Number of objects in the pack.
+    pub fn len(&self) -> usize {
+        self.oids.len() / OID_LEN
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.oids.is_empty()
+    }
+
+    /// Number of objects whose ID starts with a byte less than or equal to `first_byte`.
+    pub fn fanout(&self, first_byte: u8) -> u32 {
+        let i = usize::from(first_byte) * 4;
+        u32_at(self.fanout, i).unwrap()
+    }
+
+    pub fn oid(&self, index: usize) -> Option<&'a [u8]> {
+        let start = index.checked_mul(OID_LEN)?;
+        self.oids.get(start..start.checked_add(OID_LEN)?)
+    }
+
+    pub fn crc32(&self, index: usize) -> Option<u32> {
+        u32_at(self.crcs, index.checked_mul(4)?)
+    }
+
+    /// Offset of the object in the pack file.
+    pub fn offset(&self, index: usize) -> Option<u64> {
+        let offset = u32_at(self.offsets, index.checked_mul(4)?)?;
+        if offset & 0x8000_0000 == 0 {
+            return Some(u64::from(offset));
+        }
+        let large = ((offset & 0x7fff_ffff) as usize).checked_mul(8)?;
+        let mut reader = EndianReader::new(self.large_offsets.get(large..)?);
+        reader.read::<u64_be>().map(|v| v.to_native())
+    }
+
+    /// Index of the object with ID `oid`, found through the fan-out table.
+    pub fn find(&self, oid: &[u8]) -> Option<usize> {
+        let first = *oid.first()?;
+        let end = self.fanout(first) as usize;
+        let start = match first {
+            0 => 0,
+            _ => self.fanout(first - 1) as usize,
+        };
+        let mut range = start..end.min(self.len());
+        while range.start < range.end {
+            let mid = range.start + (range.end - range.start) / 2;
+            match self.oid(mid)?.cmp(oid) {
+                core::cmp::Ordering::Less => range.start = mid + 1,
+                core::cmp::Ordering::Greater => range.end = mid,
+                core::cmp::Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    EndianReader::new(bytes.get(offset..)?)
+        .read::<u32_be>()
+        .map(|v| v.to_native())
+}
+
+/// # Pack file header
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PackHeader {
+    pub version: u32,
+    pub objects: u32,
+}
+
+impl PackHeader {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = EndianReader::new(data);
+        if reader.read_bytes(4) != Some(&b"PACK"[..]) {
+            return Err(Error::BadHeader);
+        }
+        let version = reader.read::<u32_be>().ok_or(Error::UnexpectedEof)?;
+        let objects = reader.read::<u32_be>().ok_or(Error::UnexpectedEof)?;
+        match version.to_native() {
+            2 | 3 => Ok(Self {
+                version: version.to_native(),
+                objects: objects.to_native(),
+            }),
+            _ => Err(Error::BadHeader),
+        }
+    }
+}
+
+/// # Object type
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ObjectType {
+    Commit,
+    Tree,
+    Blob,
+    Tag,
+    OfsDelta,
+    RefDelta,
+}
+
+/// # Pack object header
+/// The type and inflated size preceding each object in a pack.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ObjectHeader {
+    pub kind: ObjectType,
+    pub size: u64,
+}
+
+impl ObjectHeader {
+    /// Parse an object header, returning it with the number of bytes it used.
+    pub fn parse(data: &[u8]) -> Result<(Self, usize), Error> {
+        let first = *data.first().ok_or(Error::UnexpectedEof)?;
+        let kind = match (first >> 4) & 7 {
+            1 => ObjectType::Commit,
+            2 => ObjectType::Tree,
+            3 => ObjectType::Blob,
+            4 => ObjectType::Tag,
+            6 => ObjectType::OfsDelta,
+            7 => ObjectType::RefDelta,
+            kind => return Err(Error::BadObjectType(kind)),
+        };
+        let mut size = u64::from(first & 0x0f);
+        let mut shift = 4;
+        let mut used = 1;
+        let mut more = first & 0x80 != 0;
+        while more {
+            let byte = *data.get(used).ok_or(Error::UnexpectedEof)?;
+            if shift > 57 {
+                return Err(Error::Overflow);
+            }
+            size |= u64::from(byte & 0x7f) << shift;
+            shift += 7;
+            used += 1;
+            more = byte & 0x80 != 0;
+        }
+        Ok((Self { kind, size }, used))
+    }
+}
+
+/// Parse the base offset of an `OfsDelta` object, which follows its header; the base object
+/// starts this many bytes before the delta object.
+pub fn parse_ofs_delta_offset(data: &[u8]) -> Result<(u64, usize), Error> {
+    let mut used = 0;
+    let mut offset: u64 = 0;
+    loop {
+        let byte = *data.get(used).ok_or(Error::UnexpectedEof)?;
+        if used > 0 {
+            offset = offset
+                .checked_add(1)
+                .and_then(|o| o.checked_mul(128))
+                .ok_or(Error::Overflow)?;
+        }
+        offset |= u64::from(byte & 0x7f);
+        used += 1;
+        if byte & 0x80 == 0 {
+            return Ok((offset, used));
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! # Mach-O headers
+//!
+//! Mach-O files start with a magic number that gives both the word size and the byte order:
+//! `MH_MAGIC` and `MH_MAGIC_64` read as themselves in the file's byte order, so reading them in
+//! the wrong order gives `MH_CIGAM` and `MH_CIGAM_64`. `MachO` detects the order from the magic
+//! and reads the header and load commands with it.
+//! ## Example
+//! ```
+//! use endiantype::format::macho::*;
+//! use endiantype::Endianness;
+//! let mut file = Vec::new();
+//! file.extend_from_slice(&MH_MAGIC_64.to_le_bytes());
+//! for field in [CPU_TYPE_ARM64 as u32, 0, MH_EXECUTE, 1, 72, 0, 0] {
+//!     file.extend_from_slice(&field.to_le_bytes());
+//! }
+//! // LC_SEGMENT_64 "__TEXT"
+//! file.extend_from_slice(&LC_SEGMENT_64.to_le_bytes());
+//! file.extend_from_slice(&72u32.to_le_bytes());
+//! file.extend_from_slice(b"__TEXT\0\0\0\0\0\0\0\0\0\0");
+//! for field in [0x1_0000_0000u64, 0x4000, 0, 0x4000] {
+//!     file.extend_from_slice(&field.to_le_bytes());
+//! }
+//! file.extend_from_slice(&[5, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+//!
+//! let macho = MachO::parse(&file).unwrap();
+//! assert!(macho.header.endianness == Endianness::Little && macho.header.is_64);
+//! assert!(macho.header.filetype == MH_EXECUTE);
+//! let command = macho.load_commands().next().unwrap().unwrap();
+//! let segment = command.segment().unwrap();
+//! assert!(segment.name() == b"__TEXT" && segment.vmaddr == 0x1_0000_0000);
+//! ```
+use crate::endianness::{detect_magic, Endianness};
+use crate::io::EndianReader;
+
+pub const MH_MAGIC: u32 = 0xfeed_face;
+pub const MH_CIGAM: u32 = 0xcefa_edfe;
+pub const MH_MAGIC_64: u32 = 0xfeed_facf;
+pub const MH_CIGAM_64: u32 = 0xcffa_edfe;
+
+pub const MH_OBJECT: u32 = 0x1;
+pub const MH_EXECUTE: u32 = 0x2;
+pub const MH_DYLIB: u32 = 0x6;
+pub const MH_BUNDLE: u32 = 0x8;
+
+pub const CPU_TYPE_X86: i32 = 7;
+pub const CPU_TYPE_X86_64: i32 = 0x0100_0007;
+pub const CPU_TYPE_ARM: i32 = 12;
+pub const CPU_TYPE_ARM64: i32 = 0x0100_000c;
+pub const CPU_TYPE_POWERPC: i32 = 18;
+
+pub const LC_SEGMENT: u32 = 0x1;
+pub const LC_SYMTAB: u32 = 0x2;
+pub const LC_UUID: u32 = 0x1b;
+pub const LC_SEGMENT_64: u32 = 0x19;
+pub const LC_MAIN: u32 = 0x8000_0028;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The file does not start with a Mach-O magic number.
+    BadMagic,
+    /// The file ends in the middle of the header or the load commands.
+    UnexpectedEof,
+    /// A load command is smaller than its own header.
+    BadCommandSize(u32),
+}
+
+/// Reads words in a byte order chosen at runtime.
+#[derive(Clone, Debug)]
+struct Reader<'a> {
+    reader: EndianReader<'a>,
+    endianness: Endianness,
+}
+
+impl<'a> Reader<'a> {
+    fn u32(&mut self) -> Option<u32> {
+        Some(self.endianness.u32_from_bytes(self.reader.read_array()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(self.endianness.u64_from_bytes(self.reader.read_array()?))
+    }
+
+    /// A 64-bit field, or a 32-bit one in 32-bit files.
+    fn word(&mut self, is_64: bool) -> Option<u64> {
+        if is_64 {
+            self.u64()
+        } else {
+            self.u32().map(u64::from)
+        }
+    }
+}
+
+/// # Mach header
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MachHeader {
+    pub endianness: Endianness,
+    pub is_64: bool,
+    pub cputype: i32,
+    pub cpusubtype: i32,
+    pub filetype: u32,
+    pub ncmds: u32,
+    pub sizeofcmds: u32,
+    pub flags: u32,
+}
+
+impl MachHeader {
+    /// Size of the header, after which the load commands start.
+    pub const fn size(&self) -> usize {
+        if self.is_64 {
+            32
+        } else {
+            28
+        }
+    }
+}
+
+/// # Mach-O file
+#[derive(Copy, Clone, Debug)]
+pub struct MachO<'a> {
+    data: &'a [u8],
+    pub header: MachHeader,
+}
+
+impl<'a> MachO<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let (endianness, is_64) = match (
+            detect_magic(data, &MH_MAGIC.to_be_bytes()),
+            detect_magic(data, &MH_MAGIC_64.to_be_bytes()),
+        ) {
+            (Some(endianness), _) => (endianness, false),
+            (_, Some(endianness)) => (endianness, true),
+            _ => return Err(Error::BadMagic),
+        };
+        let mut reader = Reader {
+            reader: EndianReader::new(&data[4..]),
+            endianness,
+        };
+        let mut field = || reader.u32().ok_or(Error::UnexpectedEof);
+        let header = MachHeader {
+            endianness,
+            is_64,
+            cputype: field()? as i32,
+            cpusubtype: field()? as i32,
+            filetype: field()?,
+            ncmds: field()?,
+            sizeofcmds: field()?,
+            flags: field()?,
+        };
+        if data.len() < header.size() {
+            return Err(Error::UnexpectedEof);
+        }
+        Ok(Self { data, header })
+    }
+
+    /// The whole file.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Iterate over the `ncmds` load commands after the header.
+    pub fn load_commands(&self) -> LoadCommands<'a> {
+        let start = self.header.size();
+        let end = start.saturating_add(self.header.sizeofcmds as usize);
+        LoadCommands {
+            commands: self.data.get(start..end).unwrap_or(&[]),
+            header: self.header,
+            left: self.header.ncmds,
+        }
+    }
+}
+
+/// # Load command
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LoadCommand<'a> {
+    pub cmd: u32,
+    /// The whole command, starting with `cmd` and `cmdsize`.
+    pub data: &'a [u8],
+    header: MachHeader,
+}
+
+impl<'a> LoadCommand<'a> {
+    fn reader(&self) -> Reader<'a> {
+        Reader {
+            reader: EndianReader::new(&self.data[8..]),
+            endianness: self.header.endianness,
+        }
+    }
+
+    /// Parse an `LC_SEGMENT` or `LC_SEGMENT_64` command.
+    pub fn segment(&self) -> Option<SegmentCommand> {
+        let is_64 = match self.cmd {
+            LC_SEGMENT => false,
+            LC_SEGMENT_64 => true,
+            _ => return None,
+        };
+        let mut reader = self.reader();
+        let segname = reader.reader.read_array()?;
+        Some(SegmentCommand {
+            segname,
+            vmaddr: reader.word(is_64)?,
+            vmsize: reader.word(is_64)?,
+            fileoff: reader.word(is_64)?,
+            filesize: reader.word(is_64)?,
+            maxprot: reader.u32()?,
+            initprot: reader.u32()?,
+            nsects: reader.u32()?,
+            flags: reader.u32()?,
+        })
+    }
+
+    /// The 16-byte UUID of an `LC_UUID` command.
+    pub fn uuid(&self) -> Option<[u8; 16]> {
+        match self.cmd {
+            LC_UUID => self.reader().reader.read_array(),
+            _ => None,
+        }
+    }
+}
+
+/// # Segment command
+/// An `LC_SEGMENT` or `LC_SEGMENT_64` command, with 32-bit fields widened.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SegmentCommand {
+    pub segname: [u8; 16],
+    pub vmaddr: u64,
+    pub vmsize: u64,
+    pub fileoff: u64,
+    pub filesize: u64,
+    pub maxprot: u32,
+    pub initprot: u32,
+    pub nsects: u32,
+    pub flags: u32,
+}
+
+impl SegmentCommand {
+    /// The segment name without its zero padding.
+    pub fn name(&self) -> &[u8] {
+        let len = self.segname.iter().position(|&b| b == 0).unwrap_or(16);
+        &self.segname[..len]
+    }
+}
+
+/// # Load command iterator
+/// Yields the load commands of a file; iteration stops after the first error.
+#[derive(Clone, Debug)]
+pub struct LoadCommands<'a> {
+    commands: &'a [u8],
+    header: MachHeader,
+    left: u32,
+}
+
+impl<'a> Iterator for LoadCommands<'a> {
+    type Item = Result<LoadCommand<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left == 0 {
+            return None;
+        }
+        self.left -= 1;
+        let mut reader = Reader {
+            reader: EndianReader::new(self.commands),
+            endianness: self.header.endianness,
+        };
+        let item = match (reader.u32(), reader.u32()) {
+            (Some(cmd), Some(size)) if size >= 8 => match self.commands.get(..size as usize) {
+                Some(data) => Ok(LoadCommand {
+                    cmd,
+                    data,
+                    header: self.header,
+                }),
+                None => Err(Error::UnexpectedEof),
+            },
+            (Some(_), Some(size)) => Err(Error::BadCommandSize(size)),
+            _ => Err(Error::UnexpectedEof),
+        };
+        match item {
+            Ok(command) => self.commands = &self.commands[command.data.len()..],
+            Err(_) => self.left = 0,
+        }
+        Some(item)
+    }
+}
@@ -0,0 +1,162 @@
+//! # Standard MIDI Files
+//!
+//! An SMF is a sequence of chunks, each an ASCII type (`MThd` or `MTrk`) followed by a big-endian
+//! `u32` length. The `MThd` header chunk gives the file format, the number of tracks and the time
+//! division; track events are prefixed with delta times stored as variable-length quantities
+//! (VLQs): 7 bits per byte, most significant group first, with the top bit set on every byte but
+//! the last.
+//! ## Example
+//! ```
+//! use endiantype::format::midi::*;
+//! use endiantype::io::*;
+//! let mut buf = [0u8; 32];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! ChunkHeader::new(*b"MThd", HeaderChunk::SIZE as u32).write(&mut writer).unwrap();
+//! HeaderChunk { format: 1, ntrks: 2, division: 480 }.write(&mut writer).unwrap();
+//! ChunkHeader::new(*b"MTrk", 4).write(&mut writer).unwrap();
+//! write_vlq(&mut writer, 0x2000).unwrap();
+//! writer.write_bytes(&[0xff, 0x2f]).unwrap();
+//! let len = writer.position();
+//! assert!(buf[22..25] == [0xc0, 0x00, 0xff]);
+//!
+//! let mut reader = EndianReader::new(&buf[..len]);
+//! let header = HeaderChunk::read(&mut reader).unwrap();
+//! assert!(header.ntrks == 2 && header.ticks_per_quarter() == Some(480));
+//! let track = ChunkHeader::read(&mut reader).unwrap();
+//! assert!(track.chunk_type == *b"MTrk" && track.length == 4);
+//! assert!(read_vlq(&mut reader) == Ok(0x2000));
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::{u16_be, u32_be};
+use crate::FourCC;
+
+/// Largest value a VLQ can hold in the 4 bytes SMF allows.
+pub const MAX_VLQ: u32 = 0x0fff_ffff;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a chunk header or quantity.
+    UnexpectedEof,
+    /// The file does not start with an `MThd` chunk of at least 6 bytes.
+    BadHeader,
+    /// A variable-length quantity is longer than 4 bytes.
+    VlqTooLong,
+}
+
+/// # Chunk header
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ChunkHeader {
+    pub chunk_type: FourCC,
+    /// Length of the chunk data, excluding this header.
+    pub length: u32,
+}
+
+impl ChunkHeader {
+    pub const SIZE: usize = 8;
+
+    pub fn new(chunk_type: impl Into<FourCC>, length: u32) -> Self {
+        Self {
+            chunk_type: chunk_type.into(),
+            length,
+        }
+    }
+
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let chunk_type = FourCC(reader.read_array().ok_or(Error::UnexpectedEof)?);
+        let length = reader.read::<u32_be>().ok_or(Error::UnexpectedEof)?;
+        Ok(Self::new(chunk_type, length.to_native()))
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write_bytes(&self.chunk_type.to_bytes())?;
+        writer.write(u32_be::from_native(self.length))
+    }
+}
+
+/// # Header chunk
+/// The data of the `MThd` chunk.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct HeaderChunk {
+    /// 0 for a single track, 1 for simultaneous tracks, 2 for independent patterns.
+    pub format: u16,
+    pub ntrks: u16,
+    /// Ticks per quarter note, or SMPTE frames and ticks per frame if the top bit is set.
+    pub division: u16,
+}
+
+impl HeaderChunk {
+    /// Size of the chunk data.
+    pub const SIZE: usize = 6;
+
+    /// Read the `MThd` chunk, including its chunk header, skipping any data past the known
+    /// fields.
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let chunk = ChunkHeader::read(reader)?;
+        if chunk.chunk_type != *b"MThd" || (chunk.length as usize) < Self::SIZE {
+            return Err(Error::BadHeader);
+        }
+        let data = reader
+            .read_bytes(chunk.length as usize)
+            .ok_or(Error::UnexpectedEof)?;
+        let mut data = EndianReader::new(data);
+        let mut field = || data.read::<u16_be>().unwrap().to_native();
+        Ok(Self {
+            format: field(),
+            ntrks: field(),
+            division: field(),
+        })
+    }
+
+    /// Write the chunk data, without the chunk header.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write(u16_be::from_native(self.format))?;
+        writer.write(u16_be::from_native(self.ntrks))?;
+        writer.write(u16_be::from_native(self.division))
+    }
+
+    /// Ticks per quarter note, or `None` for SMPTE time division.
+    pub fn ticks_per_quarter(&self) -> Option<u16> {
+        if self.division & 0x8000 == 0 {
+            Some(self.division)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read a variable-length quantity of at most 4 bytes.
+pub fn read_vlq(reader: &mut EndianReader) -> Result<u32, Error> {
+    let mut value = 0;
+    for _ in 0..4 {
+        let byte = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        value = value << 7 | u32::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::VlqTooLong)
+}
+
+/// Number of bytes `write_vlq` produces for `value`.
+pub fn vlq_len(value: u32) -> usize {
+    match value {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        0x4000..=0x1f_ffff => 3,
+        _ => 4,
+    }
+}
+
+/// Write `value` as a variable-length quantity, or `None` if the buffer is full or `value` is
+/// above `MAX_VLQ`.
+pub fn write_vlq(writer: &mut EndianWriter, value: u32) -> Option<()> {
+    if value > MAX_VLQ {
+        return None;
+    }
+    for group in (0..vlq_len(value)).rev() {
+        let continuation = if group == 0 { 0 } else { 0x80 };
+        writer.write_u8((value >> (7 * group)) as u8 & 0x7f | continuation)?;
+    }
+    Some(())
+}
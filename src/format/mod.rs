@@ -0,0 +1,13 @@
+//! # File format helpers
+//!
+//! Headers and framing for binary file and container formats, built on `EndianReader` and
+//! `EndianWriter`.
+pub mod bson;
+pub mod ebml;
+pub mod ext4;
+pub mod fits;
+pub mod git;
+pub mod macho;
+pub mod midi;
+pub mod mp4;
+pub mod tiff;
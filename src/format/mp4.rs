@@ -0,0 +1,146 @@
+//! # ISO-BMFF boxes
+//!
+//! Box (atom) traversal for MP4, MOV, HEIF and other ISO base media files. Each box starts with a
+//! big-endian `u32` size and a four-character type; a size of 1 means a 64-bit size follows, and
+//! a size of 0 means the box extends to the end of its parent.
+//! ## Example
+//! ```
+//! use endiantype::format::mp4::*;
+//! let file = [
+//!     0, 0, 0, 16, b'f', b't', b'y', b'p', b'i', b's', b'o', b'm', 0, 0, 0, 1,
+//!     0, 0, 0, 1, b'm', b'o', b'o', b'v', 0, 0, 0, 0, 0, 0, 0, 24,
+//!     0, 0, 0, 8, b'm', b'v', b'h', b'd',
+//! ];
+//! let mut boxes = Boxes::new(&file);
+//! let ftyp = boxes.next().unwrap().unwrap();
+//! assert!(ftyp.header.box_type == *b"ftyp" && &ftyp.payload[..4] == b"isom");
+//! let moov = boxes.next().unwrap().unwrap();
+//! assert!(moov.header.size == 24 && moov.header.header_len == 16);
+//! let mvhd = moov.children().next().unwrap().unwrap();
+//! assert!(mvhd.header.box_type == *b"mvhd" && mvhd.payload.is_empty());
+//! assert!(boxes.next().is_none());
+//! ```
+use crate::io::EndianReader;
+use crate::types::{u32_be, u64_be};
+use crate::FourCC;
+use core::convert::TryFrom;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a box header or payload.
+    UnexpectedEof,
+    /// The declared size is smaller than the box header.
+    InvalidSize(u64),
+}
+
+/// # Box header
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BoxHeader {
+    /// Size of the whole box, including the header.
+    pub size: u64,
+    pub box_type: FourCC,
+    /// Extended type of `uuid` boxes.
+    pub user_type: Option<[u8; 16]>,
+    /// Size of the header itself: 8, 16, 24 or 32 bytes.
+    pub header_len: usize,
+}
+
+impl BoxHeader {
+    /// Read a box header; `remaining` is the number of bytes from the start of the box to the
+    /// end of its parent, used to resolve a size of 0.
+    pub fn read(reader: &mut EndianReader, remaining: usize) -> Result<Self, Error> {
+        let start = reader.position();
+        let size32 = reader.read::<u32_be>().ok_or(Error::UnexpectedEof)?;
+        let box_type = FourCC(reader.read_array().ok_or(Error::UnexpectedEof)?);
+        let size = match size32.to_native() {
+            0 => remaining as u64,
+            1 => reader
+                .read::<u64_be>()
+                .ok_or(Error::UnexpectedEof)?
+                .to_native(),
+            size => u64::from(size),
+        };
+        let user_type = if box_type == *b"uuid" {
+            Some(reader.read_array().ok_or(Error::UnexpectedEof)?)
+        } else {
+            None
+        };
+        let header_len = reader.position() - start;
+        if size < header_len as u64 {
+            return Err(Error::InvalidSize(size));
+        }
+        Ok(Self {
+            size,
+            box_type,
+            user_type,
+            header_len,
+        })
+    }
+}
+
+/// # Box
+/// A box header and its payload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Atom<'a> {
+    pub header: BoxHeader,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Atom<'a> {
+    /// Iterate over the boxes nested in the payload of a container box such as `moov`.
+    pub fn children(&self) -> Boxes<'a> {
+        Boxes::new(self.payload)
+    }
+
+    /// Split the version and flags off the payload of a full box.
+    pub fn full_box(&self) -> Option<(u8, u32, &'a [u8])> {
+        let mut reader = EndianReader::new(self.payload);
+        let version_flags = reader.read::<u32_be>()?.to_native();
+        Some((
+            (version_flags >> 24) as u8,
+            version_flags & 0x00ff_ffff,
+            reader.rest(),
+        ))
+    }
+}
+
+/// # Box iterator
+/// Yields the boxes of a buffer in order; iteration stops after the first error.
+#[derive(Clone, Debug)]
+pub struct Boxes<'a> {
+    reader: EndianReader<'a>,
+}
+
+impl<'a> Boxes<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            reader: EndianReader::new(bytes),
+        }
+    }
+
+    fn read_box(&mut self) -> Result<Atom<'a>, Error> {
+        let header = BoxHeader::read(&mut self.reader.clone(), self.reader.remaining())?;
+        let size = usize::try_from(header.size).map_err(|_| Error::UnexpectedEof)?;
+        let bytes = self.reader.read_bytes(size).ok_or(Error::UnexpectedEof)?;
+        Ok(Atom {
+            header,
+            payload: &bytes[header.header_len..],
+        })
+    }
+}
+
+impl<'a> Iterator for Boxes<'a> {
+    type Item = Result<Atom<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.is_empty() {
+            return None;
+        }
+        let item = self.read_box();
+        if item.is_err() {
+            self.reader = EndianReader::new(&[]);
+        }
+        Some(item)
+    }
+}
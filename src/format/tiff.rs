@@ -0,0 +1,260 @@
+//! # TIFF structure
+//!
+//! TIFF, DNG and EXIF files choose their byte order with the `II` (little endian) or `MM` (big
+//! endian) marker at the start of the header, and every multi-byte field after it follows that
+//! choice. `Tiff` records the order as an `Endianness` and reads the header, image file
+//! directories (IFDs) and entry values with it.
+//! ## Example
+//! ```
+//! use endiantype::format::tiff::*;
+//! use endiantype::Endianness;
+//! let file = [
+//!     b'M', b'M', 0x00, 0x2a, 0x00, 0x00, 0x00, 0x08, // header, first IFD at 8
+//!     0x00, 0x02, // two entries
+//!     0x01, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x02, 0x80, 0x00, 0x00, // ImageWidth = 640
+//!     0x01, 0x0f, 0x00, 0x02, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x26, // Make at 38
+//!     0x00, 0x00, 0x00, 0x00, // no next IFD
+//!     b'C', b'a', b'n', b'o', b'n', 0x00,
+//! ];
+//! let tiff = Tiff::parse(&file).unwrap();
+//! assert!(tiff.endianness == Endianness::Big);
+//! let ifd = tiff.ifds().next().unwrap().unwrap();
+//! let width = ifd.get(TAG_IMAGE_WIDTH).unwrap();
+//! assert!(tiff.value_u32(&width, 0) == Some(640));
+//! let make = ifd.get(TAG_MAKE).unwrap();
+//! assert!(tiff.value_bytes(&make).unwrap() == b"Canon\0");
+//! assert!(tiff.ifds().count() == 1);
+//! ```
+use crate::endianness::{detect_magic, Endianness};
+use core::convert::TryFrom;
+
+pub const TYPE_BYTE: u16 = 1;
+pub const TYPE_ASCII: u16 = 2;
+pub const TYPE_SHORT: u16 = 3;
+pub const TYPE_LONG: u16 = 4;
+pub const TYPE_RATIONAL: u16 = 5;
+pub const TYPE_SBYTE: u16 = 6;
+pub const TYPE_UNDEFINED: u16 = 7;
+pub const TYPE_SSHORT: u16 = 8;
+pub const TYPE_SLONG: u16 = 9;
+pub const TYPE_SRATIONAL: u16 = 10;
+pub const TYPE_FLOAT: u16 = 11;
+pub const TYPE_DOUBLE: u16 = 12;
+pub const TYPE_IFD: u16 = 13;
+
+pub const TAG_IMAGE_WIDTH: u16 = 0x0100;
+pub const TAG_IMAGE_LENGTH: u16 = 0x0101;
+pub const TAG_BITS_PER_SAMPLE: u16 = 0x0102;
+pub const TAG_COMPRESSION: u16 = 0x0103;
+pub const TAG_MAKE: u16 = 0x010f;
+pub const TAG_MODEL: u16 = 0x0110;
+pub const TAG_STRIP_OFFSETS: u16 = 0x0111;
+pub const TAG_STRIP_BYTE_COUNTS: u16 = 0x0117;
+pub const TAG_EXIF_IFD: u16 = 0x8769;
+pub const TAG_GPS_IFD: u16 = 0x8825;
+
+/// Most IFDs followed before giving up, so that offset loops in damaged files terminate.
+const MAX_IFDS: usize = 1024;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The file does not start with `II*\0` or `MM\0*`.
+    BadHeader,
+    /// An offset or count points outside the file.
+    OutOfBounds,
+    /// An entry has a field type this module does not know the size of.
+    UnknownType(u16),
+}
+
+/// Size in bytes of one value of a field type.
+pub fn type_size(field_type: u16) -> Option<usize> {
+    match field_type {
+        TYPE_BYTE | TYPE_ASCII | TYPE_SBYTE | TYPE_UNDEFINED => Some(1),
+        TYPE_SHORT | TYPE_SSHORT => Some(2),
+        TYPE_LONG | TYPE_SLONG | TYPE_FLOAT | TYPE_IFD => Some(4),
+        TYPE_RATIONAL | TYPE_SRATIONAL | TYPE_DOUBLE => Some(8),
+        _ => None,
+    }
+}
+
+/// # TIFF file
+/// A TIFF file in memory along with the byte order from its header.
+#[derive(Copy, Clone, Debug)]
+pub struct Tiff<'a> {
+    data: &'a [u8],
+    pub endianness: Endianness,
+    pub first_ifd: u32,
+}
+
+impl<'a> Tiff<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let endianness = match data.get(..2) {
+            Some(b"II") => Endianness::Little,
+            Some(b"MM") => Endianness::Big,
+            _ => return Err(Error::BadHeader),
+        };
+        if detect_magic(&data[2..], &[0x00, 0x2a]) != Some(endianness) {
+            return Err(Error::BadHeader);
+        }
+        let mut tiff = Self {
+            data,
+            endianness,
+            first_ifd: 0,
+        };
+        tiff.first_ifd = tiff.u32_at(4).ok_or(Error::BadHeader)?;
+        Ok(tiff)
+    }
+
+    /// The whole file.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Read a `u16` at `offset` in the file's byte order.
+    pub fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes = self.data.get(offset..offset.checked_add(2)?)?;
+        Some(self.endianness.u16_from_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Read a `u32` at `offset` in the file's byte order.
+    pub fn u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes = self.data.get(offset..offset.checked_add(4)?)?;
+        Some(
+            self.endianness
+                .u32_from_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        )
+    }
+
+    /// The IFD at `offset`, e.g. one referenced by an `TAG_EXIF_IFD` entry.
+    pub fn ifd(&self, offset: u32) -> Result<Ifd<'a>, Error> {
+        let offset = offset as usize;
+        let count = self.u16_at(offset).ok_or(Error::OutOfBounds)?;
+        let next_at = offset + 2 + usize::from(count) * 12;
+        let next = self.u32_at(next_at).ok_or(Error::OutOfBounds)?;
+        Ok(Ifd {
+            tiff: *self,
+            offset,
+            count,
+            next,
+        })
+    }
+
+    /// Iterate over the chain of IFDs starting at `first_ifd`.
+    pub fn ifds(&self) -> Ifds<'a> {
+        Ifds {
+            tiff: *self,
+            next: self.first_ifd,
+            visited: 0,
+        }
+    }
+
+    /// The raw bytes of an entry's values, stored inline or at the offset the entry holds.
+    pub fn value_bytes(&self, entry: &IfdEntry) -> Result<&'a [u8], Error> {
+        let size = type_size(entry.field_type).ok_or(Error::UnknownType(entry.field_type))?;
+        let len = usize::try_from(entry.count)
+            .ok()
+            .and_then(|count| count.checked_mul(size))
+            .ok_or(Error::OutOfBounds)?;
+        let start = if len <= 4 {
+            entry.entry_offset + 8
+        } else {
+            self.endianness.u32_from_bytes(entry.value) as usize
+        };
+        let end = start.checked_add(len).ok_or(Error::OutOfBounds)?;
+        self.data.get(start..end).ok_or(Error::OutOfBounds)
+    }
+
+    /// The `index`th value of a `BYTE`, `SHORT`, `LONG` or `IFD` entry as a `u32`.
+    pub fn value_u32(&self, entry: &IfdEntry, index: usize) -> Option<u32> {
+        let bytes = self.value_bytes(entry).ok()?;
+        match entry.field_type {
+            TYPE_BYTE => bytes.get(index).map(|&b| u32::from(b)),
+            TYPE_SHORT => {
+                let b = bytes.get(index * 2..index * 2 + 2)?;
+                Some(u32::from(self.endianness.u16_from_bytes([b[0], b[1]])))
+            }
+            TYPE_LONG | TYPE_IFD => {
+                let b = bytes.get(index * 4..index * 4 + 4)?;
+                Some(self.endianness.u32_from_bytes([b[0], b[1], b[2], b[3]]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// # Image file directory
+#[derive(Copy, Clone, Debug)]
+pub struct Ifd<'a> {
+    tiff: Tiff<'a>,
+    offset: usize,
+    count: u16,
+    /// Offset of the next IFD, or 0 for the last one.
+    pub next: u32,
+}
+
+impl<'a> Ifd<'a> {
+    pub fn len(&self) -> usize {
+        usize::from(self.count)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = IfdEntry> + 'a {
+        let tiff = self.tiff;
+        let start = self.offset + 2;
+        (0..self.len()).map(move |i| {
+            let entry_offset = start + i * 12;
+            let value = &tiff.data[entry_offset + 8..entry_offset + 12];
+            IfdEntry {
+                tag: tiff.u16_at(entry_offset).unwrap(),
+                field_type: tiff.u16_at(entry_offset + 2).unwrap(),
+                count: tiff.u32_at(entry_offset + 4).unwrap(),
+                value: [value[0], value[1], value[2], value[3]],
+                entry_offset,
+            }
+        })
+    }
+
+    /// The first entry with `tag`.
+    pub fn get(&self, tag: u16) -> Option<IfdEntry> {
+        self.entries().find(|entry| entry.tag == tag)
+    }
+}
+
+/// # IFD entry
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IfdEntry {
+    pub tag: u16,
+    pub field_type: u16,
+    pub count: u32,
+    /// The value field as stored: the values themselves if they fit, otherwise their offset.
+    pub value: [u8; 4],
+    entry_offset: usize,
+}
+
+/// # IFD chain iterator
+pub struct Ifds<'a> {
+    tiff: Tiff<'a>,
+    next: u32,
+    visited: usize,
+}
+
+impl<'a> Iterator for Ifds<'a> {
+    type Item = Result<Ifd<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == 0 || self.visited == MAX_IFDS {
+            return None;
+        }
+        self.visited += 1;
+        let ifd = self.tiff.ifd(self.next);
+        self.next = match ifd {
+            Ok(ifd) => ifd.next,
+            Err(_) => 0,
+        };
+        Some(ifd)
+    }
+}
@@ -0,0 +1,108 @@
+//! # FourCC codes
+//!
+//! Four-character codes as used by RIFF, ISO-BMFF and V4L2. Formats disagree on whether they are
+//! big-endian or little-endian integers, so `FourCC` stores the four raw bytes and converts to and
+//! from `u32_be` and `u32_le` by reinterpreting their stored bytes, never by value.
+//! ## Example
+//! ```
+//! use endiantype::*;
+//! const FTYP: FourCC = FourCC::new(b"ftyp");
+//! assert!(FTYP == *b"ftyp");
+//! // ISO-BMFF reads box types as big-endian integers...
+//! assert!(u32_be::from(FTYP) == 0x66747970);
+//! // ...while V4L2 pixel formats are little-endian.
+//! let yuyv = FourCC::from(u32_le::from_native(0x56595559));
+//! assert!(yuyv == *b"YUYV");
+//! assert!(format!("{}", yuyv) == "YUYV");
+//! assert!(format!("{}", FourCC::new(b"a\0\x01b")) == "a\\x00\\x01b");
+//! ```
+use crate::types::{u32_be, u32_le};
+use core::fmt;
+
+/// # Four-character code
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct FourCC(pub [u8; 4]);
+
+impl FourCC {
+    pub const fn new(code: &[u8; 4]) -> Self {
+        Self(*code)
+    }
+
+    pub const fn from_bytes(bytes: [u8; 4]) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn to_bytes(self) -> [u8; 4] {
+        self.0
+    }
+}
+
+impl From<[u8; 4]> for FourCC {
+    #[inline]
+    fn from(bytes: [u8; 4]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<FourCC> for [u8; 4] {
+    #[inline]
+    fn from(code: FourCC) -> Self {
+        code.0
+    }
+}
+
+impl From<u32_be> for FourCC {
+    #[inline]
+    fn from(data: u32_be) -> Self {
+        Self(data.to_native().to_be_bytes())
+    }
+}
+
+impl From<FourCC> for u32_be {
+    #[inline]
+    fn from(code: FourCC) -> Self {
+        u32_be::from_native(u32::from_be_bytes(code.0))
+    }
+}
+
+impl From<u32_le> for FourCC {
+    #[inline]
+    fn from(data: u32_le) -> Self {
+        Self(data.to_native().to_le_bytes())
+    }
+}
+
+impl From<FourCC> for u32_le {
+    #[inline]
+    fn from(code: FourCC) -> Self {
+        u32_le::from_native(u32::from_le_bytes(code.0))
+    }
+}
+
+impl PartialEq<[u8; 4]> for FourCC {
+    #[inline]
+    fn eq(&self, rhs: &[u8; 4]) -> bool {
+        self.0 == *rhs
+    }
+}
+
+impl fmt::Display for FourCC {
+    /// Printable ASCII characters are written as is, anything else as `\xNN`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &byte in &self.0 {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                write!(f, "{}", byte as char)?;
+            } else {
+                write!(f, "\\x{:02x}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FourCC(\"{}\")", self)
+    }
+}
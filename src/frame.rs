@@ -0,0 +1,141 @@
+//! # Length-prefixed frames
+//!
+//! Most stream protocols split their byte stream into frames with a length field at a fixed
+//! offset of each frame header. `LengthField` describes that field, and `FrameReader` cuts the
+//! complete frames out of a buffer, leaving a trailing partial frame for the caller to keep until
+//! more bytes arrive.
+//! ## Example
+//! ```
+//! use endiantype::frame::*;
+//! use endiantype::Endianness;
+//! // A type byte, then a big-endian u16 length of the payload.
+//! let field = LengthField {
+//!     offset: 1,
+//!     width: 2,
+//!     endianness: Endianness::Big,
+//!     includes_header: false,
+//!     max_len: 1024,
+//! };
+//! let stream = b"\x01\x00\x02hi\x02\x00\x00\x01\x00\x05wor";
+//! let mut frames = FrameReader::new(field, stream);
+//! assert!(frames.next() == Some(Ok(&b"\x01\x00\x02hi"[..])));
+//! assert!(frames.next() == Some(Ok(&b"\x02\x00\x00"[..])));
+//! assert!(frames.next().is_none());
+//! // The rest is the start of a frame; keep it and read again when more bytes arrive.
+//! assert!(frames.consumed() == 8 && frames.rest() == b"\x01\x00\x05wor");
+//! ```
+use crate::endianness::Endianness;
+use crate::io::EndianReader;
+use core::convert::TryFrom;
+
+/// # Framing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A length that includes the header is smaller than the header.
+    TooShort(u64),
+    /// A frame is longer than `max_len`.
+    TooLong(u64),
+}
+
+/// # Length field description
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LengthField {
+    /// Offset of the length field from the start of the frame.
+    pub offset: usize,
+    /// Size of the length field in bytes, from 1 to 8.
+    pub width: usize,
+    pub endianness: Endianness,
+    /// Whether the length counts the header up to and including the length field, or only the
+    /// bytes after it.
+    pub includes_header: bool,
+    /// Longest frame accepted, including its header.
+    pub max_len: usize,
+}
+
+impl LengthField {
+    /// Number of bytes up to and including the length field.
+    pub const fn header_len(&self) -> usize {
+        self.offset + self.width
+    }
+
+    /// Length of the frame at the start of `bytes`, or `Ok(None)` if its length field has not
+    /// arrived yet.
+    ///
+    /// Panics if `width` is not between 1 and 8.
+    pub fn frame_len(&self, bytes: &[u8]) -> Result<Option<usize>, Error> {
+        assert!((1..=8).contains(&self.width));
+        let field = match bytes.get(self.offset..self.header_len()) {
+            Some(field) => field,
+            None => return Ok(None),
+        };
+        let len = match self.endianness {
+            Endianness::Little => field
+                .iter()
+                .rev()
+                .fold(0, |acc, &b| acc << 8 | u64::from(b)),
+            Endianness::Big => field.iter().fold(0, |acc, &b| acc << 8 | u64::from(b)),
+        };
+        let header_len = self.header_len() as u64;
+        let frame_len = if self.includes_header {
+            if len < header_len {
+                return Err(Error::TooShort(len));
+            }
+            len
+        } else {
+            len.checked_add(header_len).ok_or(Error::TooLong(len))?
+        };
+        match usize::try_from(frame_len) {
+            Ok(frame_len) if frame_len <= self.max_len => Ok(Some(frame_len)),
+            _ => Err(Error::TooLong(frame_len)),
+        }
+    }
+}
+
+/// # Frame reader
+/// Yields the complete frames at the start of a buffer; iteration stops at the first partial
+/// frame or after the first error.
+#[derive(Clone, Debug)]
+pub struct FrameReader<'a> {
+    field: LengthField,
+    reader: EndianReader<'a>,
+    failed: bool,
+}
+
+impl<'a> FrameReader<'a> {
+    pub fn new(field: LengthField, buf: &'a [u8]) -> Self {
+        Self {
+            field,
+            reader: EndianReader::new(buf),
+            failed: false,
+        }
+    }
+
+    /// Number of bytes taken up by the frames read so far.
+    pub fn consumed(&self) -> usize {
+        self.reader.position()
+    }
+
+    /// The bytes after the frames read so far.
+    pub fn rest(&self) -> &'a [u8] {
+        self.reader.rest()
+    }
+}
+
+impl<'a> Iterator for FrameReader<'a> {
+    type Item = Result<&'a [u8], Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.field.frame_len(self.reader.rest()) {
+            Ok(Some(len)) => self.reader.read_bytes(len).map(Ok),
+            Ok(None) => None,
+            Err(err) => {
+                // Keep the position, so that `consumed` still counts the good frames.
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! # Hex text
+//!
+//! Endian types can be written as and parsed from hex text in their stored byte order, for text
+//! protocols and debug consoles that exchange fields as hex. Both directions work on caller
+//! buffers, without allocation.
+//! ## Example
+//! ```
+//! use endiantype::hex::Error;
+//! use endiantype::*;
+//! let mut buf = [0u8; 16];
+//! assert!(u32_be::from_native(0xdeadbeef).encode_hex(&mut buf) == "deadbeef");
+//! assert!(u32_le::from_native(0xdeadbeef).encode_hex(&mut buf) == "efbeadde");
+//! assert!(u16_be::from_hex(b"CAFE").unwrap() == 0xcafe);
+//! assert!(u16_be::from_hex(b"cafe00") == Err(Error::InvalidLength));
+//! assert!(u16_be::from_hex(b"caf!") == Err(Error::InvalidDigit(b'!')));
+//! ```
+use crate::{BigEndian, LittleEndian};
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The text is not exactly two digits per byte.
+    InvalidLength,
+    /// A character is not a hex digit.
+    InvalidDigit(u8),
+}
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Write `bytes` as lowercase hex to the start of `buf`, returning the text.
+///
+/// Panics if `buf` is shorter than twice the length of `bytes`.
+pub fn encode<'b>(bytes: &[u8], buf: &'b mut [u8]) -> &'b str {
+    let text = &mut buf[..bytes.len() * 2];
+    for (pair, byte) in text.chunks_exact_mut(2).zip(bytes) {
+        pair[0] = DIGITS[usize::from(byte >> 4)];
+        pair[1] = DIGITS[usize::from(byte & 0xf)];
+    }
+    core::str::from_utf8(text).unwrap()
+}
+
+/// Parse hex text of either case into `out`, which must be exactly half as long as `hex`.
+pub fn decode(hex: &[u8], out: &mut [u8]) -> Result<(), Error> {
+    if hex.len() != out.len() * 2 {
+        return Err(Error::InvalidLength);
+    }
+    for (byte, pair) in out.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = digit(pair[0])? << 4 | digit(pair[1])?;
+    }
+    Ok(())
+}
+
+fn digit(c: u8) -> Result<u8, Error> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(Error::InvalidDigit(c)),
+    }
+}
+
+macro_rules! impl_hex {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// Write the stored bytes as lowercase hex to the start of `buf`, returning the text.
+            ///
+            /// Panics if `buf` is shorter than twice the size of the type.
+            pub fn encode_hex<'b>(&self, buf: &'b mut [u8]) -> &'b str {
+                encode(self.as_ref(), buf)
+            }
+
+            /// Parse the stored bytes from hex text of either case.
+            pub fn from_hex(hex: &[u8]) -> Result<Self, Error> {
+                let mut raw = [0; core::mem::size_of::<$type_name>()];
+                decode(hex, &mut raw)?;
+                Ok(Self::new($type_name::from_ne_bytes(raw)))
+            }
+        }
+    };
+}
+
+macro_rules! impl_hex_both {
+    ($type_name: ident) => {
+        impl_hex!($type_name, LittleEndian);
+        impl_hex!($type_name, BigEndian);
+    };
+}
+
+for_each_int!(impl_hex_both);
@@ -0,0 +1,121 @@
+//! # Hexdumps
+//!
+//! `Hexdump` formats a buffer like `hexdump -C`: offsets, sixteen hex bytes per line and an ASCII
+//! column. Given a struct layout from `Wire::layout`, it also lists the fields starting on each
+//! line with their values decoded in their own byte order, which makes byte order mistakes easy
+//! to spot. It only needs `core::fmt`, so it also works with `no_std` loggers.
+//! ## Example
+//! ```
+//! use endiantype::hexdump::Hexdump;
+//! use endiantype::wire::*;
+//! use endiantype::*;
+//!
+//! endian_struct! {
+//!     pub struct Record {
+//!         pub magic: [u8; 4],
+//!         pub len: u32_le,
+//!         pub kind: u16_be,
+//!     }
+//! }
+//!
+//! let bytes = b"REC\x00\x20\x00\x00\x00\x01\x02";
+//! assert!(
+//!     format!("{}", Hexdump::new(bytes))
+//!         == "00000000  52 45 43 00 20 00 00 00  01 02                    |REC. .....|\n"
+//! );
+//! let dump = format!("{}", Hexdump::with_fields(bytes, Record::layout()));
+//! let mut lines = dump.lines().skip(1);
+//! assert!(lines.next() == Some("          00000000  magic (4 bytes)"));
+//! assert!(lines.next() == Some("          00000004  len = 0x00000020 (little endian)"));
+//! assert!(lines.next() == Some("          00000008  kind = 0x0102 (big endian)"));
+//! ```
+use crate::endianness::Endianness;
+use crate::wire::FieldDesc;
+use core::fmt;
+
+const LINE_LEN: usize = 16;
+
+/// # Hexdump
+/// Formats `bytes` with `Display`, annotated with `fields` if there are any.
+#[derive(Copy, Clone, Debug)]
+pub struct Hexdump<'a> {
+    bytes: &'a [u8],
+    fields: &'a [FieldDesc],
+}
+
+impl<'a> Hexdump<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, fields: &[] }
+    }
+
+    /// A hexdump that lists `fields` below the lines they start on.
+    pub fn with_fields(bytes: &'a [u8], fields: &'a [FieldDesc]) -> Self {
+        Self { bytes, fields }
+    }
+
+    fn write_line(&self, f: &mut fmt::Formatter, offset: usize, line: &[u8]) -> fmt::Result {
+        write!(f, "{:08x} ", offset)?;
+        for i in 0..LINE_LEN {
+            if i % 8 == 0 {
+                f.write_str(" ")?;
+            }
+            match line.get(i) {
+                Some(byte) => write!(f, "{:02x} ", byte)?,
+                None => f.write_str("   ")?,
+            }
+        }
+        f.write_str(" |")?;
+        for &byte in line {
+            let c = match byte {
+                0x20..=0x7e => char::from(byte),
+                _ => '.',
+            };
+            write!(f, "{}", c)?;
+        }
+        f.write_str("|\n")
+    }
+
+    fn write_field(&self, f: &mut fmt::Formatter, field: &FieldDesc) -> fmt::Result {
+        write!(f, "          {:08x}  {}", field.offset, field.name)?;
+        let bytes = self.bytes.get(field.offset..field.offset + field.width);
+        match (field.endianness, bytes) {
+            (Some(endianness), Some(bytes)) if field.width <= 8 => {
+                let value = match endianness {
+                    Endianness::Little => bytes
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, &b| acc << 8 | u64::from(b)),
+                    Endianness::Big => bytes.iter().fold(0, |acc, &b| acc << 8 | u64::from(b)),
+                };
+                let order = match endianness {
+                    Endianness::Little => "little",
+                    Endianness::Big => "big",
+                };
+                writeln!(
+                    f,
+                    " = {:#0width$x} ({} endian)",
+                    value,
+                    order,
+                    width = field.width * 2 + 2
+                )
+            }
+            (_, None) => writeln!(f, " (truncated)"),
+            _ => writeln!(f, " ({} bytes)", field.width),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Hexdump<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, line) in self.bytes.chunks(LINE_LEN).enumerate() {
+            let offset = i * LINE_LEN;
+            self.write_line(f, offset, line)?;
+            for field in self.fields {
+                if (offset..offset + LINE_LEN).contains(&field.offset) {
+                    self.write_field(f, field)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+//! # Buffer reader and writer
+//!
+//! Cursors over byte buffers that read and write endian values in sequence. They never panic on
+//! short buffers: every operation returns `None` when there are not enough bytes left, and leaves
+//! the cursor where it was.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::*;
+//! let mut buf = [0u8; 8];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! writer.write(u16_be::from_native(0x0102)).unwrap();
+//! writer.write(u32_le::from_native(0x0a0b0c0d)).unwrap();
+//! writer.write_u8(0xff).unwrap();
+//! assert!(writer.position() == 7);
+//! assert!(writer.write(u16_be::from_native(0)).is_none());
+//!
+//! let mut reader = EndianReader::new(&buf);
+//! assert!(reader.read::<u16_be>().unwrap() == 0x0102);
+//! assert!(reader.read::<u32_le>().unwrap() == 0x0a0b0c0d);
+//! assert!(reader.read_bytes(2).unwrap() == [0xff, 0x00]);
+//! assert!(reader.read_u8().is_none());
+//! ```
+//!
+//! `Segments` collects a message from several borrowed slices for vectored output.
+use crate::bytes::EndianBytes;
+use crate::decoder::Decode;
+
+/// # Fixed-size encoding
+/// Values that can be encoded into exactly `SIZE` bytes; the counterpart of `Decode`.
+pub trait Encode {
+    const SIZE: usize;
+
+    /// Encode into `bytes`, which is exactly `SIZE` bytes long.
+    fn encode(&self, bytes: &mut [u8]);
+}
+
+/// The endian types, through their stored bytes.
+impl<T: EndianBytes> Encode for T {
+    const SIZE: usize = core::mem::size_of::<T::Bytes>();
+
+    #[inline]
+    fn encode(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(self.to_bytes().as_ref());
+    }
+}
+
+impl<const N: usize> Encode for [u8; N] {
+    const SIZE: usize = N;
+
+    #[inline]
+    fn encode(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(self);
+    }
+}
+
+/// # Buffer reader
+/// Reads endian values from the front of a byte slice.
+#[derive(Clone, Debug)]
+pub struct EndianReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> EndianReader<'a> {
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Number of bytes read so far.
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bytes left to read.
+    pub const fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// The bytes that have not been read yet.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    pub fn read<T: Decode>(&mut self) -> Option<T> {
+        self.read_bytes(T::SIZE).map(T::decode)
+    }
+
+    /// Decode the next value without consuming it.
+    pub fn peek<T: Decode>(&self) -> Option<T> {
+        self.rest().get(..T::SIZE).map(T::decode)
+    }
+
+    pub fn read_u8(&mut self) -> Option<u8> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.rest().get(..len)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Some(array)
+    }
+
+    pub fn skip(&mut self, len: usize) -> Option<()> {
+        self.read_bytes(len).map(|_| ())
+    }
+}
+
+/// # Buffer writer
+/// Writes endian values to the front of a mutable byte slice.
+#[derive(Debug)]
+pub struct EndianWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> EndianWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Number of bytes written so far.
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bytes left in the buffer.
+    pub const fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// The bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    /// Give up the writer, returning the bytes written.
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.pos]
+    }
+
+    pub fn write<T: Encode>(&mut self, value: T) -> Option<()> {
+        value.encode(self.reserve(T::SIZE)?);
+        Some(())
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> Option<()> {
+        self.write_bytes(&[value])
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Option<()> {
+        self.reserve(bytes.len())?.copy_from_slice(bytes);
+        Some(())
+    }
+
+    /// Overwrite a value at `offset` among the bytes already written, e.g. to fill in a length
+    /// field once the payload is known.
+    pub fn write_at<T: Encode>(&mut self, offset: usize, value: T) -> Option<()> {
+        let end = offset.checked_add(T::SIZE)?;
+        value.encode(self.buf[..self.pos].get_mut(offset..end)?);
+        Some(())
+    }
+
+    /// Advance over the next `len` bytes, returning them for the caller to fill in.
+    pub fn reserve(&mut self, len: usize) -> Option<&mut [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let bytes = self.buf.get_mut(self.pos..end)?;
+        self.pos = end;
+        Some(bytes)
+    }
+
+    /// Split off the bytes written so far, e.g. to add them to `Segments`, and continue writing
+    /// into the rest of the buffer from position 0.
+    pub fn take_written(&mut self) -> &'a mut [u8] {
+        let buf = core::mem::take(&mut self.buf);
+        let (written, rest) = buf.split_at_mut(self.pos);
+        self.buf = rest;
+        self.pos = 0;
+        written
+    }
+}
+
+/// # Vectored output
+/// Up to `N` borrowed byte slices that make up one message, like an iovec: headers written with
+/// an `EndianWriter` and split off with `take_written`, and payloads that stay where they are
+/// instead of being copied after the header. With the `std` feature, `write_to` hands them to
+/// `Write::write_vectored`, which becomes a single `writev` on sockets and files.
+/// ## Example
+/// ```
+/// use endiantype::io::*;
+/// use endiantype::*;
+///
+/// let payload = [0xaa; 1000];
+/// let mut buf = [0u8; 16];
+/// let mut writer = EndianWriter::new(&mut buf);
+/// let mut segments = Segments::<4>::new();
+/// writer.write(u16_be::from_native(1)).unwrap();
+/// writer.write(u32_be::from_native(payload.len() as u32)).unwrap();
+/// segments.push(writer.take_written()).unwrap();
+/// segments.push(&payload).unwrap();
+/// writer.write(u16_be::from_native(0xffff)).unwrap();
+/// segments.push(writer.take_written()).unwrap();
+/// assert!(segments.len() == 3 && segments.total_len() == 1008);
+///
+/// let mut out = Vec::new();
+/// segments.write_to(&mut out).unwrap();
+/// assert!(out[..6] == [0, 1, 0, 0, 0x03, 0xe8] && out[1006..] == [0xff, 0xff]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Segments<'a, const N: usize> {
+    segments: [&'a [u8]; N],
+    len: usize,
+}
+
+impl<'a, const N: usize> Segments<'a, N> {
+    pub const fn new() -> Self {
+        Self {
+            segments: [&[]; N],
+            len: 0,
+        }
+    }
+
+    /// Number of segments.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Total number of bytes in all segments.
+    pub fn total_len(&self) -> usize {
+        self.as_slices().iter().map(|segment| segment.len()).sum()
+    }
+
+    pub fn as_slices(&self) -> &[&'a [u8]] {
+        &self.segments[..self.len]
+    }
+
+    /// Append a segment, or return `None` if all `N` are in use.
+    pub fn push(&mut self, segment: &'a [u8]) -> Option<()> {
+        *self.segments.get_mut(self.len)? = segment;
+        self.len += 1;
+        Some(())
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Copy the segments one after another into the front of `buf`, returning the number of bytes
+    /// copied, or `None` if `buf` is too short.
+    pub fn copy_to(&self, buf: &mut [u8]) -> Option<usize> {
+        let mut writer = EndianWriter::new(buf);
+        for segment in self.as_slices() {
+            writer.write_bytes(segment)?;
+        }
+        Some(writer.position())
+    }
+
+    /// Write all segments with as few `write_vectored` calls as `out` allows, retrying on
+    /// `Interrupted` like `write_all`.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&self, out: &mut W) -> std::io::Result<()> {
+        use std::io::{ErrorKind, IoSlice};
+
+        let mut slices = [IoSlice::new(&[]); N];
+        for (slice, segment) in slices.iter_mut().zip(self.as_slices()) {
+            *slice = IoSlice::new(segment);
+        }
+        let mut slices = &mut slices[..self.len];
+        // Drop leading empty segments, which would otherwise look like a zero-length write.
+        IoSlice::advance_slices(&mut slices, 0);
+        while !slices.is_empty() {
+            match out.write_vectored(slices) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(written) => IoSlice::advance_slices(&mut slices, written),
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for Segments<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
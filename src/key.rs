@@ -0,0 +1,45 @@
+//! # Order-preserving float keys
+//!
+//! Ordered key-value stores compare keys as byte strings. A float's big-endian bytes do not sort
+//! like the float: negative values sort after positive ones, and in reverse among themselves.
+//! Flipping the sign bit of non-negative values, and every bit of negative ones, gives bytes
+//! that sort exactly like `f64::total_cmp`: negative NaNs first, then -inf, negative numbers,
+//! -0.0, +0.0, positive numbers, +inf and positive NaNs last.
+//! ## Example
+//! ```
+//! use endiantype::key::*;
+//! let values = [f64::NEG_INFINITY, -1.5, -0.0, 0.0, 1e-300, 2.0, f64::INFINITY, f64::NAN];
+//! let keys: Vec<_> = values.iter().map(|&v| f64_key(v)).collect();
+//! assert!(keys.windows(2).all(|w| w[0].as_ref() < w[1].as_ref()));
+//! assert!(key_f64(keys[1]) == -1.5);
+//! assert!(f32_key(-0.5).as_ref() < f32_key(0.25).as_ref());
+//! ```
+use crate::types::{u32_be, u64_be};
+
+/// The order-preserving key of `value`.
+pub fn f64_key(value: f64) -> u64_be {
+    let bits = value.to_bits();
+    let mask = if bits >> 63 == 0 { 1 << 63 } else { !0 };
+    u64_be::from_native(bits ^ mask)
+}
+
+/// The float encoded by `f64_key`.
+pub fn key_f64(key: u64_be) -> f64 {
+    let bits = key.to_native();
+    let mask = if bits >> 63 == 1 { 1 << 63 } else { !0 };
+    f64::from_bits(bits ^ mask)
+}
+
+/// The order-preserving key of `value`.
+pub fn f32_key(value: f32) -> u32_be {
+    let bits = value.to_bits();
+    let mask = if bits >> 31 == 0 { 1 << 31 } else { !0 };
+    u32_be::from_native(bits ^ mask)
+}
+
+/// The float encoded by `f32_key`.
+pub fn key_f32(key: u32_be) -> f32 {
+    let bits = key.to_native();
+    let mask = if bits >> 31 == 1 { 1 << 31 } else { !0 };
+    f32::from_bits(bits ^ mask)
+}
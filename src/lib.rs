@@ -7,17 +7,19 @@
 //!
 //! ## Usage
 //! Add this to your Cargo.toml
-//! ```
+//! ```toml
 //! endiantype = "0.1.3"
 //! ```
 //! to use in a `[no_std]` environment, you need to disable default features.
-//! ```
+//! ```toml
 //! endiantype = { version = "0.1.3", default-features = false}
 //! ```
 //! and import endian-ware types from this crate.
 //! ```
 //! use endiantype::types::*;
 //! ```
+//! In a `[no_std]` environment with an allocator, enable the `alloc` feature for the `Vec`
+//! conversions.
 //!
 //! ## Features
 //! Here is some features of `endian_type` crate.
@@ -40,12 +42,242 @@
 //! let b = u32_be::from_native(2);
 //! assert!(a+b == 3);
 //! ```
+//! `f32` and `f64` have endian types too, for IEEE-754 fields in file formats. They compare as
+//! floats, so they are `PartialEq` and `PartialOrd` only.
+//! ```
+//! use endiantype::io::EndianReader;
+//! use endiantype::*;
+//! let gain = EndianReader::new(&[0x3f, 0xc0, 0, 0]).read::<f32_be>().unwrap();
+//! assert!(gain == 1.5 && gain.as_ref() == [0x3f, 0xc0, 0, 0]);
+//! let scale: f64_le = f64::from((gain + 0.5).to_native()).into();
+//! assert!(scale.to_native() == 2.0 && scale == f64_be::from_native(2.0));
+//!
+//! // `total_cmp` sorts a column without converting it first, NaNs last.
+//! let mut column = [f32_be::from_native(f32::NAN), gain, f32_be::from_native(-0.5)];
+//! column.sort_by(f32_be::total_cmp);
+//! assert!(column[0] == -0.5 && column[1] == 1.5 && column[2].is_nan());
+//! assert!(column[2].max(gain) == 1.5 && !column[2].is_finite());
+//!
+//! // Quantizing converts like `as`: towards zero, saturating, NaN as 0.
+//! let sample = f64_be::from_native(-40000.7);
+//! assert!(sample.to_i16_le_saturating() == i16::MIN && sample.to_i32_le_saturating() == -40000);
+//! assert!(column[2].to_u8_be_saturating() == 0);
+//! ```
+//! Compound assignment, `!` and (for signed and float types) unary `-` work the same way.
+//! ```
+//! use endiantype::*;
+//! let mut flags = u16_be::from_native(0x0100);
+//! flags |= 0x0002;
+//! flags += u16_le::from_native(1);
+//! flags <<= 4;
+//! assert!(flags == 0x1030);
+//! assert!(!flags == 0xefcf && -i32_le::from_native(5) == -5);
+//! ```
+//! Shifts take a `u32` amount, like `checked_shl`.
+//! ```
+//! use endiantype::*;
+//! let status = u32_be::from_native(0x0000_a123);
+//! assert!((status >> 12) & 0xf == 0xa && (&status >> 4) & 0xff == 0x12);
+//! assert!(u16_le::from_native(1) << 15 == 0x8000);
+//! ```
+//! The `checked_*` methods of the primitive types return `None` instead of panicking, for
+//! lengths and offsets that come from untrusted input.
+//! ```
+//! use endiantype::*;
+//! let (offset, len) = (u32_be::from_native(0xffff_fff0), u32_be::from_native(0x20));
+//! assert!(offset.checked_add(len).is_none());
+//! assert!(len.checked_div(u32_be::from_native(0)).is_none());
+//! assert!(len.checked_shl(4).unwrap() == 0x200 && len.checked_pow(2).unwrap() == 0x400);
+//! ```
+//! The `wrapping_*` methods are there too, for sequence numbers and checksums meant to wrap.
+//! ```
+//! use endiantype::*;
+//! let seq = u16_be::from_native(0xffff);
+//! assert!(seq.wrapping_add(u16_be::from_native(2)) == 1);
+//! assert!(u32_le::from_native(1).wrapping_neg() == u32::MAX);
+//! assert!(u8_be::from_native(1).wrapping_shl(9) == 2);
+//! // The `overflowing_*` methods report the overflow alongside the wrapped value.
+//! assert!(seq.overflowing_add(u16_be::from_native(2)) == (u16_be::from_native(1), true));
+//! ```
+//! `to_bytes` and `from_bytes` convert to and from the stored bytes as arrays, also in constant
+//! expressions; `to_le_bytes` and friends give the bytes of the value in a chosen order.
+//! ```
+//! use endiantype::*;
+//! const MAGIC: u32_be = u32_be::from_bytes(*b"\x7fELF");
+//! assert!(MAGIC == 0x7f454c46 && MAGIC.to_bytes() == *b"\x7fELF");
+//! assert!(MAGIC.to_le_bytes() == *b"FLE\x7f");
+//! assert!(f32_le::from_be_bytes([0x3f, 0x80, 0, 0]).to_bytes() == [0, 0, 0x80, 0x3f]);
+//! // `as_bytes` and `as_bytes_mut` borrow the stored bytes without copying them.
+//! let mut len = u16_be::from_native(0x0102);
+//! let mut packet = [0u8; 4];
+//! packet[2..].copy_from_slice(len.as_bytes());
+//! len.as_bytes_mut()[0] = 0xff;
+//! assert!(packet == [0, 0, 1, 2] && len == 0xff02);
+//! ```
+//! Integer endian types are `Ord`, so they can be sorted and used as `BTreeMap` keys. Unsigned big-endian
+//! values are ordered by their stored bytes, which sort like the numbers, without converting.
+//! ```
+//! use endiantype::*;
+//! let mut keys = [u64_be::from_native(300), u64_be::from_native(2), u64_be::from_native(70000)];
+//! keys.sort();
+//! assert!(keys == [u64_be::from_native(2), u64_be::from_native(300), u64_be::from_native(70000)]);
+//! assert!(keys.binary_search(&u64_be::from_native(300)) == Ok(1));
+//! ```
+//! Operators and comparisons also take references, so iterator pipelines need no copies.
+//! ```
+//! use endiantype::*;
+//! let lens = [u32_be::from_native(3), u32_be::from_native(4)];
+//! let total = lens.iter().fold(0u32, |acc, len| acc + len);
+//! assert!(total == 7);
+//! assert!(&lens[0] + &lens[1] == 7 && lens[0] + &lens[1] == 7);
+//! assert!(lens.iter().any(|len| len == &u32_le::from_native(4)));
+//! assert!(&lens[0] < &lens[1]);
+//! ```
+//! Length arithmetic that must never wrap can use the strict methods, which panic on overflow
+//! in every build profile.
+//! ```should_panic
+//! use endiantype::*;
+//! let len = u16_be::from_native(0xfff0);
+//! let header = u16_be::from_native(4);
+//! assert!(len.strict_add(header) == 0xfff4);
+//! len.strict_add(u16_be::from_native(0x10));
+//! ```
+//! Static tables can be checked at compile time with the const comparisons.
+//! ```
+//! use core::cmp::Ordering;
+//! use endiantype::*;
+//! const MAGIC: u32_be = u32_be::from_native(0xcafebabe);
+//! const TABLE: [u32_be; 2] = [u32_be::from_native(0xcafebabe), u32_be::from_native(0xfeedface)];
+//! const _: () = assert!(TABLE[0].const_eq(&MAGIC));
+//! const _: () = assert!(matches!(TABLE[0].const_cmp(&TABLE[1]), Ordering::Less));
+//! // The same table in the other byte order, built at compile time.
+//! const TABLE_LE: [u32_le; 2] = [u32_le::from_be_value(TABLE[0]), u32_le::from_be_value(TABLE[1])];
+//! const _: () = assert!(u32_be::from_le_value(TABLE_LE[1]).const_eq(&TABLE[1]));
+//! assert!(TABLE_LE[0] == 0xcafebabe);
+//! ```
+//! Values computed with wider native integers can be stored into narrower fields with range checking.
+//! ```
+//! use core::convert::TryFrom;
+//! use endiantype::*;
+//! let len = u16_be::try_from(1500u64).unwrap();
+//! assert!(len == 1500);
+//! assert!(u16_be::try_from(70000u64).is_err());
+//! assert!(i16_le::try_from(-5i64).unwrap() == -5);
+//! ```
+//! Endian types can be viewed as their stored bytes, e.g. to feed a hasher or a writer.
+//! ```
+//! use endiantype::*;
+//! let magic = u32_be::from_native(0xdeadbeef);
+//! assert!(magic.as_ref() == [0xde, 0xad, 0xbe, 0xef]);
+//! ```
+//! Unsigned types have the carry and widening building blocks of multi-limb arithmetic.
+//! ```
+//! use endiantype::*;
+//! // 0x1_ffffffffffffffff + 1 over two big-endian limbs, least significant first.
+//! let a = [u64_be::from_native(u64::MAX), u64_be::from_native(1)];
+//! let b = [u64_be::from_native(1), u64_be::from_native(0)];
+//! let (low, carry) = a[0].carrying_add(b[0], false);
+//! let (high, carry) = a[1].carrying_add(b[1], carry);
+//! assert!(low == 0 && high == 2 && !carry);
+//! let (low, high) = u128_le::from_native(u128::MAX).widening_mul(u128_le::from_native(2));
+//! assert!(low == u128::MAX - 1 && high == 1);
+//! ```
+//! Tables of endian values can be generated from native ranges.
+//! ```
+//! use endiantype::*;
+//! let table: Vec<u16_be> = u16_be::range(0..100).collect();
+//! assert!(table.len() == 100 && table[42] == 42);
+//! let evens: Vec<u8_le> = u8_le::iter_from_step(0, 2).collect();
+//! assert!(evens.len() == 128 && evens[127] == 254);
+//! assert!(u8_le::iter_from_step(7, 0).eq([u8_le::from_native(7)]));
+//! ```
 #![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
 use core::cmp::Ordering;
-use core::ops::{Add, BitAnd, BitOr, BitXor, Sub};
+use core::convert::TryFrom;
+use core::iter::Map;
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, DivAssign,
+    MulAssign, Neg, Not, Range, RangeInclusive, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
 
+pub use bytes::EndianError;
+pub use endianness::Endianness;
+pub use fourcc::FourCC;
 pub use types::*;
 
+/// Invokes `$macro!(T)` for every integer type `T` that the endian types wrap; modules that
+/// implement something per type use it instead of their own list.
+macro_rules! for_each_int {
+    ($macro: ident) => {
+        $macro!(u8);
+        $macro!(u16);
+        $macro!(u32);
+        $macro!(u64);
+        $macro!(u128);
+        $macro!(usize);
+        $macro!(i8);
+        $macro!(i16);
+        $macro!(i32);
+        $macro!(i64);
+        $macro!(i128);
+        $macro!(isize);
+    };
+}
+
+/// Invokes `$macro!(T)` for every type `T` that the endian types wrap, integers and floats.
+macro_rules! for_each_primitive {
+    ($macro: ident) => {
+        for_each_int!($macro);
+        $macro!(f32);
+        $macro!(f64);
+    };
+}
+
+pub mod ascii;
+#[macro_use]
+mod bitfield;
+pub mod bitorder;
+pub mod bitset;
+pub mod both;
+pub mod bounded;
+pub mod byteorder;
+pub mod bytes;
+pub mod cast;
+pub mod cell;
+pub mod checksum;
+pub mod decoder;
+pub mod endianness;
+pub mod format;
+pub mod fourcc;
+pub mod frame;
+pub mod hex;
+pub mod hexdump;
+pub mod io;
+pub mod key;
+pub mod native;
+pub mod net;
+mod packed;
+pub mod packet;
+pub mod payload;
+pub mod pcm;
+pub mod pixel;
+pub mod proto;
+pub mod pstring;
+pub mod ptr;
+pub mod pystruct;
+pub mod register;
+pub mod ring;
+pub mod search;
+pub mod slice;
+pub mod timestamp;
+pub mod utf16;
+#[cfg(feature = "alloc")]
+pub mod vec;
+pub mod wasm;
+pub mod wire;
+
 /// # Little endian types
 /// ## Example
 /// ```
@@ -60,7 +292,7 @@ pub use types::*;
 /// let deadbeef: u32_le = u32_be::from_native(0xdeadbeef).into();
 /// assert!(deadbeef == 0xdeadbeef);
 /// ```
-#[derive(Copy, Clone, Debug, Hash)]
+#[derive(Copy, Clone, Debug, Default, Hash)]
 #[repr(transparent)]
 pub struct LittleEndian<T>(T);
 
@@ -78,23 +310,52 @@ pub struct LittleEndian<T>(T);
 /// let deadbeef: u32_be = u32_le::from_native(0xdeadbeef).into();
 /// assert!(deadbeef == 0xdeadbeef);
 /// ```
-#[derive(Copy, Clone, Debug, Hash)]
+#[derive(Copy, Clone, Debug, Default, Hash)]
 #[repr(transparent)]
 pub struct BigEndian<T>(T);
 
 macro_rules! impl_endian {
-    ($type_name:ident) => {
+    ($type_name:ident, $be_order: ident) => {
         impl_endian_base!($type_name);
         impl_endian_from_native!($type_name, LittleEndian);
         impl_endian_from_native!($type_name, BigEndian);
         impl_endian_from_each!($type_name);
+        impl_endian_as_ref!($type_name, LittleEndian);
+        impl_endian_as_ref!($type_name, BigEndian);
+        impl_endian_bytes!($type_name, LittleEndian);
+        impl_endian_bytes!($type_name, BigEndian);
+        impl_endian_range!($type_name, LittleEndian);
+        impl_endian_range!($type_name, BigEndian);
+        impl_endian_strict!($type_name, LittleEndian);
+        impl_endian_strict!($type_name, BigEndian);
+        impl_endian_checked!($type_name, LittleEndian);
+        impl_endian_checked!($type_name, BigEndian);
+        impl_endian_wrapping!($type_name, LittleEndian);
+        impl_endian_wrapping!($type_name, BigEndian);
+        impl_endian_overflowing!($type_name, LittleEndian);
+        impl_endian_overflowing!($type_name, BigEndian);
         impl_endian_op!($type_name, BitAnd, bitand);
         impl_endian_op!($type_name, BitOr, bitor);
         impl_endian_op!($type_name, BitXor, bitxor);
         impl_endian_op!($type_name, Add, add);
         impl_endian_op!($type_name, Sub, sub);
+        impl_endian_not!($type_name, LittleEndian);
+        impl_endian_not!($type_name, BigEndian);
+        impl_endian_op_assign!($type_name, AddAssign, add_assign);
+        impl_endian_op_assign!($type_name, SubAssign, sub_assign);
+        impl_endian_op_assign!($type_name, MulAssign, mul_assign);
+        impl_endian_op_assign!($type_name, DivAssign, div_assign);
+        impl_endian_op_assign!($type_name, BitAndAssign, bitand_assign);
+        impl_endian_op_assign!($type_name, BitOrAssign, bitor_assign);
+        impl_endian_op_assign!($type_name, BitXorAssign, bitxor_assign);
+        impl_endian_op_assign!($type_name, ShlAssign, shl_assign);
+        impl_endian_op_assign!($type_name, ShrAssign, shr_assign);
+        impl_endian_shift!($type_name, Shl, shl);
+        impl_endian_shift!($type_name, Shr, shr);
         impl_endian_cmp!($type_name, PartialEq, eq, bool);
         impl_endian_cmp!($type_name, PartialOrd, partial_cmp, Option<Ordering>);
+        impl_endian_ord!($type_name, LittleEndian, native);
+        impl_endian_ord!($type_name, BigEndian, $be_order);
     };
 }
 macro_rules! impl_endian_base {
@@ -104,11 +365,21 @@ macro_rules! impl_endian_base {
                 Self(data.to_be())
             }
 
+            /// The same value in big endian, usable in constant expressions.
+            pub const fn from_le_value(data: LittleEndian<$type_name>) -> Self {
+                Self(data.0.swap_bytes())
+            }
+
             pub const fn new(data: $type_name) -> Self {
                 Self(data)
             }
 
-            pub fn to_native(&self) -> $type_name {
+            /// The stored representation, without any conversion; the inverse of `new`.
+            pub const fn into_inner(self) -> $type_name {
+                self.0
+            }
+
+            pub const fn to_native(&self) -> $type_name {
                 match () {
                     #[cfg(target_endian = "big")]
                     () => self.0,
@@ -116,17 +387,50 @@ macro_rules! impl_endian_base {
                     () => self.0.swap_bytes(),
                 }
             }
+
+            /// A guard for operating on the native value, stored back when it is dropped.
+            pub fn native_mut(&mut self) -> crate::native::NativeMut<'_, Self, $type_name> {
+                let value = self.to_native();
+                crate::native::NativeMut::new(self, value)
+            }
+
+            /// `self == other`, usable in constant expressions.
+            pub const fn const_eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+
+            /// `self.cmp(other)`, usable in constant expressions.
+            pub const fn const_cmp(&self, other: &Self) -> Ordering {
+                let (a, b) = (self.to_native(), other.to_native());
+                if a < b {
+                    Ordering::Less
+                } else if a > b {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            }
         }
 
         impl LittleEndian<$type_name> {
             pub const fn from_native(data: $type_name) -> Self {
                 Self(data.to_le())
             }
+
+            /// The same value in little endian, usable in constant expressions.
+            pub const fn from_be_value(data: BigEndian<$type_name>) -> Self {
+                Self(data.0.swap_bytes())
+            }
             pub const fn new(data: $type_name) -> Self {
                 Self(data)
             }
 
-            pub fn to_native(&self) -> $type_name {
+            /// The stored representation, without any conversion; the inverse of `new`.
+            pub const fn into_inner(self) -> $type_name {
+                self.0
+            }
+
+            pub const fn to_native(&self) -> $type_name {
                 match () {
                     #[cfg(target_endian = "big")]
                     () => self.0.swap_bytes(),
@@ -134,6 +438,246 @@ macro_rules! impl_endian_base {
                     () => self.0,
                 }
             }
+
+            /// A guard for operating on the native value, stored back when it is dropped.
+            pub fn native_mut(&mut self) -> crate::native::NativeMut<'_, Self, $type_name> {
+                let value = self.to_native();
+                crate::native::NativeMut::new(self, value)
+            }
+
+            /// `self == other`, usable in constant expressions.
+            pub const fn const_eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+
+            /// `self.cmp(other)`, usable in constant expressions.
+            pub const fn const_cmp(&self, other: &Self) -> Ordering {
+                let (a, b) = (self.to_native(), other.to_native());
+                if a < b {
+                    Ordering::Less
+                } else if a > b {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_strict {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// `self + rhs`, panicking on overflow even in release builds.
+            #[inline]
+            #[track_caller]
+            pub fn strict_add(self, rhs: Self) -> Self {
+                match self.to_native().checked_add(rhs.to_native()) {
+                    Some(sum) => Self::from_native(sum),
+                    None => panic!("attempt to add with overflow"),
+                }
+            }
+
+            /// `self - rhs`, panicking on overflow even in release builds.
+            #[inline]
+            #[track_caller]
+            pub fn strict_sub(self, rhs: Self) -> Self {
+                match self.to_native().checked_sub(rhs.to_native()) {
+                    Some(diff) => Self::from_native(diff),
+                    None => panic!("attempt to subtract with overflow"),
+                }
+            }
+
+            /// `self * rhs`, panicking on overflow even in release builds.
+            #[inline]
+            #[track_caller]
+            pub fn strict_mul(self, rhs: Self) -> Self {
+                match self.to_native().checked_mul(rhs.to_native()) {
+                    Some(product) => Self::from_native(product),
+                    None => panic!("attempt to multiply with overflow"),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_checked {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// `self + rhs`, or `None` on overflow.
+            #[inline]
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                self.to_native()
+                    .checked_add(rhs.to_native())
+                    .map(Self::from_native)
+            }
+
+            /// `self - rhs`, or `None` on overflow.
+            #[inline]
+            pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                self.to_native()
+                    .checked_sub(rhs.to_native())
+                    .map(Self::from_native)
+            }
+
+            /// `self * rhs`, or `None` on overflow.
+            #[inline]
+            pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+                self.to_native()
+                    .checked_mul(rhs.to_native())
+                    .map(Self::from_native)
+            }
+
+            /// `self / rhs`, or `None` if `rhs` is zero or the division overflows.
+            #[inline]
+            pub fn checked_div(self, rhs: Self) -> Option<Self> {
+                self.to_native()
+                    .checked_div(rhs.to_native())
+                    .map(Self::from_native)
+            }
+
+            /// `self % rhs`, or `None` if `rhs` is zero or the division overflows.
+            #[inline]
+            pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+                self.to_native()
+                    .checked_rem(rhs.to_native())
+                    .map(Self::from_native)
+            }
+
+            /// `self << rhs`, or `None` if `rhs` is not less than the number of bits.
+            #[inline]
+            pub fn checked_shl(self, rhs: u32) -> Option<Self> {
+                self.to_native().checked_shl(rhs).map(Self::from_native)
+            }
+
+            /// `self >> rhs`, or `None` if `rhs` is not less than the number of bits.
+            #[inline]
+            pub fn checked_shr(self, rhs: u32) -> Option<Self> {
+                self.to_native().checked_shr(rhs).map(Self::from_native)
+            }
+
+            /// `self` raised to `exp`, or `None` on overflow.
+            #[inline]
+            pub fn checked_pow(self, exp: u32) -> Option<Self> {
+                self.to_native().checked_pow(exp).map(Self::from_native)
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_wrapping {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// `self + rhs`, wrapping around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_add(self, rhs: Self) -> Self {
+                Self::from_native(self.to_native().wrapping_add(rhs.to_native()))
+            }
+
+            /// `self - rhs`, wrapping around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_sub(self, rhs: Self) -> Self {
+                Self::from_native(self.to_native().wrapping_sub(rhs.to_native()))
+            }
+
+            /// `self * rhs`, wrapping around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_mul(self, rhs: Self) -> Self {
+                Self::from_native(self.to_native().wrapping_mul(rhs.to_native()))
+            }
+
+            /// `self / rhs`, wrapping `MIN / -1` around to `MIN`. Panics if `rhs` is zero.
+            #[inline]
+            pub fn wrapping_div(self, rhs: Self) -> Self {
+                Self::from_native(self.to_native().wrapping_div(rhs.to_native()))
+            }
+
+            /// `self % rhs`, with `MIN % -1` as 0. Panics if `rhs` is zero.
+            #[inline]
+            pub fn wrapping_rem(self, rhs: Self) -> Self {
+                Self::from_native(self.to_native().wrapping_rem(rhs.to_native()))
+            }
+
+            /// `-self`, wrapping around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_neg(self) -> Self {
+                Self::from_native(self.to_native().wrapping_neg())
+            }
+
+            /// `self << rhs`, with `rhs` taken modulo the number of bits.
+            #[inline]
+            pub fn wrapping_shl(self, rhs: u32) -> Self {
+                Self::from_native(self.to_native().wrapping_shl(rhs))
+            }
+
+            /// `self >> rhs`, with `rhs` taken modulo the number of bits.
+            #[inline]
+            pub fn wrapping_shr(self, rhs: u32) -> Self {
+                Self::from_native(self.to_native().wrapping_shr(rhs))
+            }
+
+            /// `self` raised to `exp`, wrapping around at the boundary of the type.
+            #[inline]
+            pub fn wrapping_pow(self, exp: u32) -> Self {
+                Self::from_native(self.to_native().wrapping_pow(exp))
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_overflowing {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// `self + rhs` wrapped, and whether it overflowed.
+            #[inline]
+            pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                let (sum, overflow) = self.to_native().overflowing_add(rhs.to_native());
+                (Self::from_native(sum), overflow)
+            }
+
+            /// `self - rhs` wrapped, and whether it overflowed.
+            #[inline]
+            pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                let (diff, overflow) = self.to_native().overflowing_sub(rhs.to_native());
+                (Self::from_native(diff), overflow)
+            }
+
+            /// `self * rhs` wrapped, and whether it overflowed.
+            #[inline]
+            pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                let (product, overflow) = self.to_native().overflowing_mul(rhs.to_native());
+                (Self::from_native(product), overflow)
+            }
+
+            /// `-self` wrapped, and whether it overflowed.
+            #[inline]
+            pub fn overflowing_neg(self) -> (Self, bool) {
+                let (neg, overflow) = self.to_native().overflowing_neg();
+                (Self::from_native(neg), overflow)
+            }
+
+            /// `self << rhs` with `rhs` taken modulo the number of bits, and whether it was at
+            /// least the number of bits.
+            #[inline]
+            pub fn overflowing_shl(self, rhs: u32) -> (Self, bool) {
+                let (shifted, overflow) = self.to_native().overflowing_shl(rhs);
+                (Self::from_native(shifted), overflow)
+            }
+
+            /// `self >> rhs` with `rhs` taken modulo the number of bits, and whether it was at
+            /// least the number of bits.
+            #[inline]
+            pub fn overflowing_shr(self, rhs: u32) -> (Self, bool) {
+                let (shifted, overflow) = self.to_native().overflowing_shr(rhs);
+                (Self::from_native(shifted), overflow)
+            }
+
+            /// `self` raised to `exp` wrapped, and whether it overflowed.
+            #[inline]
+            pub fn overflowing_pow(self, exp: u32) -> (Self, bool) {
+                let (power, overflow) = self.to_native().overflowing_pow(exp);
+                (Self::from_native(power), overflow)
+            }
         }
     };
 }
@@ -174,6 +718,233 @@ macro_rules! impl_endian_from_each {
     };
 }
 
+macro_rules! impl_endian_as_ref {
+    ($type_name: ident, $endian_name: ident) => {
+        impl AsRef<[u8]> for $endian_name<$type_name> {
+            /// Stored bytes, in the byte order of the endian type.
+            #[inline]
+            fn as_ref(&self) -> &[u8] {
+                self.as_bytes()
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_bytes {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// The stored bytes, in the byte order of the endian type.
+            #[inline]
+            pub const fn to_bytes(self) -> [u8; core::mem::size_of::<$type_name>()] {
+                self.0.to_ne_bytes()
+            }
+
+            /// The value whose stored bytes are `bytes`; the inverse of `to_bytes`.
+            #[inline]
+            pub const fn from_bytes(bytes: [u8; core::mem::size_of::<$type_name>()]) -> Self {
+                Self::new($type_name::from_ne_bytes(bytes))
+            }
+
+            /// The stored bytes, borrowed in place.
+            #[inline]
+            pub fn as_bytes(&self) -> &[u8; core::mem::size_of::<$type_name>()] {
+                // SAFETY: the wrapper is `repr(transparent)` over a primitive number, so it is
+                // `size_of::<$type_name>()` initialized bytes without padding, and a byte array
+                // needs no alignment.
+                unsafe {
+                    &*(self as *const Self as *const [u8; core::mem::size_of::<$type_name>()])
+                }
+            }
+
+            /// The stored bytes, to be overwritten in place.
+            #[inline]
+            pub fn as_bytes_mut(&mut self) -> &mut [u8; core::mem::size_of::<$type_name>()] {
+                // SAFETY: as for `as_bytes`; any bytes are a valid primitive number.
+                unsafe {
+                    &mut *(self as *mut Self as *mut [u8; core::mem::size_of::<$type_name>()])
+                }
+            }
+
+            /// The value as little-endian bytes, whatever the byte order of the type.
+            #[inline]
+            pub const fn to_le_bytes(self) -> [u8; core::mem::size_of::<$type_name>()] {
+                self.to_native().to_le_bytes()
+            }
+
+            /// The value as big-endian bytes, whatever the byte order of the type.
+            #[inline]
+            pub const fn to_be_bytes(self) -> [u8; core::mem::size_of::<$type_name>()] {
+                self.to_native().to_be_bytes()
+            }
+
+            /// The value as bytes in the byte order of the target.
+            #[inline]
+            pub const fn to_ne_bytes(self) -> [u8; core::mem::size_of::<$type_name>()] {
+                self.to_native().to_ne_bytes()
+            }
+
+            /// The value of little-endian `bytes`, whatever the byte order of the type.
+            #[inline]
+            pub const fn from_le_bytes(bytes: [u8; core::mem::size_of::<$type_name>()]) -> Self {
+                Self::from_native($type_name::from_le_bytes(bytes))
+            }
+
+            /// The value of big-endian `bytes`, whatever the byte order of the type.
+            #[inline]
+            pub const fn from_be_bytes(bytes: [u8; core::mem::size_of::<$type_name>()]) -> Self {
+                Self::from_native($type_name::from_be_bytes(bytes))
+            }
+
+            /// The value of `bytes` in the byte order of the target.
+            #[inline]
+            pub const fn from_ne_bytes(bytes: [u8; core::mem::size_of::<$type_name>()]) -> Self {
+                Self::from_native($type_name::from_ne_bytes(bytes))
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_range {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// Iterate over a native range, yielding endian values.
+            #[inline]
+            pub fn range(
+                range: Range<$type_name>,
+            ) -> Map<Range<$type_name>, fn($type_name) -> Self> {
+                range.map(Self::from_native)
+            }
+
+            /// Iterate over a native inclusive range, yielding endian values.
+            #[inline]
+            pub fn range_inclusive(
+                range: RangeInclusive<$type_name>,
+            ) -> Map<RangeInclusive<$type_name>, fn($type_name) -> Self> {
+                range.map(Self::from_native)
+            }
+
+            /// Iterate from `start` in increments of `step`, stopping before the value overflows.
+            /// A `step` of 0 yields `start` once instead of repeating it forever.
+            #[inline]
+            pub fn iter_from_step(
+                start: $type_name,
+                step: $type_name,
+            ) -> impl Iterator<Item = Self> {
+                core::iter::successors(Some(start), move |n| match step {
+                    0 => None,
+                    _ => n.checked_add(step),
+                })
+                .map(Self::from_native)
+            }
+        }
+    };
+}
+
+/// Full product of two unsigned integers as `(low, high)` halves.
+trait WideMul: Sized {
+    fn wide_mul(self, rhs: Self) -> (Self, Self);
+}
+
+macro_rules! impl_wide_mul {
+    ($($type_name: ident => $wide_name: ident),*) => {
+        $(
+            impl WideMul for $type_name {
+                #[inline]
+                fn wide_mul(self, rhs: Self) -> (Self, Self) {
+                    let product = self as $wide_name * rhs as $wide_name;
+                    (product as $type_name, (product >> $type_name::BITS) as $type_name)
+                }
+            }
+        )*
+    };
+}
+
+impl_wide_mul!(u8 => u16, u16 => u32, u32 => u64, u64 => u128, usize => u128);
+
+impl WideMul for u128 {
+    fn wide_mul(self, rhs: Self) -> (Self, Self) {
+        const MASK: u128 = u64::MAX as u128;
+        let (a1, a0) = (self >> 64, self & MASK);
+        let (b1, b0) = (rhs >> 64, rhs & MASK);
+        let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+        let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+        let low = (p00 & MASK) | (mid << 64);
+        let high = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+        (low, high)
+    }
+}
+
+macro_rules! impl_endian_bigint {
+    ($($type_name: ident),*) => {
+        $(
+            impl_endian_bigint_each!($type_name, LittleEndian);
+            impl_endian_bigint_each!($type_name, BigEndian);
+        )*
+    };
+}
+
+macro_rules! impl_endian_bigint_each {
+    ($type_name: ident, $endian_name: ident) => {
+        impl $endian_name<$type_name> {
+            /// `self + rhs + carry`, with the carry out of the top bit.
+            #[inline]
+            pub fn carrying_add(self, rhs: Self, carry: bool) -> (Self, bool) {
+                let (sum, c1) = self.to_native().overflowing_add(rhs.to_native());
+                let (sum, c2) = sum.overflowing_add(carry as $type_name);
+                (Self::from_native(sum), c1 || c2)
+            }
+
+            /// `self - rhs - borrow`, with the borrow out of the top bit.
+            #[inline]
+            pub fn borrowing_sub(self, rhs: Self, borrow: bool) -> (Self, bool) {
+                let (diff, b1) = self.to_native().overflowing_sub(rhs.to_native());
+                let (diff, b2) = diff.overflowing_sub(borrow as $type_name);
+                (Self::from_native(diff), b1 || b2)
+            }
+
+            /// The full product `self * rhs` as `(low, high)` halves.
+            #[inline]
+            pub fn widening_mul(self, rhs: Self) -> (Self, Self) {
+                let (low, high) = self.to_native().wide_mul(rhs.to_native());
+                (Self::from_native(low), Self::from_native(high))
+            }
+
+            /// `self * rhs + carry` as `(low, high)` halves, which cannot overflow.
+            #[inline]
+            pub fn carrying_mul(self, rhs: Self, carry: Self) -> (Self, Self) {
+                let (low, high) = self.to_native().wide_mul(rhs.to_native());
+                let (low, c) = low.overflowing_add(carry.to_native());
+                (
+                    Self::from_native(low),
+                    Self::from_native(high + c as $type_name),
+                )
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_try_from {
+    ($type_name: ident, $($from_name: ident),*) => {
+        $(
+            impl TryFrom<$from_name> for BigEndian<$type_name> {
+                type Error = <$type_name as TryFrom<$from_name>>::Error;
+                #[inline]
+                fn try_from(data: $from_name) -> Result<Self, Self::Error> {
+                    $type_name::try_from(data).map(Self::from_native)
+                }
+            }
+
+            impl TryFrom<$from_name> for LittleEndian<$type_name> {
+                type Error = <$type_name as TryFrom<$from_name>>::Error;
+                #[inline]
+                fn try_from(data: $from_name) -> Result<Self, Self::Error> {
+                    $type_name::try_from(data).map(Self::from_native)
+                }
+            }
+        )*
+    };
+}
+
 macro_rules! impl_endian_cmp_each {
     ($type_name: ident, $endian_name: ident, $other_endian_name: ident, $trait_name: ident, $trait_func_name: ident, $return_type: ty) => {
         impl $trait_name<$other_endian_name<$type_name>> for $endian_name<$type_name> {
@@ -202,6 +973,33 @@ macro_rules! impl_endian_cmp_native {
     };
 }
 
+/// Implements `&lhs op &rhs`, `&lhs op rhs` and `lhs op &rhs` in terms of `lhs op rhs`.
+macro_rules! forward_ref_op {
+    ($lhs: ty, $rhs: ty, $trait_name: ident, $trait_func_name: ident) => {
+        impl<'a> $trait_name<$rhs> for &'a $lhs {
+            type Output = <$lhs as $trait_name<$rhs>>::Output;
+            #[inline]
+            fn $trait_func_name(self, rhs: $rhs) -> Self::Output {
+                (*self).$trait_func_name(rhs)
+            }
+        }
+        impl<'a> $trait_name<&'a $rhs> for $lhs {
+            type Output = <$lhs as $trait_name<$rhs>>::Output;
+            #[inline]
+            fn $trait_func_name(self, rhs: &'a $rhs) -> Self::Output {
+                self.$trait_func_name(*rhs)
+            }
+        }
+        impl<'a, 'b> $trait_name<&'a $rhs> for &'b $lhs {
+            type Output = <$lhs as $trait_name<$rhs>>::Output;
+            #[inline]
+            fn $trait_func_name(self, rhs: &'a $rhs) -> Self::Output {
+                (*self).$trait_func_name(*rhs)
+            }
+        }
+    };
+}
+
 macro_rules! impl_endian_op_each {
     ($type_name: ident, $endian_name: ident, $other_endian_name: ident, $trait_name: ident, $trait_func_name: ident) => {
         impl $trait_name<$other_endian_name<$type_name>> for $endian_name<$type_name> {
@@ -213,6 +1011,12 @@ macro_rules! impl_endian_op_each {
                 )
             }
         }
+        forward_ref_op!(
+            $endian_name<$type_name>,
+            $other_endian_name<$type_name>,
+            $trait_name,
+            $trait_func_name
+        );
     };
 }
 
@@ -232,6 +1036,18 @@ macro_rules! impl_endian_op_native {
                 self.$trait_func_name(rhs.to_native())
             }
         }
+        forward_ref_op!(
+            $endian_name<$type_name>,
+            $type_name,
+            $trait_name,
+            $trait_func_name
+        );
+        forward_ref_op!(
+            $type_name,
+            $endian_name<$type_name>,
+            $trait_name,
+            $trait_func_name
+        );
     };
 }
 
@@ -270,6 +1086,176 @@ macro_rules! impl_endian_op {
     };
 }
 
+/// Implements `to_<int>_<le|be>_saturating` on endian floats, converting with `as`: towards zero,
+/// saturating at the bounds of the integer, with NaN as 0.
+macro_rules! impl_endian_float_to_int {
+    ($type_name: ident; $($conversions: tt)*) => {
+        impl_endian_float_to_int!(@each $type_name, LittleEndian; $($conversions)*);
+        impl_endian_float_to_int!(@each $type_name, BigEndian; $($conversions)*);
+    };
+    (
+        @each $type_name: ident, $endian_name: ident;
+        $($func_name: ident: $int_name: ident, $int_endian_name: ident;)*
+    ) => {
+        impl $endian_name<$type_name> {
+            $(
+                /// The value rounded towards zero and saturated to the integer; NaN is 0.
+                #[inline]
+                pub fn $func_name(self) -> $int_endian_name<$int_name> {
+                    $int_endian_name::<$int_name>::from_native(self.to_native() as $int_name)
+                }
+            )*
+        }
+    };
+}
+
+/// Implements `!value` on the stored representation, since flipping every bit does not depend on
+/// the byte order.
+macro_rules! impl_endian_not {
+    ($type_name: ident, $endian_name: ident) => {
+        impl Not for $endian_name<$type_name> {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                Self(!self.0)
+            }
+        }
+
+        impl<'a> Not for &'a $endian_name<$type_name> {
+            type Output = $endian_name<$type_name>;
+
+            #[inline]
+            fn not(self) -> Self::Output {
+                !*self
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_neg {
+    ($($type_name: ident),*) => {
+        $(
+            impl_endian_neg!(@each $type_name, LittleEndian);
+            impl_endian_neg!(@each $type_name, BigEndian);
+        )*
+    };
+    (@each $type_name: ident, $endian_name: ident) => {
+        impl Neg for $endian_name<$type_name> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self::from_native(-self.to_native())
+            }
+        }
+
+        impl<'a> Neg for &'a $endian_name<$type_name> {
+            type Output = $endian_name<$type_name>;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                -*self
+            }
+        }
+    };
+}
+
+/// Implements `lhs op= rhs` for an endian `lhs` and a native, little-endian or big-endian `rhs`,
+/// by value or by reference, operating on the native value.
+macro_rules! impl_endian_op_assign {
+    ($type_name: ident, $trait_name: ident, $trait_func_name: ident) => {
+        impl_endian_op_assign!(@each $type_name, LittleEndian, $trait_name, $trait_func_name);
+        impl_endian_op_assign!(@each $type_name, BigEndian, $trait_name, $trait_func_name);
+    };
+    (@each $type_name: ident, $endian_name: ident, $trait_name: ident, $trait_func_name: ident) => {
+        impl_endian_op_assign!(
+            @rhs $type_name, $endian_name, $type_name, $trait_name, $trait_func_name
+        );
+        impl_endian_op_assign!(
+            @rhs $type_name, $endian_name, LittleEndian<$type_name>, $trait_name, $trait_func_name
+        );
+        impl_endian_op_assign!(
+            @rhs $type_name, $endian_name, BigEndian<$type_name>, $trait_name, $trait_func_name
+        );
+    };
+    (@rhs $type_name: ident, $endian_name: ident, $rhs: ty, $trait_name: ident, $trait_func_name: ident) => {
+        impl $trait_name<$rhs> for $endian_name<$type_name> {
+            #[inline]
+            fn $trait_func_name(&mut self, rhs: $rhs) {
+                let mut value = self.to_native();
+                value.$trait_func_name($type_name::from(rhs));
+                *self = Self::from_native(value);
+            }
+        }
+
+        impl<'a> $trait_name<&'a $rhs> for $endian_name<$type_name> {
+            #[inline]
+            fn $trait_func_name(&mut self, rhs: &'a $rhs) {
+                self.$trait_func_name(*rhs)
+            }
+        }
+    };
+}
+
+/// Implements `value << n` and `value >> n` for a `u32` shift amount, the type `checked_shl` and
+/// `rotate_left` take. Unlike the primitive types there is one right-hand side type only: with
+/// more, `n` would not be inferred in `(value >> 12) & 0xf` and the mask would fall back to `i32`.
+macro_rules! impl_endian_shift {
+    ($type_name: ident, $trait_name: ident, $trait_func_name: ident) => {
+        impl_endian_shift!(@each $type_name, LittleEndian, $trait_name, $trait_func_name);
+        impl_endian_shift!(@each $type_name, BigEndian, $trait_name, $trait_func_name);
+    };
+    (@each $type_name: ident, $endian_name: ident, $trait_name: ident, $trait_func_name: ident) => {
+        impl $trait_name<u32> for $endian_name<$type_name> {
+            type Output = Self;
+
+            #[inline]
+            fn $trait_func_name(self, rhs: u32) -> Self {
+                Self::from_native(self.to_native().$trait_func_name(rhs))
+            }
+        }
+
+        forward_ref_op!($endian_name<$type_name>, u32, $trait_name, $trait_func_name);
+    };
+}
+
+/// Implements `Eq` and `Ord` between values of the same endian type. With `stored`, values are
+/// ordered by their stored bytes without converting them, which is only correct for unsigned
+/// big-endian types.
+macro_rules! impl_endian_ord {
+    ($type_name: ident, $endian_name: ident, $order: ident) => {
+        impl PartialEq for $endian_name<$type_name> {
+            #[inline]
+            fn eq(&self, rhs: &Self) -> bool {
+                self.0 == rhs.0
+            }
+        }
+
+        impl Eq for $endian_name<$type_name> {}
+
+        impl PartialOrd for $endian_name<$type_name> {
+            #[inline]
+            fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+                Some(self.cmp(rhs))
+            }
+        }
+
+        impl Ord for $endian_name<$type_name> {
+            #[inline]
+            fn cmp(&self, rhs: &Self) -> Ordering {
+                impl_endian_ord!(@cmp $order, self, rhs)
+            }
+        }
+    };
+    (@cmp native, $lhs: ident, $rhs: ident) => {
+        $lhs.to_native().cmp(&$rhs.to_native())
+    };
+    (@cmp stored, $lhs: ident, $rhs: ident) => {
+        $lhs.0.to_ne_bytes().cmp(&$rhs.0.to_ne_bytes())
+    };
+}
+
 macro_rules! impl_endian_cmp {
     ($type_name: ident, $trait_name: ident, $trait_func_name: ident, $return_type: ty) => {
         impl_endian_cmp_each!(
@@ -288,22 +1274,6 @@ macro_rules! impl_endian_cmp {
             $trait_func_name,
             $return_type
         );
-        impl_endian_cmp_each!(
-            $type_name,
-            LittleEndian,
-            LittleEndian,
-            $trait_name,
-            $trait_func_name,
-            $return_type
-        );
-        impl_endian_cmp_each!(
-            $type_name,
-            BigEndian,
-            BigEndian,
-            $trait_name,
-            $trait_func_name,
-            $return_type
-        );
         impl_endian_cmp_native!(
             $type_name,
             BigEndian,
@@ -321,18 +1291,205 @@ macro_rules! impl_endian_cmp {
     };
 }
 
-impl_endian!(u8);
-impl_endian!(u16);
-impl_endian!(u32);
-impl_endian!(u64);
-impl_endian!(u128);
-impl_endian!(usize);
-impl_endian!(i8);
-impl_endian!(i16);
-impl_endian!(i32);
-impl_endian!(i64);
-impl_endian!(i128);
-impl_endian!(isize);
+/// Implements endian floats in terms of the integer type `$bits_name` of the same size, since
+/// floats have no `swap_bytes`. Values compare as floats, so there is no `Eq` or `Ord`.
+macro_rules! impl_endian_float {
+    ($type_name: ident, $bits_name: ident) => {
+        impl_endian_float_base!($type_name, $bits_name, BigEndian, LittleEndian, to_be);
+        impl_endian_float_base!($type_name, $bits_name, LittleEndian, BigEndian, to_le);
+        impl_endian_from_native!($type_name, LittleEndian);
+        impl_endian_from_native!($type_name, BigEndian);
+        impl_endian_as_ref!($type_name, LittleEndian);
+        impl_endian_as_ref!($type_name, BigEndian);
+        impl_endian_bytes!($type_name, LittleEndian);
+        impl_endian_bytes!($type_name, BigEndian);
+        impl_endian_op!($type_name, Add, add);
+        impl_endian_op!($type_name, Sub, sub);
+        impl_endian_op_assign!($type_name, AddAssign, add_assign);
+        impl_endian_op_assign!($type_name, SubAssign, sub_assign);
+        impl_endian_op_assign!($type_name, MulAssign, mul_assign);
+        impl_endian_op_assign!($type_name, DivAssign, div_assign);
+        impl_endian_cmp!($type_name, PartialEq, eq, bool);
+        impl_endian_cmp!($type_name, PartialOrd, partial_cmp, Option<Ordering>);
+    };
+}
+
+macro_rules! impl_endian_float_base {
+    ($type_name: ident, $bits_name: ident, $endian_name: ident, $other_endian_name: ident, $to_order: ident) => {
+        impl $endian_name<$type_name> {
+            pub const fn from_native(data: $type_name) -> Self {
+                Self($type_name::from_bits(data.to_bits().$to_order()))
+            }
+
+            pub const fn new(data: $type_name) -> Self {
+                Self(data)
+            }
+
+            /// The stored representation, without any conversion; the inverse of `new`.
+            pub const fn into_inner(self) -> $type_name {
+                self.0
+            }
+
+            pub const fn to_native(&self) -> $type_name {
+                // Converting to the byte order is its own inverse.
+                $type_name::from_bits(self.0.to_bits().$to_order())
+            }
+
+            /// A guard for operating on the native value, stored back when it is dropped.
+            pub fn native_mut(&mut self) -> crate::native::NativeMut<'_, Self, $type_name> {
+                let value = self.to_native();
+                crate::native::NativeMut::new(self, value)
+            }
+
+            /// The IEEE total order of `self` and `other`, which orders every value, NaNs
+            /// included.
+            #[inline]
+            pub fn total_cmp(&self, other: &Self) -> Ordering {
+                self.to_native().total_cmp(&other.to_native())
+            }
+
+            #[inline]
+            pub fn is_nan(self) -> bool {
+                self.to_native().is_nan()
+            }
+
+            /// Whether the value is neither infinite nor NaN.
+            #[inline]
+            pub fn is_finite(self) -> bool {
+                self.to_native().is_finite()
+            }
+
+            #[inline]
+            pub fn is_infinite(self) -> bool {
+                self.to_native().is_infinite()
+            }
+
+            #[inline]
+            pub fn classify(self) -> core::num::FpCategory {
+                self.to_native().classify()
+            }
+
+            /// The smaller of `self` and `other`; if one of them is NaN, the other.
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                Self::from_native(self.to_native().min(other.to_native()))
+            }
+
+            /// The larger of `self` and `other`; if one of them is NaN, the other.
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                Self::from_native(self.to_native().max(other.to_native()))
+            }
+        }
+
+        impl From<$other_endian_name<$type_name>> for $endian_name<$type_name> {
+            #[inline]
+            fn from(data: $other_endian_name<$type_name>) -> Self {
+                Self($type_name::from_bits(data.0.to_bits().swap_bytes()))
+            }
+        }
+
+        impl PartialEq for $endian_name<$type_name> {
+            #[inline]
+            fn eq(&self, rhs: &Self) -> bool {
+                self.to_native() == rhs.to_native()
+            }
+        }
+
+        impl PartialOrd for $endian_name<$type_name> {
+            #[inline]
+            fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+                self.to_native().partial_cmp(&rhs.to_native())
+            }
+        }
+    };
+}
+
+impl_endian!(u8, stored);
+impl_endian!(u16, stored);
+impl_endian!(u32, stored);
+impl_endian!(u64, stored);
+impl_endian!(u128, stored);
+impl_endian!(usize, stored);
+impl_endian!(i8, native);
+impl_endian!(i16, native);
+impl_endian!(i32, native);
+impl_endian!(i64, native);
+impl_endian!(i128, native);
+impl_endian!(isize, native);
+impl_endian_float!(f32, u32);
+impl_endian_float!(f64, u64);
+impl_endian_float_to_int! {
+    f32;
+        to_u8_le_saturating: u8, LittleEndian;
+        to_u8_be_saturating: u8, BigEndian;
+        to_u16_le_saturating: u16, LittleEndian;
+        to_u16_be_saturating: u16, BigEndian;
+        to_u32_le_saturating: u32, LittleEndian;
+        to_u32_be_saturating: u32, BigEndian;
+        to_u64_le_saturating: u64, LittleEndian;
+        to_u64_be_saturating: u64, BigEndian;
+        to_u128_le_saturating: u128, LittleEndian;
+        to_u128_be_saturating: u128, BigEndian;
+        to_usize_le_saturating: usize, LittleEndian;
+        to_usize_be_saturating: usize, BigEndian;
+        to_i8_le_saturating: i8, LittleEndian;
+        to_i8_be_saturating: i8, BigEndian;
+        to_i16_le_saturating: i16, LittleEndian;
+        to_i16_be_saturating: i16, BigEndian;
+        to_i32_le_saturating: i32, LittleEndian;
+        to_i32_be_saturating: i32, BigEndian;
+        to_i64_le_saturating: i64, LittleEndian;
+        to_i64_be_saturating: i64, BigEndian;
+        to_i128_le_saturating: i128, LittleEndian;
+        to_i128_be_saturating: i128, BigEndian;
+        to_isize_le_saturating: isize, LittleEndian;
+        to_isize_be_saturating: isize, BigEndian;
+}
+impl_endian_float_to_int! {
+    f64;
+        to_u8_le_saturating: u8, LittleEndian;
+        to_u8_be_saturating: u8, BigEndian;
+        to_u16_le_saturating: u16, LittleEndian;
+        to_u16_be_saturating: u16, BigEndian;
+        to_u32_le_saturating: u32, LittleEndian;
+        to_u32_be_saturating: u32, BigEndian;
+        to_u64_le_saturating: u64, LittleEndian;
+        to_u64_be_saturating: u64, BigEndian;
+        to_u128_le_saturating: u128, LittleEndian;
+        to_u128_be_saturating: u128, BigEndian;
+        to_usize_le_saturating: usize, LittleEndian;
+        to_usize_be_saturating: usize, BigEndian;
+        to_i8_le_saturating: i8, LittleEndian;
+        to_i8_be_saturating: i8, BigEndian;
+        to_i16_le_saturating: i16, LittleEndian;
+        to_i16_be_saturating: i16, BigEndian;
+        to_i32_le_saturating: i32, LittleEndian;
+        to_i32_be_saturating: i32, BigEndian;
+        to_i64_le_saturating: i64, LittleEndian;
+        to_i64_be_saturating: i64, BigEndian;
+        to_i128_le_saturating: i128, LittleEndian;
+        to_i128_be_saturating: i128, BigEndian;
+        to_isize_le_saturating: isize, LittleEndian;
+        to_isize_be_saturating: isize, BigEndian;
+}
+
+impl_endian_try_from!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_endian_try_from!(u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_endian_try_from!(u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_endian_try_from!(u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_endian_try_from!(u128, usize, i8, i16, i32, i64, i128, isize);
+impl_endian_try_from!(usize, u32, u64, u128, i8, i16, i32, i64, i128, isize);
+impl_endian_try_from!(i8, u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+impl_endian_try_from!(i16, u16, u32, u64, u128, usize, i32, i64, i128, isize);
+impl_endian_try_from!(i32, u32, u64, u128, usize, i64, i128, isize);
+impl_endian_try_from!(i64, u64, u128, usize, i128, isize);
+impl_endian_try_from!(i128, u128, usize, isize);
+impl_endian_try_from!(isize, u16, u32, u64, u128, usize, i32, i64, i128);
+
+impl_endian_bigint!(u8, u16, u32, u64, u128, usize);
+
+impl_endian_neg!(i8, i16, i32, i64, i128, isize, f32, f64);
 
 #[allow(non_camel_case_types)]
 pub mod types {
@@ -348,6 +1505,8 @@ pub mod types {
     pub type i64_le = super::LittleEndian<i64>;
     pub type i128_le = super::LittleEndian<i128>;
     pub type isize_le = super::LittleEndian<isize>;
+    pub type f32_le = super::LittleEndian<f32>;
+    pub type f64_le = super::LittleEndian<f64>;
     pub type u8_be = super::BigEndian<u8>;
     pub type u16_be = super::BigEndian<u16>;
     pub type u32_be = super::BigEndian<u32>;
@@ -360,4 +1519,15 @@ pub mod types {
     pub type i64_be = super::BigEndian<i64>;
     pub type i128_be = super::BigEndian<i128>;
     pub type isize_be = super::BigEndian<isize>;
+    pub type f32_be = super::BigEndian<f32>;
+    pub type f64_be = super::BigEndian<f64>;
+}
+
+/// # Prelude
+/// The zerocopy-style names (`U32<BE>` and friends) and their byte order markers as one set.
+pub mod prelude {
+    pub use crate::byteorder::{
+        ByteOrder, Isize, NetworkEndian, Usize, BE, F32, F64, I128, I16, I32, I64, I8, LE, NE,
+        U128, U16, U32, U64, U8,
+    };
 }
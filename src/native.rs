@@ -0,0 +1,52 @@
+//! # Native views
+//!
+//! `native_mut` converts an endian value to native order once and returns a guard that derefs to
+//! the native value; the result is converted back and stored when the guard is dropped. A long
+//! sequence of operations then costs two byte swaps instead of two per operator.
+//! ## Example
+//! ```
+//! use endiantype::*;
+//! let mut hash = u64_be::from_native(0xcbf2_9ce4_8422_2325);
+//! {
+//!     let mut h = hash.native_mut();
+//!     for &byte in b"abc" {
+//!         *h ^= u64::from(byte);
+//!         *h = h.wrapping_mul(0x100_0000_01b3);
+//!     }
+//! }
+//! assert!(hash == 0xe71f_a219_0541_574b);
+//! ```
+use core::ops::{Deref, DerefMut};
+
+/// # Native view guard
+/// A native copy of an endian value `E`, written back to it on drop.
+pub struct NativeMut<'a, E: From<T>, T: Copy> {
+    target: &'a mut E,
+    value: T,
+}
+
+impl<'a, E: From<T>, T: Copy> NativeMut<'a, E, T> {
+    pub(crate) fn new(target: &'a mut E, value: T) -> Self {
+        Self { target, value }
+    }
+}
+
+impl<E: From<T>, T: Copy> Deref for NativeMut<'_, E, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<E: From<T>, T: Copy> DerefMut for NativeMut<'_, E, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<E: From<T>, T: Copy> Drop for NativeMut<'_, E, T> {
+    fn drop(&mut self) {
+        *self.target = E::from(self.value);
+    }
+}
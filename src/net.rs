@@ -0,0 +1,247 @@
+//! # Socket addresses
+//!
+//! Conversions between network-order address and port fields and `core::net` addresses.
+//! Addresses and ports read from packet headers are `u32_be`/`u128_be` and `u16_be`; keeping them
+//! in those types until they become a `SocketAddrV4` or `SocketAddrV6` means a port can no longer
+//! be swapped twice or not at all.
+//! ## Example
+//! ```
+//! use core::net::{Ipv4Addr, SocketAddrV4};
+//! use endiantype::io::EndianReader;
+//! use endiantype::net::*;
+//! use endiantype::*;
+//!
+//! let mut header = EndianReader::new(&[192, 168, 1, 20, 0x1f, 0x90]);
+//! let addr = header.read::<u32_be>().unwrap();
+//! let port = header.read::<u16_be>().unwrap();
+//! let socket = socket_addr_v4(addr, port);
+//! assert!(socket == SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 20), 8080));
+//! assert!(wire_v4(&socket) == (addr, port));
+//! ```
+//!
+//! `mac!`, `ipv4!` and `ipv6!` parse address literals at compile time, so packet templates and
+//! filter tables can be written in the usual notation; a malformed literal fails the build.
+//! ## Example
+//! ```
+//! use endiantype::*;
+//!
+//! const GATEWAY: u32_be = ipv4!("192.168.1.1");
+//! const BROADCAST: [u8; 6] = mac!("ff:ff:ff:ff:ff:ff");
+//! const LINK_LOCAL: u128_be = ipv6!("fe80::1");
+//!
+//! assert!(GATEWAY.as_ref() == [192, 168, 1, 1] && BROADCAST == [0xff; 6]);
+//! assert!(LINK_LOCAL == 0xfe80_0000_0000_0000_0000_0000_0000_0001);
+//! ```
+//! ```compile_fail
+//! use endiantype::*;
+//! const GATEWAY: u32_be = ipv4!("192.168.1.256");
+//! ```
+use crate::types::{u128_be, u16_be, u32_be};
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+impl From<u32_be> for Ipv4Addr {
+    #[inline]
+    fn from(addr: u32_be) -> Self {
+        Ipv4Addr::from(addr.to_native())
+    }
+}
+
+impl From<Ipv4Addr> for u32_be {
+    #[inline]
+    fn from(addr: Ipv4Addr) -> Self {
+        u32_be::from_native(addr.into())
+    }
+}
+
+impl From<u128_be> for Ipv6Addr {
+    #[inline]
+    fn from(addr: u128_be) -> Self {
+        Ipv6Addr::from(addr.to_native())
+    }
+}
+
+impl From<Ipv6Addr> for u128_be {
+    #[inline]
+    fn from(addr: Ipv6Addr) -> Self {
+        u128_be::from_native(addr.into())
+    }
+}
+
+pub fn socket_addr_v4(addr: u32_be, port: u16_be) -> SocketAddrV4 {
+    SocketAddrV4::new(addr.into(), port.to_native())
+}
+
+/// The address and port of `socket` in network order.
+pub fn wire_v4(socket: &SocketAddrV4) -> (u32_be, u16_be) {
+    ((*socket.ip()).into(), socket.port().into())
+}
+
+/// A socket address with no flow information or scope.
+pub fn socket_addr_v6(addr: u128_be, port: u16_be) -> SocketAddrV6 {
+    SocketAddrV6::new(addr.into(), port.to_native(), 0, 0)
+}
+
+/// The address and port of `socket` in network order.
+pub fn wire_v6(socket: &SocketAddrV6) -> (u128_be, u16_be) {
+    ((*socket.ip()).into(), socket.port().into())
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Parse a MAC address written as six pairs of hex digits separated by `:` or `-`. Panics on
+/// malformed input, so called from a constant it fails the build; see `mac!`.
+pub const fn parse_mac(s: &str) -> [u8; 6] {
+    let s = s.as_bytes();
+    assert!(s.len() == 17, "MAC address must be 17 characters");
+    let mut mac = [0; 6];
+    let mut i = 0;
+    while i < 6 {
+        let at = i * 3;
+        if i > 0 {
+            assert!(
+                s[at - 1] == b':' || s[at - 1] == b'-',
+                "bad MAC address separator"
+            );
+        }
+        let (high, low) = match (hex_digit(s[at]), hex_digit(s[at + 1])) {
+            (Some(high), Some(low)) => (high, low),
+            _ => panic!("bad hex digit in MAC address"),
+        };
+        mac[i] = high << 4 | low;
+        i += 1;
+    }
+    mac
+}
+
+/// Parse an IPv4 address in dotted decimal. Panics on malformed input; see `ipv4!`.
+pub const fn parse_ipv4(s: &str) -> u32_be {
+    let s = s.as_bytes();
+    let mut addr: u32 = 0;
+    let mut octets = 0;
+    let mut i = 0;
+    while octets < 4 {
+        let start = i;
+        let mut octet: u32 = 0;
+        while i < s.len() && s[i].is_ascii_digit() {
+            octet = octet * 10 + (s[i] - b'0') as u32;
+            assert!(octet <= 255, "IPv4 octet out of range");
+            i += 1;
+        }
+        assert!(i > start, "missing IPv4 octet");
+        assert!(
+            i - start == 1 || s[start] != b'0',
+            "leading zero in IPv4 octet"
+        );
+        addr = addr << 8 | octet;
+        octets += 1;
+        if octets < 4 {
+            assert!(
+                i < s.len() && s[i] == b'.',
+                "IPv4 address must have four octets"
+            );
+            i += 1;
+        }
+    }
+    assert!(i == s.len(), "trailing characters after IPv4 address");
+    u32_be::from_native(addr)
+}
+
+/// Parse an IPv6 address in colon-separated hex groups, with at most one `::` standing for a
+/// run of zero groups. Panics on malformed input; see `ipv6!`.
+pub const fn parse_ipv6(s: &str) -> u128_be {
+    let s = s.as_bytes();
+    let mut groups = [0u16; 8];
+    let mut count = 0;
+    // Index in `groups` where the `::` gap starts, if any.
+    let mut gap = 8;
+    let mut i = 0;
+    if s.len() >= 2 && s[0] == b':' && s[1] == b':' {
+        gap = 0;
+        i = 2;
+    }
+    while i < s.len() {
+        assert!(count < 8, "too many groups in IPv6 address");
+        let start = i;
+        let mut group: u32 = 0;
+        while i < s.len() && s[i] != b':' {
+            let digit = match hex_digit(s[i]) {
+                Some(digit) => digit,
+                None => panic!("bad hex digit in IPv6 address"),
+            };
+            group = group << 4 | digit as u32;
+            i += 1;
+        }
+        assert!(
+            i > start && i - start <= 4,
+            "IPv6 group must have 1 to 4 hex digits"
+        );
+        groups[count] = group as u16;
+        count += 1;
+        if i < s.len() {
+            i += 1;
+            if i < s.len() && s[i] == b':' {
+                assert!(gap == 8, "more than one :: in IPv6 address");
+                gap = count;
+                i += 1;
+            } else {
+                assert!(i < s.len(), "trailing : in IPv6 address");
+            }
+        }
+    }
+    if gap == 8 {
+        assert!(count == 8, "IPv6 address must have eight groups");
+    } else {
+        assert!(count < 8, "too many groups in IPv6 address");
+    }
+    let mut addr: u128 = 0;
+    let mut group = 0;
+    while group < 8 {
+        let value = if group < gap {
+            groups[group]
+        } else if group >= 8 - (count - gap) {
+            groups[group - (8 - count)]
+        } else {
+            0
+        };
+        addr = addr << 16 | value as u128;
+        group += 1;
+    }
+    u128_be::from_native(addr)
+}
+
+/// # MAC address literal
+/// A `[u8; 6]` parsed from a string literal at compile time, e.g. `mac!("02:00:5e:10:00:01")`.
+#[macro_export]
+macro_rules! mac {
+    ($addr: expr) => {{
+        const MAC: [u8; 6] = $crate::net::parse_mac($addr);
+        MAC
+    }};
+}
+
+/// # IPv4 address literal
+/// A `u32_be` parsed from a string literal at compile time, e.g. `ipv4!("10.0.0.1")`.
+#[macro_export]
+macro_rules! ipv4 {
+    ($addr: expr) => {{
+        const ADDR: $crate::types::u32_be = $crate::net::parse_ipv4($addr);
+        ADDR
+    }};
+}
+
+/// # IPv6 address literal
+/// A `u128_be` parsed from a string literal at compile time, e.g. `ipv6!("2001:db8::1")`.
+#[macro_export]
+macro_rules! ipv6 {
+    ($addr: expr) => {{
+        const ADDR: $crate::types::u128_be = $crate::net::parse_ipv6($addr);
+        ADDR
+    }};
+}
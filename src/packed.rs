@@ -0,0 +1,51 @@
+/// # Packed field reads
+/// Reads a field of a `#[repr(C, packed)]` struct through `addr_of!` and an unaligned read,
+/// without ever creating a reference to the possibly misaligned field, which would be undefined
+/// behavior.
+///
+/// The struct is given as a reference, which is always aligned for a packed struct; for a raw
+/// pointer received over FFI, dereference it with `&*ptr` in an `unsafe` block first. The type
+/// must match the field's declared type.
+/// ## Example
+/// ```
+/// use endiantype::*;
+///
+/// #[repr(C, packed)]
+/// struct Header {
+///     kind: u8,
+///     len: u32_be,
+/// }
+///
+/// let mut header = Header {
+///     kind: 1,
+///     len: u32_be::from_native(0x1234),
+/// };
+/// let len = read_packed_field!(&header, len, u32_be);
+/// assert!(len == 0x1234);
+/// write_packed_field!(&mut header, len, u32_be::from_native(len.to_native() + 4));
+/// assert!(read_packed_field!(&header, len, u32_be) == 0x1238);
+/// ```
+#[macro_export]
+macro_rules! read_packed_field {
+    ($packed: expr, $field: ident, $field_type: ty) => {{
+        let packed = $packed;
+        let ptr: *const $field_type = ::core::ptr::addr_of!(packed.$field);
+        // SAFETY: `ptr` points to a field of a live struct; the read does not require alignment.
+        unsafe { ::core::ptr::read_unaligned(ptr) }
+    }};
+}
+
+/// # Packed field writes
+/// Writes a field of a `#[repr(C, packed)]` struct through `addr_of_mut!` and an unaligned
+/// write; the counterpart of `read_packed_field!`.
+#[macro_export]
+macro_rules! write_packed_field {
+    ($packed: expr, $field: ident, $value: expr) => {{
+        let packed = $packed;
+        let value = $value;
+        let ptr = ::core::ptr::addr_of_mut!(packed.$field);
+        // SAFETY: `ptr` points to a field of a struct borrowed mutably; the write does not
+        // require alignment.
+        unsafe { ::core::ptr::write_unaligned(ptr, value) }
+    }};
+}
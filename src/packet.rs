@@ -0,0 +1,99 @@
+//! # Packet builder
+//!
+//! `PacketBuilder` writes a packet with chained calls and fills in length and checksum fields
+//! once the bytes they cover are known. Running out of buffer is remembered rather than reported
+//! by every call, and `finish` returns `None` if it happened anywhere in the chain.
+//! ## Example
+//! ```
+//! use endiantype::packet::PacketBuilder;
+//! use endiantype::*;
+//! let mut buf = [0u8; 64];
+//! let mut builder = PacketBuilder::new(&mut buf);
+//! builder
+//!     .put::<u8_be>(0x01)
+//!     .put::<u16_be>(0)
+//!     .put_bytes(b"hello")
+//!     .put_padded(12, 0)
+//!     .patch_with(1, |packet| u16_be::from_native(packet.len() as u16));
+//! let packet = builder.finish().unwrap();
+//! assert!(packet == b"\x01\x00\x0chello\0\0\0\0");
+//!
+//! let mut small = [0u8; 4];
+//! let mut builder = PacketBuilder::new(&mut small);
+//! builder.put::<u32_le>(1).put::<u8_le>(2);
+//! assert!(builder.finish().is_none());
+//! ```
+use crate::io::{Encode, EndianWriter};
+
+/// # Packet builder
+#[derive(Debug)]
+pub struct PacketBuilder<'a> {
+    writer: EndianWriter<'a>,
+    overflow: bool,
+}
+
+impl<'a> PacketBuilder<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            writer: EndianWriter::new(buf),
+            overflow: false,
+        }
+    }
+
+    /// Number of bytes written so far; also the offset of the next field, for patching later.
+    pub fn len(&self) -> usize {
+        self.writer.position()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check(&mut self, result: Option<()>) -> &mut Self {
+        self.overflow |= result.is_none();
+        self
+    }
+
+    pub fn put<T: Encode>(&mut self, value: impl Into<T>) -> &mut Self {
+        let result = self.writer.write(value.into());
+        self.check(result)
+    }
+
+    pub fn put_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        let result = self.writer.write_bytes(bytes);
+        self.check(result)
+    }
+
+    /// Append `fill` bytes until the packet is at least `len` bytes long.
+    pub fn put_padded(&mut self, len: usize, fill: u8) -> &mut Self {
+        let pad = len.saturating_sub(self.len());
+        let result = self.writer.reserve(pad).map(|bytes| bytes.fill(fill));
+        self.check(result)
+    }
+
+    /// Overwrite the field at `offset` with `value`.
+    pub fn patch<T: Encode>(&mut self, offset: usize, value: impl Into<T>) -> &mut Self {
+        let result = self.writer.write_at(offset, value.into());
+        self.check(result)
+    }
+
+    /// Overwrite the field at `offset` with a value computed from the packet written so far, such
+    /// as its length or checksum.
+    pub fn patch_with<T: Encode>(
+        &mut self,
+        offset: usize,
+        f: impl FnOnce(&[u8]) -> T,
+    ) -> &mut Self {
+        let value = f(self.writer.written());
+        self.patch::<T>(offset, value)
+    }
+
+    /// The packet, or `None` if any call ran out of buffer or patched outside the packet.
+    pub fn finish(self) -> Option<&'a mut [u8]> {
+        if self.overflow {
+            None
+        } else {
+            Some(self.writer.into_written())
+        }
+    }
+}
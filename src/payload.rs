@@ -0,0 +1,160 @@
+//! # Newtype payloads
+//!
+//! Endian types usually wrap a primitive integer. Implementing `EndianPayload` for a
+//! `#[repr(transparent)]` newtype over one lets it be wrapped too, so a `UserId` can stay a
+//! `UserId` in wire structs: `LittleEndian<UserId>` converts to and from `UserId` and can be
+//! read and written like any other endian type.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::payload::EndianPayload;
+//! use endiantype::*;
+//!
+//! #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+//! #[repr(transparent)]
+//! struct UserId(u32);
+//!
+//! // SAFETY: `UserId` is a transparent wrapper around `u32` with no invariants.
+//! unsafe impl EndianPayload for UserId {
+//!     type Repr = u32;
+//! }
+//!
+//! let id = BigEndian::<UserId>::from_native(UserId(0x0102_0304));
+//! assert!(id.to_native() == UserId(0x0102_0304));
+//! let mut buf = [0u8; 4];
+//! EndianWriter::new(&mut buf).write(id).unwrap();
+//! assert!(buf == [1, 2, 3, 4]);
+//! let read: BigEndian<UserId> = EndianReader::new(&buf).read().unwrap();
+//! assert!(read.to_native() == id.to_native());
+//! ```
+use crate::bytes::EndianBytes;
+use crate::{BigEndian, LittleEndian};
+use core::mem::transmute_copy;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// # Payload representation
+/// The primitive integers a payload can be represented as.
+pub trait Primitive: Copy + Eq + sealed::Sealed {
+    #[doc(hidden)]
+    fn to_le(self) -> Self;
+
+    #[doc(hidden)]
+    fn to_be(self) -> Self;
+
+    #[doc(hidden)]
+    type Bytes: Copy + Default + AsRef<[u8]> + AsMut<[u8]>;
+
+    #[doc(hidden)]
+    fn to_ne_bytes(self) -> Self::Bytes;
+
+    #[doc(hidden)]
+    fn from_ne_bytes(bytes: Self::Bytes) -> Self;
+}
+
+macro_rules! impl_primitive {
+    ($($type_name: ident),*) => {
+        $(
+            impl sealed::Sealed for $type_name {}
+
+            impl Primitive for $type_name {
+                type Bytes = [u8; core::mem::size_of::<$type_name>()];
+
+                #[inline]
+                fn to_le(self) -> Self {
+                    $type_name::to_le(self)
+                }
+
+                #[inline]
+                fn to_be(self) -> Self {
+                    $type_name::to_be(self)
+                }
+
+                #[inline]
+                fn to_ne_bytes(self) -> Self::Bytes {
+                    $type_name::to_ne_bytes(self)
+                }
+
+                #[inline]
+                fn from_ne_bytes(bytes: Self::Bytes) -> Self {
+                    $type_name::from_ne_bytes(bytes)
+                }
+            }
+        )*
+    };
+}
+
+for_each_int!(impl_primitive);
+
+/// # Endian payload
+/// A newtype that can be stored in `LittleEndian` and `BigEndian` through its primitive `Repr`.
+///
+/// # Safety
+/// The type must be `#[repr(transparent)]` over `Repr`, and every value of `Repr` must be a
+/// valid value of the type, since byte-swapped values are stored in it.
+pub unsafe trait EndianPayload: Copy {
+    type Repr: Primitive;
+}
+
+#[inline]
+fn to_repr<T: EndianPayload>(value: T) -> T::Repr {
+    // SAFETY: `T` is transparent over `T::Repr`, as `EndianPayload` requires.
+    unsafe { transmute_copy(&value) }
+}
+
+#[inline]
+fn from_repr<T: EndianPayload>(repr: T::Repr) -> T {
+    // SAFETY: `T` is transparent over `T::Repr` and valid for any of its values.
+    unsafe { transmute_copy(&repr) }
+}
+
+macro_rules! impl_payload {
+    ($endian_name: ident, $convert: ident) => {
+        impl<T: EndianPayload> $endian_name<T> {
+            pub fn from_native(data: T) -> Self {
+                Self(from_repr(to_repr(data).$convert()))
+            }
+
+            pub fn to_native(&self) -> T {
+                from_repr(to_repr(self.0).$convert())
+            }
+        }
+
+        impl<T: EndianPayload> From<T> for $endian_name<T> {
+            #[inline]
+            fn from(data: T) -> Self {
+                Self::from_native(data)
+            }
+        }
+
+        impl<T: EndianPayload> EndianBytes for $endian_name<T> {
+            type Bytes = <T::Repr as Primitive>::Bytes;
+
+            #[inline]
+            fn to_bytes(self) -> Self::Bytes {
+                to_repr(self.0).to_ne_bytes()
+            }
+
+            #[inline]
+            fn from_bytes(bytes: Self::Bytes) -> Self {
+                Self(from_repr(T::Repr::from_ne_bytes(bytes)))
+            }
+
+            #[inline]
+            fn ref_from_bytes(bytes: &Self::Bytes) -> Option<&Self> {
+                let ptr = bytes as *const Self::Bytes as *const Self;
+                if ptr as usize % core::mem::align_of::<Self>() != 0 {
+                    return None;
+                }
+                // SAFETY: the wrapper and `T` are transparent over `T::Repr`, a primitive number
+                // of the same size as `bytes`, every value of which is valid for `T`.
+                Some(unsafe { &*ptr })
+            }
+        }
+    };
+}
+
+impl_payload!(LittleEndian, to_le);
+impl_payload!(BigEndian, to_be);
@@ -0,0 +1,107 @@
+//! # PCM samples
+//!
+//! Conversion of interleaved PCM buffers, such as the big-endian sample data of AIFF files and
+//! many ADCs, into native samples, optionally split into one buffer per channel. 24-bit samples
+//! are packed in 3 bytes and are sign-extended into `i32`.
+//! ## Example
+//! ```
+//! use endiantype::pcm::*;
+//! use endiantype::*;
+//!
+//! let stereo = [i16_be::from_native(1), i16_be::from_native(-1), i16_be::from_native(2), i16_be::from_native(-2)];
+//! let mut samples = [0i16; 4];
+//! assert!(to_native(&stereo, &mut samples) == 4);
+//! assert!(samples == [1, -1, 2, -2]);
+//!
+//! let (mut left, mut right) = ([0i16; 2], [0i16; 2]);
+//! assert!(deinterleave(&stereo, &mut [&mut left, &mut right]) == 2);
+//! assert!(left == [1, 2] && right == [-1, -2]);
+//!
+//! let packed = [0x7f, 0xff, 0xff, 0xff, 0xff, 0xfe];
+//! let mut samples = [0i32; 2];
+//! assert!(i24_to_native(&packed, Endianness::Big, &mut samples) == 2);
+//! assert!(samples == [0x7f_ffff, -2]);
+//! ```
+use crate::endianness::Endianness;
+
+/// Convert samples to native order; returns the number converted, the length of the shorter
+/// buffer.
+pub fn to_native<E: Copy + Into<T>, T>(src: &[E], dst: &mut [T]) -> usize {
+    let len = src.len().min(dst.len());
+    for (dst, &src) in dst[..len].iter_mut().zip(&src[..len]) {
+        *dst = src.into();
+    }
+    len
+}
+
+/// Split interleaved frames into one native buffer per channel; returns the number of frames
+/// converted, limited by the shortest channel buffer. Trailing samples of an incomplete frame are
+/// ignored.
+pub fn deinterleave<E: Copy + Into<T>, T>(src: &[E], channels: &mut [&mut [T]]) -> usize {
+    let count = channels.len();
+    if count == 0 {
+        return 0;
+    }
+    let frames = channels
+        .iter()
+        .map(|channel| channel.len())
+        .fold(src.len() / count, usize::min);
+    if frames == 0 {
+        return 0;
+    }
+    for (channel, buf) in channels.iter_mut().enumerate() {
+        let samples = src[channel..].iter().step_by(count);
+        for (dst, &src) in buf[..frames].iter_mut().zip(samples) {
+            *dst = src.into();
+        }
+    }
+    frames
+}
+
+/// Decode one packed 24-bit sample, sign-extended.
+pub fn i24_from_bytes(bytes: [u8; 3], endianness: Endianness) -> i32 {
+    let [a, b, c] = bytes;
+    let raw = match endianness {
+        Endianness::Little => [c, b, a, 0],
+        Endianness::Big => [a, b, c, 0],
+    };
+    i32::from_be_bytes(raw) >> 8
+}
+
+/// Encode the low 24 bits of `sample` as one packed sample.
+pub fn i24_to_bytes(sample: i32, endianness: Endianness) -> [u8; 3] {
+    let [_, a, b, c] = sample.to_be_bytes();
+    match endianness {
+        Endianness::Little => [c, b, a],
+        Endianness::Big => [a, b, c],
+    }
+}
+
+/// Convert packed 24-bit samples to sign-extended native `i32`s; returns the number converted.
+/// Trailing bytes of an incomplete sample are ignored.
+pub fn i24_to_native(src: &[u8], endianness: Endianness, dst: &mut [i32]) -> usize {
+    let len = (src.len() / 3).min(dst.len());
+    for (dst, bytes) in dst[..len].iter_mut().zip(src.chunks_exact(3)) {
+        *dst = i24_from_bytes([bytes[0], bytes[1], bytes[2]], endianness);
+    }
+    len
+}
+
+/// Split interleaved packed 24-bit frames into one buffer per channel; returns the number of
+/// frames converted.
+pub fn deinterleave_i24(src: &[u8], endianness: Endianness, channels: &mut [&mut [i32]]) -> usize {
+    let count = channels.len();
+    if count == 0 {
+        return 0;
+    }
+    let frames = channels
+        .iter()
+        .map(|channel| channel.len())
+        .fold(src.len() / 3 / count, usize::min);
+    for (frame, bytes) in src.chunks_exact(3 * count).take(frames).enumerate() {
+        for (buf, sample) in channels.iter_mut().zip(bytes.chunks_exact(3)) {
+            buf[frame] = i24_from_bytes([sample[0], sample[1], sample[2]], endianness);
+        }
+    }
+    frames
+}
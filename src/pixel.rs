@@ -0,0 +1,104 @@
+//! # RGB565 pixels
+//!
+//! RGB565 packs a pixel into 16 bits: 5 bits of red at the top, 6 of green and 5 of blue. Whether
+//! the two bytes go to the display low byte first depends on the controller and bus, so there
+//! is a pixel type for each order: `Rgb565Le` and `Rgb565Be`. Both are `bitfield!` types with the
+//! same channel accessors, convert into each other, and can be converted in bulk for panels that
+//! expect the other order.
+//! ## Example
+//! ```
+//! use endiantype::pixel::*;
+//! let orange = Rgb565Be::from_rgb888(0xff, 0x80, 0x00);
+//! assert!(orange.red() == 31 && orange.green() == 32 && orange.blue() == 0);
+//! assert!(orange.0.as_ref() == [0xfc, 0x00]);
+//! assert!(orange.to_rgb888() == (0xff, 0x82, 0x00));
+//!
+//! let frame = [Rgb565Le::from_rgb888(0xff, 0x80, 0x00); 4];
+//! let mut panel = [Rgb565Be::default(); 4];
+//! convert(&frame, &mut panel);
+//! assert!(panel[3] == orange);
+//!
+//! // A framebuffer already in memory can be swapped in place.
+//! let mut bytes = [0x00, 0xfc, 0x1f, 0x00];
+//! swap_bytes_in_place(&mut bytes);
+//! assert!(bytes == [0xfc, 0x00, 0x00, 0x1f]);
+//! ```
+use crate::bitorder::Lsb0;
+use crate::types::{u16_be, u16_le};
+
+macro_rules! impl_rgb565 {
+    ($name: ident, $other: ident) => {
+        impl $name {
+            /// A pixel from 5-bit red, 6-bit green and 5-bit blue channels.
+            pub fn new(red: u8, green: u8, blue: u8) -> Self {
+                let mut pixel = Self::default();
+                pixel.set_red(red);
+                pixel.set_green(green);
+                pixel.set_blue(blue);
+                pixel
+            }
+
+            /// A pixel from 8-bit channels, keeping their high bits.
+            pub fn from_rgb888(red: u8, green: u8, blue: u8) -> Self {
+                Self::new(red >> 3, green >> 2, blue >> 3)
+            }
+
+            /// The 8-bit channels, with the high bits repeated into the low ones so that full
+            /// intensity stays 0xff.
+            pub fn to_rgb888(&self) -> (u8, u8, u8) {
+                let (red, green, blue) = (self.red(), self.green(), self.blue());
+                (
+                    red << 3 | red >> 2,
+                    green << 2 | green >> 4,
+                    blue << 3 | blue >> 2,
+                )
+            }
+        }
+
+        impl From<$other> for $name {
+            #[inline]
+            fn from(pixel: $other) -> Self {
+                Self(pixel.0.into())
+            }
+        }
+    };
+}
+
+bitfield! {
+    /// RGB565 pixel sent low byte first.
+    pub struct Rgb565Le(u16_le, Lsb0) {
+        pub blue, set_blue: u8 = 0, 5;
+        pub green, set_green: u8 = 5, 6;
+        pub red, set_red: u8 = 11, 5;
+    }
+}
+
+bitfield! {
+    /// RGB565 pixel sent high byte first.
+    pub struct Rgb565Be(u16_be, Lsb0) {
+        pub blue, set_blue: u8 = 0, 5;
+        pub green, set_green: u8 = 5, 6;
+        pub red, set_red: u8 = 11, 5;
+    }
+}
+
+impl_rgb565!(Rgb565Le, Rgb565Be);
+impl_rgb565!(Rgb565Be, Rgb565Le);
+
+/// Convert pixels between byte orders, e.g. `Rgb565Le` to `Rgb565Be`; returns the number
+/// converted, the length of the shorter buffer.
+pub fn convert<S: Copy + Into<D>, D>(src: &[S], dst: &mut [D]) -> usize {
+    let len = src.len().min(dst.len());
+    for (dst, &src) in dst[..len].iter_mut().zip(&src[..len]) {
+        *dst = src.into();
+    }
+    len
+}
+
+/// Swap the bytes of every 16-bit pixel of a framebuffer in place. A trailing odd byte is left
+/// alone.
+pub fn swap_bytes_in_place(bytes: &mut [u8]) {
+    for pixel in bytes.chunks_exact_mut(2) {
+        pixel.swap(0, 1);
+    }
+}
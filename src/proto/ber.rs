@@ -0,0 +1,215 @@
+//! # ASN.1 BER/DER framing
+//!
+//! Tag and length parsing for BER and DER encoded data, enough to walk certificates or SNMP
+//! messages without a full ASN.1 stack. Every parser returns the value together with the number of
+//! bytes it consumed.
+//! ## Example
+//! ```
+//! use endiantype::io::EndianWriter;
+//! use endiantype::proto::ber::*;
+//! // SEQUENCE, 0x0104 bytes long (long form length)
+//! let (header, used) = Header::parse(&[0x30, 0x82, 0x01, 0x04]).unwrap();
+//! assert!(header.tag == Tag { class: Class::Universal, constructed: true, number: 16 });
+//! assert!(header.length == Length::Definite(0x104) && used == 4);
+//! // Context-specific tag in high-tag-number form
+//! let (tag, used) = Tag::parse(&[0x9f, 0x81, 0x00]).unwrap();
+//! assert!(tag.class == Class::ContextSpecific && tag.number == 128 && used == 3);
+//! // DER rejects non-minimal lengths
+//! assert!(Length::parse_der(&[0x81, 0x05]) == Err(Error::NonMinimalLength));
+//!
+//! let mut buf = [0u8; 8];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! header.write(&mut writer).unwrap();
+//! assert!(writer.written() == [0x30, 0x82, 0x01, 0x04]);
+//! ```
+use crate::io::EndianWriter;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a tag or length.
+    UnexpectedEof,
+    /// A high tag number does not fit in a `u32`.
+    TagOverflow,
+    /// A definite length does not fit in a `usize`.
+    LengthOverflow,
+    /// The reserved length octet `0xff` was found.
+    ReservedLength,
+    /// A length was not encoded in the shortest form, which DER requires.
+    NonMinimalLength,
+    /// An indefinite length was found where DER requires a definite one.
+    IndefiniteLength,
+}
+
+/// # Tag class
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Class {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+/// # Identifier octets
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub class: Class,
+    pub constructed: bool,
+    pub number: u32,
+}
+
+impl Tag {
+    pub fn parse(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let first = *bytes.first().ok_or(Error::UnexpectedEof)?;
+        let class = match first >> 6 {
+            0 => Class::Universal,
+            1 => Class::Application,
+            2 => Class::ContextSpecific,
+            _ => Class::Private,
+        };
+        let constructed = first & 0x20 != 0;
+        if first & 0x1f != 0x1f {
+            let number = u32::from(first & 0x1f);
+            return Ok((
+                Self {
+                    class,
+                    constructed,
+                    number,
+                },
+                1,
+            ));
+        }
+        let mut number: u32 = 0;
+        for (i, &byte) in bytes.iter().enumerate().skip(1) {
+            if number > u32::MAX >> 7 {
+                return Err(Error::TagOverflow);
+            }
+            number = (number << 7) | u32::from(byte & 0x7f);
+            if byte & 0x80 == 0 {
+                return Ok((
+                    Self {
+                        class,
+                        constructed,
+                        number,
+                    },
+                    i + 1,
+                ));
+            }
+        }
+        Err(Error::UnexpectedEof)
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        let class = match self.class {
+            Class::Universal => 0x00,
+            Class::Application => 0x40,
+            Class::ContextSpecific => 0x80,
+            Class::Private => 0xc0,
+        };
+        let first = class | if self.constructed { 0x20 } else { 0 };
+        if self.number < 0x1f {
+            return writer.write_u8(first | self.number as u8);
+        }
+        writer.write_u8(first | 0x1f)?;
+        let groups = (32 - self.number.leading_zeros()).div_ceil(7);
+        for i in (0..groups).rev() {
+            let more = if i == 0 { 0 } else { 0x80 };
+            writer.write_u8(more | (self.number >> (i * 7)) as u8 & 0x7f)?;
+        }
+        Some(())
+    }
+}
+
+/// # Length octets
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Length {
+    Definite(usize),
+    /// Contents end with an end-of-contents marker (BER only).
+    Indefinite,
+}
+
+impl Length {
+    /// Parse a BER length, accepting any long form that fits in a `usize`.
+    pub fn parse(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let first = *bytes.first().ok_or(Error::UnexpectedEof)?;
+        match first {
+            0x00..=0x7f => Ok((Length::Definite(usize::from(first)), 1)),
+            0x80 => Ok((Length::Indefinite, 1)),
+            0xff => Err(Error::ReservedLength),
+            _ => {
+                let count = usize::from(first & 0x7f);
+                let octets = bytes.get(1..1 + count).ok_or(Error::UnexpectedEof)?;
+                let mut len: usize = 0;
+                for &byte in octets {
+                    if len > usize::MAX >> 8 {
+                        return Err(Error::LengthOverflow);
+                    }
+                    len = (len << 8) | usize::from(byte);
+                }
+                Ok((Length::Definite(len), 1 + count))
+            }
+        }
+    }
+
+    /// Parse a DER length, which must be definite and in its shortest form.
+    pub fn parse_der(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let (length, used) = Self::parse(bytes)?;
+        match length {
+            Length::Indefinite => Err(Error::IndefiniteLength),
+            Length::Definite(len) if used != Self::Definite(len).encoded_len() => {
+                Err(Error::NonMinimalLength)
+            }
+            Length::Definite(_) => Ok((length, used)),
+        }
+    }
+
+    /// Number of bytes of the shortest encoding.
+    pub fn encoded_len(&self) -> usize {
+        match *self {
+            Length::Definite(len) if len >= 0x80 => {
+                1 + (usize::BITS - len.leading_zeros()).div_ceil(8) as usize
+            }
+            _ => 1,
+        }
+    }
+
+    /// Write the shortest encoding.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        match *self {
+            Length::Indefinite => writer.write_u8(0x80),
+            Length::Definite(len) if len < 0x80 => writer.write_u8(len as u8),
+            Length::Definite(len) => {
+                let count = self.encoded_len() - 1;
+                writer.write_u8(0x80 | count as u8)?;
+                writer.write_bytes(&len.to_be_bytes()[core::mem::size_of::<usize>() - count..])
+            }
+        }
+    }
+}
+
+/// # TLV header
+/// The tag and length preceding the contents of a value.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Header {
+    pub tag: Tag,
+    pub length: Length,
+}
+
+impl Header {
+    pub fn parse(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let (tag, tag_len) = Tag::parse(bytes)?;
+        let (length, length_len) = Length::parse(&bytes[tag_len..])?;
+        Ok((Self { tag, length }, tag_len + length_len))
+    }
+
+    pub fn parse_der(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let (tag, tag_len) = Tag::parse(bytes)?;
+        let (length, length_len) = Length::parse_der(&bytes[tag_len..])?;
+        Ok((Self { tag, length }, tag_len + length_len))
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        self.tag.write(writer)?;
+        self.length.write(writer)
+    }
+}
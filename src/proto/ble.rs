@@ -0,0 +1,204 @@
+//! # Bluetooth LE advertising data
+//!
+//! Advertising and scan response payloads are a sequence of AD structures: a length byte, an AD
+//! type and data. Multi-byte fields inside them are little endian, including UUIDs, which are
+//! stored with their bytes reversed relative to the usual text form. 16 and 32-bit UUIDs are
+//! short forms of 128-bit UUIDs built on the Bluetooth base UUID.
+//! ## Example
+//! ```
+//! use endiantype::proto::ble::*;
+//! let adv = [
+//!     0x02, 0x01, 0x06, // flags
+//!     0x03, 0x03, 0x0d, 0x18, // heart rate service
+//!     0x05, 0xff, 0x4c, 0x00, 0x02, 0x15, // Apple manufacturer data
+//! ];
+//! let mut structures = AdStructures::new(&adv);
+//! let flags = structures.next().unwrap().unwrap();
+//! assert!(flags.ad_type == AD_FLAGS && flags.data == [0x06]);
+//! let services = structures.next().unwrap().unwrap();
+//! let uuid = services.uuids().next().unwrap();
+//! assert!(uuid.as_u16() == Some(0x180d));
+//! assert!(format!("{}", uuid) == "0000180d-0000-1000-8000-00805f9b34fb");
+//! let manufacturer = structures.next().unwrap().unwrap();
+//! assert!(manufacturer.company_id() == Some(0x004c));
+//! assert!(manufacturer.manufacturer_data() == Some(&[0x02, 0x15][..]));
+//! assert!(structures.next().is_none());
+//! ```
+use crate::io::EndianReader;
+use crate::types::{u128_le, u16_le, u32_le};
+use core::convert::TryFrom;
+use core::fmt;
+
+pub const AD_FLAGS: u8 = 0x01;
+pub const AD_INCOMPLETE_UUID16: u8 = 0x02;
+pub const AD_COMPLETE_UUID16: u8 = 0x03;
+pub const AD_INCOMPLETE_UUID32: u8 = 0x04;
+pub const AD_COMPLETE_UUID32: u8 = 0x05;
+pub const AD_INCOMPLETE_UUID128: u8 = 0x06;
+pub const AD_COMPLETE_UUID128: u8 = 0x07;
+pub const AD_SHORT_NAME: u8 = 0x08;
+pub const AD_COMPLETE_NAME: u8 = 0x09;
+pub const AD_TX_POWER: u8 = 0x0a;
+pub const AD_SERVICE_DATA_UUID16: u8 = 0x16;
+pub const AD_SERVICE_DATA_UUID32: u8 = 0x20;
+pub const AD_SERVICE_DATA_UUID128: u8 = 0x21;
+pub const AD_MANUFACTURER_DATA: u8 = 0xff;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// An AD structure is longer than the bytes left in the payload.
+    Truncated,
+}
+
+/// # UUID
+/// A 128-bit UUID, held as the number its text form spells out.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Uuid(pub u128);
+
+impl Uuid {
+    /// The Bluetooth base UUID, `00000000-0000-1000-8000-00805f9b34fb`.
+    pub const BASE: Uuid = Uuid(0x0000_0000_0000_1000_8000_0080_5f9b_34fb);
+
+    pub const fn from_u16(short: u16) -> Self {
+        Self::from_u32(short as u32)
+    }
+
+    pub const fn from_u32(short: u32) -> Self {
+        Self(Self::BASE.0 | (short as u128) << 96)
+    }
+
+    /// Read a 2, 4 or 16-byte little-endian UUID.
+    pub fn from_le_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = EndianReader::new(bytes);
+        let uuid = match bytes.len() {
+            2 => Self::from_u16(reader.read::<u16_le>()?.to_native()),
+            4 => Self::from_u32(reader.read::<u32_le>()?.to_native()),
+            16 => Self(reader.read::<u128_le>()?.to_native()),
+            _ => return None,
+        };
+        Some(uuid)
+    }
+
+    /// The 16-byte little-endian form used on the air.
+    pub fn to_le_bytes(self) -> [u8; 16] {
+        self.0.to_le_bytes()
+    }
+
+    /// The 32-bit short form, if this UUID is built on the base UUID.
+    pub fn as_u32(&self) -> Option<u32> {
+        if self.0 & !(0xffff_ffff << 96) == Self::BASE.0 {
+            Some((self.0 >> 96) as u32)
+        } else {
+            None
+        }
+    }
+
+    /// The 16-bit short form, if this UUID is built on the base UUID.
+    pub fn as_u16(&self) -> Option<u16> {
+        self.as_u32().and_then(|short| u16::try_from(short).ok())
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let v = self.0;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            v >> 96,
+            (v >> 80) & 0xffff,
+            (v >> 64) & 0xffff,
+            (v >> 48) & 0xffff,
+            v & 0xffff_ffff_ffff
+        )
+    }
+}
+
+/// # AD structure
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AdStructure<'a> {
+    pub ad_type: u8,
+    pub data: &'a [u8],
+}
+
+impl<'a> AdStructure<'a> {
+    /// The service UUIDs of a 16, 32 or 128-bit service UUID list; empty for other types.
+    pub fn uuids(&self) -> impl Iterator<Item = Uuid> + 'a {
+        let (data, size) = match self.ad_type {
+            AD_INCOMPLETE_UUID16 | AD_COMPLETE_UUID16 => (self.data, 2),
+            AD_INCOMPLETE_UUID32 | AD_COMPLETE_UUID32 => (self.data, 4),
+            AD_INCOMPLETE_UUID128 | AD_COMPLETE_UUID128 => (self.data, 16),
+            _ => (&[][..], 2),
+        };
+        data.chunks_exact(size).filter_map(Uuid::from_le_bytes)
+    }
+
+    /// The service UUID and data of a service data structure.
+    pub fn service_data(&self) -> Option<(Uuid, &'a [u8])> {
+        let size = match self.ad_type {
+            AD_SERVICE_DATA_UUID16 => 2,
+            AD_SERVICE_DATA_UUID32 => 4,
+            AD_SERVICE_DATA_UUID128 => 16,
+            _ => return None,
+        };
+        let uuid = Uuid::from_le_bytes(self.data.get(..size)?)?;
+        Some((uuid, &self.data[size..]))
+    }
+
+    /// The little-endian company identifier of manufacturer specific data.
+    pub fn company_id(&self) -> Option<u16> {
+        match self.ad_type {
+            AD_MANUFACTURER_DATA => EndianReader::new(self.data)
+                .read::<u16_le>()
+                .map(|id| id.to_native()),
+            _ => None,
+        }
+    }
+
+    /// The data after the company identifier of manufacturer specific data.
+    pub fn manufacturer_data(&self) -> Option<&'a [u8]> {
+        self.company_id()?;
+        Some(&self.data[2..])
+    }
+}
+
+/// # AD structure iterator
+/// Yields the AD structures of a payload; iteration stops at a zero length byte, which starts
+/// the padding of a fixed-size payload, and after the first error.
+#[derive(Clone, Debug)]
+pub struct AdStructures<'a> {
+    reader: EndianReader<'a>,
+}
+
+impl<'a> AdStructures<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self {
+            reader: EndianReader::new(payload),
+        }
+    }
+}
+
+impl<'a> Iterator for AdStructures<'a> {
+    type Item = Result<AdStructure<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = match self.reader.read_u8()? {
+            0 => {
+                self.reader = EndianReader::new(&[]);
+                return None;
+            }
+            len => usize::from(len),
+        };
+        match self.reader.read_bytes(len) {
+            Some(structure) => Some(Ok(AdStructure {
+                ad_type: structure[0],
+                data: &structure[1..],
+            })),
+            None => {
+                self.reader = EndianReader::new(&[]);
+                Some(Err(Error::Truncated))
+            }
+        }
+    }
+}
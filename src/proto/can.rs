@@ -0,0 +1,148 @@
+//! # CAN and J1939 identifiers
+//!
+//! Packing and unpacking of 29-bit extended CAN identifiers and of the J1939 fields inside them:
+//! a 3-bit priority, the parameter group number (PGN) and the source address. PDU1 groups
+//! (PDU format below 240) carry a destination address in the low byte of the PGN, which is then
+//! reported as zero, as in the J1939 tables.
+//!
+//! Signals (SPNs) are bit ranges of the 8-byte payload. Little-endian signals, the J1939 default,
+//! count `start` from the least significant bit of byte 0 to the least significant bit of the
+//! field; big-endian ("Motorola") signals count from the most significant bit of byte 0 to the
+//! most significant bit of the field.
+//! ## Example
+//! ```
+//! use endiantype::proto::can::*;
+//! use endiantype::Endianness;
+//! // EEC1 (PGN 61444) from the engine (source address 0) at priority 3.
+//! let id = J1939Id::from_can_id(0x0cf0_0400).unwrap();
+//! assert!(id.priority == 3 && id.pgn == 61444 && id.source_address == 0);
+//! assert!(id.destination_address().is_none());
+//! assert!(id.to_can_id() == Some(0x0cf0_0400));
+//!
+//! // Engine speed, SPN 190: bytes 4 and 5, little endian, 0.125 rpm per bit.
+//! let payload = [0xff, 0xff, 0xff, 0x60, 0x13, 0xff, 0xff, 0xff];
+//! let raw = read_signal(&payload, 24, 16, Endianness::Little);
+//! assert!(raw == 0x1360 && raw / 8 == 620);
+//!
+//! let mut payload = [0; 8];
+//! write_signal(&mut payload, 4, 12, Endianness::Big, 0xabc);
+//! assert!(payload[..3] == [0x0a, 0xbc, 0x00]);
+//! assert!(read_signal(&payload, 4, 12, Endianness::Big) == 0xabc);
+//! ```
+use crate::bitorder::{Lsb0, Msb0};
+use crate::endianness::Endianness;
+use crate::types::{u64_be, u64_le};
+
+/// Largest extended (29-bit) CAN identifier.
+pub const MAX_EXTENDED_ID: u32 = 0x1fff_ffff;
+
+/// Destination address for broadcast to all nodes.
+pub const ADDRESS_GLOBAL: u8 = 0xff;
+
+/// Smallest PDU format of PDU2 (broadcast) parameter groups.
+pub const PDU2_MIN_FORMAT: u8 = 240;
+
+/// # J1939 identifier
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct J1939Id {
+    /// Priority from 0 (highest) to 7.
+    pub priority: u8,
+    /// Parameter group number, 18 bits with the extended data page; for PDU1 groups the
+    /// destination address byte is zero.
+    pub pgn: u32,
+    /// Destination of a PDU1 group; ignored for PDU2 groups.
+    pub destination: u8,
+    pub source_address: u8,
+}
+
+impl J1939Id {
+    /// Split a 29-bit identifier, or `None` if it is wider than 29 bits.
+    pub fn from_can_id(id: u32) -> Option<Self> {
+        if id > MAX_EXTENDED_ID {
+            return None;
+        }
+        let pgn = id >> 8 & 0x3_ffff;
+        let pdu_format = (pgn >> 8) as u8;
+        let (pgn, destination) = if pdu_format < PDU2_MIN_FORMAT {
+            (pgn & !0xff, pgn as u8)
+        } else {
+            (pgn, ADDRESS_GLOBAL)
+        };
+        Some(Self {
+            priority: (id >> 26) as u8,
+            pgn,
+            destination,
+            source_address: id as u8,
+        })
+    }
+
+    /// Join the fields into a 29-bit identifier, or `None` if the priority or PGN is out of
+    /// range.
+    pub fn to_can_id(&self) -> Option<u32> {
+        if self.priority > 7 || self.pgn > 0x3_ffff {
+            return None;
+        }
+        let pgn = if self.pdu_format() < PDU2_MIN_FORMAT {
+            self.pgn & !0xff | u32::from(self.destination)
+        } else {
+            self.pgn
+        };
+        Some(u32::from(self.priority) << 26 | pgn << 8 | u32::from(self.source_address))
+    }
+
+    /// The PDU format, the high byte of the PGN without the data page bits.
+    pub fn pdu_format(&self) -> u8 {
+        (self.pgn >> 8) as u8
+    }
+
+    /// The destination of a PDU1 group, or `None` for a broadcast PDU2 group.
+    pub fn destination_address(&self) -> Option<u8> {
+        if self.pdu_format() < PDU2_MIN_FORMAT {
+            Some(self.destination)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read the `width`-bit signal at `start` of a CAN payload.
+///
+/// Panics if the signal does not fit in the payload.
+pub fn read_signal(payload: &[u8; 8], start: u32, width: u32, endianness: Endianness) -> u64 {
+    let raw = u64::from_ne_bytes(*payload);
+    match endianness {
+        Endianness::Little => u64_le::new(raw).get_bits::<Lsb0>(start, width),
+        Endianness::Big => u64_be::new(raw).get_bits::<Msb0>(start, width),
+    }
+}
+
+/// Replace the `width`-bit signal at `start` of a CAN payload with the low bits of `value`.
+///
+/// Panics if the signal does not fit in the payload.
+pub fn write_signal(
+    payload: &mut [u8; 8],
+    start: u32,
+    width: u32,
+    endianness: Endianness,
+    value: u64,
+) {
+    let raw = u64::from_ne_bytes(*payload);
+    let raw = match endianness {
+        Endianness::Little => {
+            let mut word = u64_le::new(raw);
+            word.set_bits::<Lsb0>(start, width, value);
+            word.into_inner()
+        }
+        Endianness::Big => {
+            let mut word = u64_be::new(raw);
+            word.set_bits::<Msb0>(start, width, value);
+            word.into_inner()
+        }
+    };
+    *payload = raw.to_ne_bytes();
+}
+
+/// Whether a J1939 signal value is "not available", all ones in its width.
+pub fn is_not_available(value: u64, width: u32) -> bool {
+    width != 0 && value == !0 >> (64 - width)
+}
@@ -0,0 +1,160 @@
+//! # CBOR headers
+//!
+//! Every CBOR data item starts with a header byte holding a 3-bit major type and 5 bits of
+//! additional information. Values below 24 are stored inline; 24 to 27 announce a big-endian
+//! argument of 1, 2, 4 or 8 bytes, and 31 marks an indefinite-length item or a "break".
+//! `Header::read_canonical` additionally rejects arguments that are not in their shortest form,
+//! as required by the deterministic encoding used in COSE and CTAP2.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::cbor::*;
+//! let mut buf = [0u8; 16];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! Header::new(MAJOR_UNSIGNED, 500).write(&mut writer).unwrap();
+//! Header::new(MAJOR_TEXT, 5).write(&mut writer).unwrap();
+//! assert!(writer.written() == [0x19, 0x01, 0xf4, 0x65]);
+//!
+//! let mut reader = EndianReader::new(&buf[..4]);
+//! let header = Header::read_canonical(&mut reader).unwrap();
+//! assert!(header.major == MAJOR_UNSIGNED && header.argument == Some(500));
+//! assert!(Header::read(&mut reader).unwrap() == Header::new(MAJOR_TEXT, 5));
+//! // 10 in a 2-byte argument is well-formed, but not canonical.
+//! let mut reader = EndianReader::new(&[0x19, 0x00, 0x0a]);
+//! assert!(Header::read_canonical(&mut reader) == Err(Error::NonCanonical));
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::{u16_be, u32_be, u64_be};
+
+pub const MAJOR_UNSIGNED: u8 = 0;
+pub const MAJOR_NEGATIVE: u8 = 1;
+pub const MAJOR_BYTES: u8 = 2;
+pub const MAJOR_TEXT: u8 = 3;
+pub const MAJOR_ARRAY: u8 = 4;
+pub const MAJOR_MAP: u8 = 5;
+pub const MAJOR_TAG: u8 = 6;
+pub const MAJOR_SIMPLE: u8 = 7;
+
+pub const INFO_U8: u8 = 24;
+pub const INFO_U16: u8 = 25;
+pub const INFO_U32: u8 = 26;
+pub const INFO_U64: u8 = 27;
+pub const INFO_INDEFINITE: u8 = 31;
+
+/// The "break" byte that ends an indefinite-length item.
+pub const BREAK: u8 = 0xff;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a header.
+    UnexpectedEof,
+    /// The additional information is one of the reserved values 28 to 30.
+    ReservedInfo(u8),
+    /// The argument is not encoded in its shortest form.
+    NonCanonical,
+}
+
+/// # Item header
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Header {
+    pub major: u8,
+    /// The argument, or `None` for additional information 31: an indefinite length, or a break
+    /// in major type 7.
+    pub argument: Option<u64>,
+}
+
+impl Header {
+    pub fn new(major: u8, argument: u64) -> Self {
+        Self {
+            major,
+            argument: Some(argument),
+        }
+    }
+
+    /// Header of an indefinite-length byte string, text string, array or map.
+    pub fn indefinite(major: u8) -> Self {
+        Self {
+            major,
+            argument: None,
+        }
+    }
+
+    /// Read a header, accepting arguments of any length.
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        Self::read_info(reader).map(|(header, _)| header)
+    }
+
+    /// Read a header whose argument must use the shortest encoding.
+    ///
+    /// The 2, 4 and 8-byte arguments of major type 7 hold floats and are not checked.
+    pub fn read_canonical(reader: &mut EndianReader) -> Result<Self, Error> {
+        let (header, info) = Self::read_info(reader)?;
+        if let Some(argument) = header.argument {
+            let is_float = header.major == MAJOR_SIMPLE && info > INFO_U8;
+            if !is_float && info != argument_info(argument) {
+                return Err(Error::NonCanonical);
+            }
+        }
+        Ok(header)
+    }
+
+    fn read_info(reader: &mut EndianReader) -> Result<(Self, u8), Error> {
+        let initial = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let argument = match info {
+            0..=23 => Some(u64::from(info)),
+            INFO_U8 => reader.read_u8().map(u64::from),
+            INFO_U16 => reader.read::<u16_be>().map(|v| u64::from(v.to_native())),
+            INFO_U32 => reader.read::<u32_be>().map(|v| u64::from(v.to_native())),
+            INFO_U64 => reader.read::<u64_be>().map(|v| v.to_native()),
+            INFO_INDEFINITE => return Ok((Self::indefinite(major), info)),
+            _ => return Err(Error::ReservedInfo(info)),
+        };
+        let argument = argument.ok_or(Error::UnexpectedEof)?;
+        Ok((Self::new(major, argument), info))
+    }
+
+    /// Number of bytes `write` produces.
+    pub fn encoded_len(&self) -> usize {
+        match self.argument.map(argument_info) {
+            Some(INFO_U8) => 2,
+            Some(INFO_U16) => 3,
+            Some(INFO_U32) => 5,
+            Some(INFO_U64) => 9,
+            _ => 1,
+        }
+    }
+
+    /// Write the header with its argument in the shortest form.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        let argument = match self.argument {
+            Some(argument) => argument,
+            None => return writer.write_u8(self.major << 5 | INFO_INDEFINITE),
+        };
+        let info = argument_info(argument);
+        writer.write_u8(self.major << 5 | info)?;
+        match info {
+            INFO_U8 => writer.write_u8(argument as u8),
+            INFO_U16 => writer.write(u16_be::from_native(argument as u16)),
+            INFO_U32 => writer.write(u32_be::from_native(argument as u32)),
+            INFO_U64 => writer.write(u64_be::from_native(argument)),
+            _ => Some(()),
+        }
+    }
+}
+
+/// Additional information of the shortest encoding of `argument`.
+fn argument_info(argument: u64) -> u8 {
+    if argument < u64::from(INFO_U8) {
+        argument as u8
+    } else if argument <= u64::from(u8::MAX) {
+        INFO_U8
+    } else if argument <= u64::from(u16::MAX) {
+        INFO_U16
+    } else if argument <= u64::from(u32::MAX) {
+        INFO_U32
+    } else {
+        INFO_U64
+    }
+}
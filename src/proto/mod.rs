@@ -0,0 +1,18 @@
+//! # Protocol helpers
+//!
+//! Framing and field helpers for wire protocols, built on `EndianReader` and `EndianWriter`.
+pub mod ber;
+pub mod ble;
+pub mod can;
+pub mod cbor;
+pub mod mqtt;
+pub mod msgpack;
+pub mod postgres;
+pub mod ptp;
+pub mod quic;
+pub mod rtp;
+pub mod sdmmc;
+pub mod thrift;
+pub mod tls;
+pub mod websocket;
+pub mod xdr;
@@ -0,0 +1,213 @@
+//! # MQTT fixed headers
+//!
+//! Every MQTT control packet starts with a fixed header: the packet type in the high nibble of
+//! the first byte and type-specific flags in the low nibble, then the remaining length as a
+//! variable byte integer of 1 to 4 bytes, 7 bits per byte with the least significant group
+//! first. The rest of the protocol uses big-endian `u16` fields (packet identifiers, keep-alive)
+//! and `u16`-length-prefixed strings and binary data, which have readers and writers here too.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::mqtt::*;
+//! let mut buf = [0u8; 16];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! FixedHeader::new(PUBLISH, 0b0010, 200).write(&mut writer).unwrap();
+//! write_string(&mut writer, "a/b").unwrap();
+//! write_u16(&mut writer, 10).unwrap();
+//! assert!(writer.written() == [0x32, 0xc8, 0x01, 0, 3, b'a', b'/', b'b', 0, 10]);
+//! let len = writer.position();
+//!
+//! let mut reader = EndianReader::new(&buf[..len]);
+//! let header = FixedHeader::read(&mut reader).unwrap();
+//! assert!(header.packet_type == PUBLISH && header.qos() == 1 && header.remaining_length == 200);
+//! assert!(read_string(&mut reader) == Ok("a/b"));
+//! assert!(read_u16(&mut reader) == Ok(10));
+//!
+//! let mut reader = EndianReader::new(&[0x82, 0x02]);
+//! assert!(FixedHeader::read(&mut reader).is_ok());
+//! let mut reader = EndianReader::new(&[0x80, 0x02]);
+//! assert!(FixedHeader::read(&mut reader) == Err(Error::InvalidFlags(0)));
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::u16_be;
+use core::convert::TryFrom;
+
+pub const CONNECT: u8 = 1;
+pub const CONNACK: u8 = 2;
+pub const PUBLISH: u8 = 3;
+pub const PUBACK: u8 = 4;
+pub const PUBREC: u8 = 5;
+pub const PUBREL: u8 = 6;
+pub const PUBCOMP: u8 = 7;
+pub const SUBSCRIBE: u8 = 8;
+pub const SUBACK: u8 = 9;
+pub const UNSUBSCRIBE: u8 = 10;
+pub const UNSUBACK: u8 = 11;
+pub const PINGREQ: u8 = 12;
+pub const PINGRESP: u8 = 13;
+pub const DISCONNECT: u8 = 14;
+/// MQTT 5 only.
+pub const AUTH: u8 = 15;
+
+/// Largest remaining length, the maximum of a 4-byte variable byte integer.
+pub const MAX_REMAINING_LENGTH: u32 = 268_435_455;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a field.
+    UnexpectedEof,
+    /// Packet type 0 is reserved.
+    ReservedType,
+    /// The flags are not the fixed value of the packet type, or PUBLISH has QoS 3.
+    InvalidFlags(u8),
+    /// A variable byte integer is longer than 4 bytes.
+    MalformedLength,
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+}
+
+/// # Fixed header
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FixedHeader {
+    pub packet_type: u8,
+    /// Low nibble of the first byte: DUP, QoS and RETAIN for PUBLISH, fixed for other types.
+    pub flags: u8,
+    /// Length of the variable header and payload.
+    pub remaining_length: u32,
+}
+
+impl FixedHeader {
+    /// Largest encoded size of a fixed header.
+    pub const MAX_SIZE: usize = 5;
+
+    pub fn new(packet_type: u8, flags: u8, remaining_length: u32) -> Self {
+        Self {
+            packet_type,
+            flags,
+            remaining_length,
+        }
+    }
+
+    /// Read a fixed header, checking the flags required by the packet type.
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let first = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        let (packet_type, flags) = (first >> 4, first & 0xf);
+        if packet_type == 0 {
+            return Err(Error::ReservedType);
+        }
+        let valid = match packet_type {
+            PUBLISH => flags & 0b0110 != 0b0110,
+            PUBREL | SUBSCRIBE | UNSUBSCRIBE => flags == 0b0010,
+            _ => flags == 0,
+        };
+        if !valid {
+            return Err(Error::InvalidFlags(flags));
+        }
+        let remaining_length = read_varint(reader)?;
+        Ok(Self::new(packet_type, flags, remaining_length))
+    }
+
+    /// Number of bytes `write` produces.
+    pub fn encoded_len(&self) -> usize {
+        1 + varint_len(self.remaining_length)
+    }
+
+    /// Write the header, or `None` if the buffer is full or the remaining length is above
+    /// `MAX_REMAINING_LENGTH`.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write_u8(self.packet_type << 4 | self.flags & 0xf)?;
+        write_varint(writer, self.remaining_length)
+    }
+
+    /// QoS level of a PUBLISH packet.
+    pub fn qos(&self) -> u8 {
+        self.flags >> 1 & 0b11
+    }
+
+    /// DUP flag of a PUBLISH packet.
+    pub fn dup(&self) -> bool {
+        self.flags & 0b1000 != 0
+    }
+
+    /// RETAIN flag of a PUBLISH packet.
+    pub fn retain(&self) -> bool {
+        self.flags & 0b0001 != 0
+    }
+}
+
+/// Read a variable byte integer.
+pub fn read_varint(reader: &mut EndianReader) -> Result<u32, Error> {
+    let mut value = 0;
+    for shift in (0..4).map(|i| 7 * i) {
+        let byte = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        value |= u32::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::MalformedLength)
+}
+
+/// Number of bytes `write_varint` produces for `value`.
+pub fn varint_len(value: u32) -> usize {
+    match value {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        0x4000..=0x1f_ffff => 3,
+        _ => 4,
+    }
+}
+
+/// Write a variable byte integer, or `None` if the buffer is full or `value` is above
+/// `MAX_REMAINING_LENGTH`.
+pub fn write_varint(writer: &mut EndianWriter, mut value: u32) -> Option<()> {
+    if value > MAX_REMAINING_LENGTH {
+        return None;
+    }
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_u8(byte);
+        }
+        writer.write_u8(byte | 0x80)?;
+    }
+}
+
+/// Read a two byte integer.
+pub fn read_u16(reader: &mut EndianReader) -> Result<u16, Error> {
+    reader
+        .read::<u16_be>()
+        .map(|v| v.to_native())
+        .ok_or(Error::UnexpectedEof)
+}
+
+/// Write a two byte integer.
+pub fn write_u16(writer: &mut EndianWriter, value: u16) -> Option<()> {
+    writer.write(u16_be::from_native(value))
+}
+
+/// Read binary data prefixed with its `u16` length.
+pub fn read_binary<'a>(reader: &mut EndianReader<'a>) -> Result<&'a [u8], Error> {
+    let len = read_u16(reader)?;
+    reader
+        .read_bytes(usize::from(len))
+        .ok_or(Error::UnexpectedEof)
+}
+
+/// Read a UTF-8 string prefixed with its `u16` length.
+pub fn read_string<'a>(reader: &mut EndianReader<'a>) -> Result<&'a str, Error> {
+    core::str::from_utf8(read_binary(reader)?).map_err(|_| Error::InvalidUtf8)
+}
+
+/// Write binary data with its `u16` length, or `None` if it is longer than 65535 bytes.
+pub fn write_binary(writer: &mut EndianWriter, data: &[u8]) -> Option<()> {
+    write_u16(writer, u16::try_from(data.len()).ok()?)?;
+    writer.write_bytes(data)
+}
+
+/// Write a UTF-8 string with its `u16` length, or `None` if it is longer than 65535 bytes.
+pub fn write_string(writer: &mut EndianWriter, value: &str) -> Option<()> {
+    write_binary(writer, value.as_bytes())
+}
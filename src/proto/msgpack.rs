@@ -0,0 +1,170 @@
+//! # MessagePack numbers
+//!
+//! Readers and writers for MessagePack's numeric families: positive and negative fixints, and the
+//! marker-prefixed big-endian `uint 8/16/32/64`, `int 8/16/32/64` and `float 32/64` formats.
+//! Writers pick the smallest format that holds the value.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::msgpack::*;
+//! let mut buf = [0u8; 32];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! write_uint(&mut writer, 7).unwrap();
+//! write_uint(&mut writer, 300).unwrap();
+//! write_int(&mut writer, -33).unwrap();
+//! write_f64(&mut writer, 0.5).unwrap();
+//! assert!(writer.written()[..4] == [0x07, UINT16, 0x01, 0x2c]);
+//! let len = writer.position();
+//!
+//! let mut reader = EndianReader::new(&buf[..len]);
+//! assert!(read_number(&mut reader) == Ok(Number::Uint(7)));
+//! assert!(read_uint(&mut reader) == Ok(300));
+//! assert!(read_int(&mut reader) == Ok(-33));
+//! assert!(read_f64(&mut reader) == Ok(0.5));
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::{i16_be, i32_be, i64_be, u16_be, u32_be, u64_be};
+use core::convert::TryFrom;
+
+pub const FLOAT32: u8 = 0xca;
+pub const FLOAT64: u8 = 0xcb;
+pub const UINT8: u8 = 0xcc;
+pub const UINT16: u8 = 0xcd;
+pub const UINT32: u8 = 0xce;
+pub const UINT64: u8 = 0xcf;
+pub const INT8: u8 = 0xd0;
+pub const INT16: u8 = 0xd1;
+pub const INT32: u8 = 0xd2;
+pub const INT64: u8 = 0xd3;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a value.
+    UnexpectedEof,
+    /// The marker is not a numeric format.
+    UnexpectedMarker(u8),
+    /// The value does not fit in the requested type.
+    OutOfRange,
+    /// A float was found where an integer was expected, or the other way round.
+    TypeMismatch,
+}
+
+/// # Number
+/// A decoded numeric value, keeping integers and floats apart.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Number {
+    Uint(u64),
+    Int(i64),
+    F32(f32),
+    F64(f64),
+}
+
+fn read_byte(reader: &mut EndianReader) -> Result<u8, Error> {
+    reader.read_u8().ok_or(Error::UnexpectedEof)
+}
+
+fn read<T: crate::decoder::Decode>(reader: &mut EndianReader) -> Result<T, Error> {
+    reader.read().ok_or(Error::UnexpectedEof)
+}
+
+/// Read any numeric format. Non-negative values of signed formats are returned as `Uint`.
+pub fn read_number(reader: &mut EndianReader) -> Result<Number, Error> {
+    let marker = read_byte(reader)?;
+    let number = match marker {
+        0x00..=0x7f => Number::Uint(u64::from(marker)),
+        0xe0..=0xff => Number::Int(i64::from(marker as i8)),
+        UINT8 => Number::Uint(u64::from(read_byte(reader)?)),
+        UINT16 => Number::Uint(u64::from(read::<u16_be>(reader)?.to_native())),
+        UINT32 => Number::Uint(u64::from(read::<u32_be>(reader)?.to_native())),
+        UINT64 => Number::Uint(read::<u64_be>(reader)?.to_native()),
+        INT8 => Number::Int(i64::from(read_byte(reader)? as i8)),
+        INT16 => Number::Int(i64::from(read::<i16_be>(reader)?.to_native())),
+        INT32 => Number::Int(i64::from(read::<i32_be>(reader)?.to_native())),
+        INT64 => Number::Int(read::<i64_be>(reader)?.to_native()),
+        FLOAT32 => Number::F32(f32::from_bits(read::<u32_be>(reader)?.to_native())),
+        FLOAT64 => Number::F64(f64::from_bits(read::<u64_be>(reader)?.to_native())),
+        _ => return Err(Error::UnexpectedMarker(marker)),
+    };
+    Ok(match number {
+        Number::Int(value) if value >= 0 => Number::Uint(value as u64),
+        number => number,
+    })
+}
+
+/// Read an integer of any format that fits in a `u64`.
+pub fn read_uint(reader: &mut EndianReader) -> Result<u64, Error> {
+    match read_number(reader)? {
+        Number::Uint(value) => Ok(value),
+        Number::Int(_) => Err(Error::OutOfRange),
+        _ => Err(Error::TypeMismatch),
+    }
+}
+
+/// Read an integer of any format that fits in an `i64`.
+pub fn read_int(reader: &mut EndianReader) -> Result<i64, Error> {
+    match read_number(reader)? {
+        Number::Uint(value) => i64::try_from(value).map_err(|_| Error::OutOfRange),
+        Number::Int(value) => Ok(value),
+        _ => Err(Error::TypeMismatch),
+    }
+}
+
+/// Read a float; `float 32` values are widened.
+pub fn read_f64(reader: &mut EndianReader) -> Result<f64, Error> {
+    match read_number(reader)? {
+        Number::F32(value) => Ok(f64::from(value)),
+        Number::F64(value) => Ok(value),
+        _ => Err(Error::TypeMismatch),
+    }
+}
+
+/// Write an unsigned integer in the smallest format.
+pub fn write_uint(writer: &mut EndianWriter, value: u64) -> Option<()> {
+    if value < 0x80 {
+        writer.write_u8(value as u8)
+    } else if value <= u64::from(u8::MAX) {
+        writer.write_u8(UINT8)?;
+        writer.write_u8(value as u8)
+    } else if value <= u64::from(u16::MAX) {
+        writer.write_u8(UINT16)?;
+        writer.write(u16_be::from_native(value as u16))
+    } else if value <= u64::from(u32::MAX) {
+        writer.write_u8(UINT32)?;
+        writer.write(u32_be::from_native(value as u32))
+    } else {
+        writer.write_u8(UINT64)?;
+        writer.write(u64_be::from_native(value))
+    }
+}
+
+/// Write a signed integer in the smallest format; non-negative values use the unsigned formats.
+pub fn write_int(writer: &mut EndianWriter, value: i64) -> Option<()> {
+    if value >= 0 {
+        write_uint(writer, value as u64)
+    } else if value >= -32 {
+        writer.write_u8(value as u8)
+    } else if value >= i64::from(i8::MIN) {
+        writer.write_u8(INT8)?;
+        writer.write_u8(value as u8)
+    } else if value >= i64::from(i16::MIN) {
+        writer.write_u8(INT16)?;
+        writer.write(i16_be::from_native(value as i16))
+    } else if value >= i64::from(i32::MIN) {
+        writer.write_u8(INT32)?;
+        writer.write(i32_be::from_native(value as i32))
+    } else {
+        writer.write_u8(INT64)?;
+        writer.write(i64_be::from_native(value))
+    }
+}
+
+pub fn write_f32(writer: &mut EndianWriter, value: f32) -> Option<()> {
+    writer.write_u8(FLOAT32)?;
+    writer.write(u32_be::from_native(value.to_bits()))
+}
+
+pub fn write_f64(writer: &mut EndianWriter, value: f64) -> Option<()> {
+    writer.write_u8(FLOAT64)?;
+    writer.write(u64_be::from_native(value.to_bits()))
+}
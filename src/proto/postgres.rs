@@ -0,0 +1,164 @@
+//! # PostgreSQL wire protocol
+//!
+//! Message framing for the PostgreSQL frontend/backend protocol. After startup every message is a
+//! type byte followed by a big-endian `i32` length that counts itself and the body; the startup
+//! message has no type byte and starts with its length and a request code.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::postgres::*;
+//! use endiantype::*;
+//! let mut buf = [0u8; 64];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! // ReadyForQuery, idle
+//! write_message(&mut writer, b'Z', b"I").unwrap();
+//! // RowDescription with the body written in place
+//! let start = begin_message(&mut writer, b'T').unwrap();
+//! writer.write(i16_be::from_native(1)).unwrap();
+//! writer.write_bytes(b"id\0").unwrap();
+//! finish_message(&mut writer, start).unwrap();
+//! let len = writer.position();
+//!
+//! let (ready, used) = Message::parse(&buf[..len]).unwrap().unwrap();
+//! assert!(ready.tag == b'Z' && ready.body == b"I" && used == 6);
+//! let (row, _) = Message::parse(&buf[used..len]).unwrap().unwrap();
+//! let mut fields = row.reader();
+//! assert!(fields.read::<i16_be>().unwrap() == 1);
+//! assert!(read_cstr(&mut fields).unwrap() == b"id");
+//!
+//! // Not enough bytes yet
+//! assert!(Message::parse(&buf[..3]).unwrap().is_none());
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::i32_be;
+use core::convert::TryFrom;
+
+/// Request code of a protocol 3.0 startup message.
+pub const PROTOCOL_VERSION_3: i32 = 196_608;
+/// Request code of an `SSLRequest`.
+pub const SSL_REQUEST_CODE: i32 = 80_877_103;
+/// Request code of a `GSSENCRequest`.
+pub const GSSENC_REQUEST_CODE: i32 = 80_877_104;
+/// Request code of a `CancelRequest`.
+pub const CANCEL_REQUEST_CODE: i32 = 80_877_102;
+
+/// # Framing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The length field is smaller than the minimum for the message kind.
+    InvalidLength(i32),
+}
+
+/// # Message
+/// A type byte and the body that follows the length field.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Message<'a> {
+    pub tag: u8,
+    pub body: &'a [u8],
+}
+
+impl<'a> Message<'a> {
+    /// Split the first message off `buf`, returning it with the number of bytes it occupied, or
+    /// `None` if `buf` does not hold a complete message yet.
+    pub fn parse(buf: &'a [u8]) -> Result<Option<(Self, usize)>, Error> {
+        let mut reader = EndianReader::new(buf);
+        let tag = match reader.read_u8() {
+            Some(tag) => tag,
+            None => return Ok(None),
+        };
+        let body = match framed_body(&mut reader, 4)? {
+            Some(body) => body,
+            None => return Ok(None),
+        };
+        Ok(Some((Self { tag, body }, reader.position())))
+    }
+
+    /// A reader over the message body.
+    pub fn reader(&self) -> EndianReader<'a> {
+        EndianReader::new(self.body)
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        write_message(writer, self.tag, self.body)
+    }
+}
+
+/// # Startup message
+/// The untyped first message of a connection: `StartupMessage`, `SSLRequest`, `GSSENCRequest` or
+/// `CancelRequest`, told apart by `code`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StartupMessage<'a> {
+    pub code: i32_be,
+    pub body: &'a [u8],
+}
+
+impl<'a> StartupMessage<'a> {
+    /// Split the startup message off `buf`, returning it with the number of bytes it occupied, or
+    /// `None` if `buf` does not hold a complete message yet.
+    pub fn parse(buf: &'a [u8]) -> Result<Option<(Self, usize)>, Error> {
+        let mut reader = EndianReader::new(buf);
+        let mut body = match framed_body(&mut reader, 8)? {
+            Some(body) => EndianReader::new(body),
+            None => return Ok(None),
+        };
+        let code = body.read::<i32_be>().unwrap();
+        Ok(Some((
+            Self {
+                code,
+                body: body.rest(),
+            },
+            reader.position(),
+        )))
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        let len = i32::try_from(8 + self.body.len()).ok()?;
+        writer.write(i32_be::from_native(len))?;
+        writer.write(self.code)?;
+        writer.write_bytes(self.body)
+    }
+}
+
+/// Read the length field and return the rest of the frame, which must be at least `min_len`
+/// bytes long including the length field itself.
+fn framed_body<'a>(reader: &mut EndianReader<'a>, min_len: i32) -> Result<Option<&'a [u8]>, Error> {
+    let len = match reader.read::<i32_be>() {
+        Some(len) => len.to_native(),
+        None => return Ok(None),
+    };
+    if len < min_len {
+        return Err(Error::InvalidLength(len));
+    }
+    Ok(reader.read_bytes(len as usize - 4))
+}
+
+/// Write a complete message with the given type byte and body.
+pub fn write_message(writer: &mut EndianWriter, tag: u8, body: &[u8]) -> Option<()> {
+    let len = i32::try_from(4 + body.len()).ok()?;
+    writer.write_u8(tag)?;
+    writer.write(i32_be::from_native(len))?;
+    writer.write_bytes(body)
+}
+
+/// Write a type byte and a placeholder length, returning the position to pass to
+/// `finish_message` once the body has been written.
+pub fn begin_message(writer: &mut EndianWriter, tag: u8) -> Option<usize> {
+    writer.write_u8(tag)?;
+    let start = writer.position();
+    writer.write(i32_be::from_native(0))?;
+    Some(start)
+}
+
+/// Fill in the length of the message started at `start`.
+pub fn finish_message(writer: &mut EndianWriter, start: usize) -> Option<()> {
+    let len = i32::try_from(writer.position().checked_sub(start)?).ok()?;
+    writer.write_at(start, i32_be::from_native(len))
+}
+
+/// Read a null-terminated string, returning it without the terminator.
+pub fn read_cstr<'a>(reader: &mut EndianReader<'a>) -> Option<&'a [u8]> {
+    let len = reader.rest().iter().position(|&b| b == 0)?;
+    let s = reader.read_bytes(len)?;
+    reader.skip(1)?;
+    Some(s)
+}
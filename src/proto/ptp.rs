@@ -0,0 +1,155 @@
+//! # PTP message headers
+//!
+//! The IEEE 1588 (PTPv2) common message header and timestamp, declared with `endian_struct!`.
+//! All fields are big endian; timestamps carry a 48-bit seconds field, and the correction field
+//! counts nanoseconds in 48.16 fixed point.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::ptp::*;
+//! use core::time::Duration;
+//! let mut buf = [0u8; 44];
+//! let mut header = Header::default();
+//! header.message_type.set_message_type(MSG_SYNC);
+//! header.version.set_version(2);
+//! header.message_length = (buf.len() as u16).into();
+//! header.set_correction_nanos(1500);
+//! let mut writer = EndianWriter::new(&mut buf);
+//! writer.write(header).unwrap();
+//! writer.write(Timestamp::from_duration(Duration::new(0x1_0000_0001, 42))).unwrap();
+//! assert!(buf[8..14] == [0, 0, 0, 0, 0x05, 0xdc]);
+//!
+//! let mut reader = EndianReader::new(&buf);
+//! let header = reader.read::<Header>().unwrap();
+//! assert!(header.message_type.message_type() == MSG_SYNC && header.correction_nanos() == 1500);
+//! let origin = reader.read::<Timestamp>().unwrap();
+//! assert!(origin.seconds() == 0x1_0000_0001 && origin.nanoseconds == 42);
+//! ```
+use crate::bitorder::Msb0;
+use crate::types::{i64_be, i8_be, u16_be, u32_be, u8_be};
+use core::time::Duration;
+
+pub const MSG_SYNC: u8 = 0x0;
+pub const MSG_DELAY_REQ: u8 = 0x1;
+pub const MSG_PDELAY_REQ: u8 = 0x2;
+pub const MSG_PDELAY_RESP: u8 = 0x3;
+pub const MSG_FOLLOW_UP: u8 = 0x8;
+pub const MSG_DELAY_RESP: u8 = 0x9;
+pub const MSG_PDELAY_RESP_FOLLOW_UP: u8 = 0xa;
+pub const MSG_ANNOUNCE: u8 = 0xb;
+pub const MSG_SIGNALING: u8 = 0xc;
+pub const MSG_MANAGEMENT: u8 = 0xd;
+
+pub const FLAG_ALTERNATE_MASTER: u16 = 0x0100;
+pub const FLAG_TWO_STEP: u16 = 0x0200;
+pub const FLAG_UNICAST: u16 = 0x0400;
+pub const FLAG_LEAP61: u16 = 0x0001;
+pub const FLAG_LEAP59: u16 = 0x0002;
+pub const FLAG_UTC_OFFSET_VALID: u16 = 0x0004;
+pub const FLAG_PTP_TIMESCALE: u16 = 0x0008;
+
+bitfield! {
+    /// First octet of the header.
+    pub struct MessageType(u8_be, Msb0) {
+        pub major_sdo_id, set_major_sdo_id: u8 = 0, 4;
+        pub message_type, set_message_type: u8 = 4, 4;
+    }
+}
+
+bitfield! {
+    /// Second octet of the header.
+    pub struct Version(u8_be, Msb0) {
+        pub minor_version, set_minor_version: u8 = 0, 4;
+        pub version, set_version: u8 = 4, 4;
+    }
+}
+
+crate::endian_struct! {
+    /// # Common message header
+    /// The 34-byte header at the start of every PTP message.
+    #[derive(Default)]
+    pub struct Header {
+        pub message_type: MessageType,
+        pub version: Version,
+        pub message_length: u16_be,
+        pub domain_number: u8_be,
+        pub minor_sdo_id: u8_be,
+        pub flags: u16_be,
+        /// Nanoseconds multiplied by 2^16.
+        pub correction: i64_be,
+        pub message_type_specific: u32_be,
+        pub clock_identity: [u8; 8],
+        pub port_number: u16_be,
+        pub sequence_id: u16_be,
+        pub control: u8_be,
+        pub log_message_interval: i8_be,
+    }
+}
+
+impl Header {
+    /// Whole nanoseconds of the correction field, rounded towards negative infinity.
+    pub fn correction_nanos(&self) -> i64 {
+        self.correction.to_native() >> 16
+    }
+
+    /// Fractional nanoseconds of the correction field, in units of 2^-16 ns.
+    pub fn correction_subnanos(&self) -> u16 {
+        self.correction.to_native() as u16
+    }
+
+    /// Set the correction field to `nanos`, saturating at the largest representable value.
+    pub fn set_correction_nanos(&mut self, nanos: i64) {
+        let scaled = nanos.saturating_mul(1 << 16);
+        self.correction = i64_be::from_native(scaled);
+    }
+
+    /// Add `nanos` to the correction field, as transparent clocks do for residence time.
+    pub fn add_correction_nanos(&mut self, nanos: i64) {
+        let scaled = nanos.saturating_mul(1 << 16);
+        self.correction = i64_be::from_native(self.correction.to_native().saturating_add(scaled));
+    }
+
+    pub fn has_flag(&self, flag: u16) -> bool {
+        self.flags.to_native() & flag != 0
+    }
+}
+
+crate::endian_struct! {
+    /// # Timestamp
+    /// Seconds and nanoseconds since the PTP epoch, with a 48-bit seconds field.
+    #[derive(Default)]
+    pub struct Timestamp {
+        pub seconds_high: u16_be,
+        pub seconds_low: u32_be,
+        pub nanoseconds: u32_be,
+    }
+}
+
+impl Timestamp {
+    /// Largest value of the seconds field.
+    pub const MAX_SECONDS: u64 = (1 << 48) - 1;
+
+    /// Panics if `seconds` does not fit in 48 bits.
+    pub fn new(seconds: u64, nanoseconds: u32) -> Self {
+        assert!(seconds <= Self::MAX_SECONDS);
+        Self {
+            seconds_high: u16_be::from_native((seconds >> 32) as u16),
+            seconds_low: u32_be::from_native(seconds as u32),
+            nanoseconds: u32_be::from_native(nanoseconds),
+        }
+    }
+
+    /// Panics if the seconds do not fit in 48 bits.
+    pub fn from_duration(duration: Duration) -> Self {
+        Self::new(duration.as_secs(), duration.subsec_nanos())
+    }
+
+    pub fn seconds(&self) -> u64 {
+        u64::from(self.seconds_high.to_native()) << 32 | u64::from(self.seconds_low.to_native())
+    }
+
+    /// The timestamp as time since the PTP epoch; nanoseconds beyond one second carry over.
+    pub fn to_duration(&self) -> Duration {
+        Duration::new(self.seconds(), self.nanoseconds.to_native())
+    }
+}
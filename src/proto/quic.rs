@@ -0,0 +1,109 @@
+//! # QUIC variable-length integers
+//!
+//! RFC 9000 varints: a big-endian integer of 1, 2, 4 or 8 bytes whose two most significant bits
+//! give the length, leaving 62 bits for the value. Unlike LEB128 the length is known from the
+//! first byte.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::quic::VarInt;
+//! let mut buf = [0u8; 16];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! VarInt::from_u32(37).write(&mut writer).unwrap();
+//! VarInt::from_u32(15293).write(&mut writer).unwrap();
+//! VarInt::new(151_288_809_941_952_652).unwrap().write(&mut writer).unwrap();
+//! assert!(writer.written()[..3] == [0x25, 0x7b, 0xbd]);
+//! assert!(writer.position() == 11);
+//!
+//! let mut reader = EndianReader::new(&buf);
+//! assert!(VarInt::read(&mut reader).unwrap().into_inner() == 37);
+//! assert!(VarInt::read(&mut reader).unwrap().into_inner() == 15293);
+//! assert!(VarInt::decode(&[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c]).unwrap().1 == 8);
+//! assert!(VarInt::new(1 << 62).is_none());
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::{u16_be, u32_be, u64_be};
+
+/// # Variable-length integer
+/// A value in `0..=VarInt::MAX`.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VarInt(u64);
+
+impl VarInt {
+    pub const MAX: u64 = (1 << 62) - 1;
+
+    /// Largest size of an encoded varint.
+    pub const MAX_SIZE: usize = 8;
+
+    /// `None` if `value` is larger than `VarInt::MAX`.
+    pub const fn new(value: u64) -> Option<Self> {
+        if value <= Self::MAX {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    pub const fn from_u32(value: u32) -> Self {
+        Self(value as u64)
+    }
+
+    pub const fn into_inner(self) -> u64 {
+        self.0
+    }
+
+    /// Number of bytes of the shortest encoding.
+    pub const fn encoded_len(&self) -> usize {
+        if self.0 < 1 << 6 {
+            1
+        } else if self.0 < 1 << 14 {
+            2
+        } else if self.0 < 1 << 30 {
+            4
+        } else {
+            8
+        }
+    }
+
+    /// Decode a varint from the front of `bytes`, returning it with the number of bytes used, or
+    /// `None` if `bytes` is too short.
+    pub fn decode(bytes: &[u8]) -> Option<(Self, usize)> {
+        let mut reader = EndianReader::new(bytes);
+        let value = Self::read(&mut reader)?;
+        Some((value, reader.position()))
+    }
+
+    pub fn read(reader: &mut EndianReader) -> Option<Self> {
+        let value = match reader.rest().first()? >> 6 {
+            0 => u64::from(reader.read_u8()?),
+            1 => u64::from(reader.read::<u16_be>()?.to_native() & 0x3fff),
+            2 => u64::from(reader.read::<u32_be>()?.to_native() & 0x3fff_ffff),
+            _ => reader.read::<u64_be>()?.to_native() & Self::MAX,
+        };
+        Some(Self(value))
+    }
+
+    /// Write the shortest encoding.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        match self.encoded_len() {
+            1 => writer.write_u8(self.0 as u8),
+            2 => writer.write(u16_be::from_native(0x4000 | self.0 as u16)),
+            4 => writer.write(u32_be::from_native(0x8000_0000 | self.0 as u32)),
+            _ => writer.write(u64_be::from_native(0xc000_0000_0000_0000 | self.0)),
+        }
+    }
+}
+
+impl From<u32> for VarInt {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self::from_u32(value)
+    }
+}
+
+impl From<VarInt> for u64 {
+    #[inline]
+    fn from(value: VarInt) -> Self {
+        value.0
+    }
+}
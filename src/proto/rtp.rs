@@ -0,0 +1,216 @@
+//! # RTP and RTCP headers
+//!
+//! The RTP fixed header and the RTCP common header (RFC 3550), with their bit-packed first octets
+//! declared through `bitfield!`. `RtpPacket` splits a packet into header, CSRC list, header
+//! extension and payload; `RtcpPackets` walks a compound RTCP packet.
+//! ## Example
+//! ```
+//! use endiantype::proto::rtp::*;
+//! let packet = [
+//!     0x81, 0xe0, 0x12, 0x34, // V=2, CC=1, M=1, PT=96, sequence
+//!     0x00, 0x00, 0x00, 0x10, // timestamp
+//!     0xca, 0xfe, 0xba, 0xbe, // SSRC
+//!     0x00, 0x00, 0x00, 0x01, // CSRC
+//!     0xaa, 0xbb,             // payload
+//! ];
+//! let rtp = RtpPacket::parse(&packet).unwrap();
+//! assert!(rtp.header.flags.marker() == 1 && rtp.header.flags.payload_type() == 96);
+//! assert!(rtp.header.sequence == 0x1234 && rtp.header.ssrc == 0xcafebabe);
+//! assert!(rtp.csrcs().eq([1u32].iter().copied()));
+//! assert!(rtp.payload == [0xaa, 0xbb]);
+//!
+//! // Receiver report with no report blocks, then an empty BYE
+//! let compound = [0x80, 201, 0x00, 0x01, 0, 0, 0, 1, 0x80, 203, 0x00, 0x00];
+//! let mut packets = RtcpPackets::new(&compound);
+//! let (rr, body) = packets.next().unwrap().unwrap();
+//! assert!(rr.packet_type == PT_RR && body.len() == 4);
+//! assert!(packets.next().unwrap().unwrap().0.packet_type == PT_BYE);
+//! assert!(packets.next().is_none());
+//! ```
+use crate::bitorder::Msb0;
+use crate::io::EndianReader;
+use crate::types::{u16_be, u32_be, u8_be};
+
+pub const RTP_VERSION: u8 = 2;
+
+pub const PT_SR: u8 = 200;
+pub const PT_RR: u8 = 201;
+pub const PT_SDES: u8 = 202;
+pub const PT_BYE: u8 = 203;
+pub const PT_APP: u8 = 204;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a header or the lengths it declares.
+    UnexpectedEof,
+    /// The version bits are not 2.
+    BadVersion(u8),
+    /// The padding count is zero or larger than the packet.
+    InvalidPadding,
+}
+
+bitfield! {
+    /// First two octets of the RTP fixed header.
+    pub struct RtpFlags(u16_be, Msb0) {
+        pub version, set_version: u8 = 0, 2;
+        pub padding, set_padding: u8 = 2, 1;
+        pub extension, set_extension: u8 = 3, 1;
+        pub csrc_count, set_csrc_count: u8 = 4, 4;
+        pub marker, set_marker: u8 = 8, 1;
+        pub payload_type, set_payload_type: u8 = 9, 7;
+    }
+}
+
+bitfield! {
+    /// First octet of the RTCP common header.
+    pub struct RtcpFlags(u8_be, Msb0) {
+        pub version, set_version: u8 = 0, 2;
+        pub padding, set_padding: u8 = 2, 1;
+        pub count, set_count: u8 = 3, 5;
+    }
+}
+
+/// # RTP fixed header
+/// The 12-byte header at the start of every RTP packet, laid out as on the wire.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct RtpHeader {
+    pub flags: RtpFlags,
+    pub sequence: u16_be,
+    pub timestamp: u32_be,
+    pub ssrc: u32_be,
+}
+
+impl RtpHeader {
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let header = Self {
+            flags: RtpFlags(reader.read().ok_or(Error::UnexpectedEof)?),
+            sequence: reader.read().ok_or(Error::UnexpectedEof)?,
+            timestamp: reader.read().ok_or(Error::UnexpectedEof)?,
+            ssrc: reader.read().ok_or(Error::UnexpectedEof)?,
+        };
+        match header.flags.version() {
+            RTP_VERSION => Ok(header),
+            version => Err(Error::BadVersion(version)),
+        }
+    }
+}
+
+/// # RTP packet
+/// An RTP packet split into its parts; padding is removed from the payload.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RtpPacket<'a> {
+    pub header: RtpHeader,
+    /// The CSRC list, `csrc_count` big-endian `u32`s.
+    pub csrc: &'a [u8],
+    /// Profile-defined identifier and data of the header extension.
+    pub extension: Option<(u16_be, &'a [u8])>,
+    pub payload: &'a [u8],
+}
+
+impl<'a> RtpPacket<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let mut reader = EndianReader::new(bytes);
+        let header = RtpHeader::read(&mut reader)?;
+        let csrc_len = usize::from(header.flags.csrc_count()) * 4;
+        let csrc = reader.read_bytes(csrc_len).ok_or(Error::UnexpectedEof)?;
+        let extension = if header.flags.extension() == 1 {
+            let profile = reader.read::<u16_be>().ok_or(Error::UnexpectedEof)?;
+            let words = reader.read::<u16_be>().ok_or(Error::UnexpectedEof)?;
+            let data = reader
+                .read_bytes(usize::from(words.to_native()) * 4)
+                .ok_or(Error::UnexpectedEof)?;
+            Some((profile, data))
+        } else {
+            None
+        };
+        let mut payload = reader.rest();
+        if header.flags.padding() == 1 {
+            let count = usize::from(*payload.last().ok_or(Error::InvalidPadding)?);
+            if count == 0 || count > payload.len() {
+                return Err(Error::InvalidPadding);
+            }
+            payload = &payload[..payload.len() - count];
+        }
+        Ok(Self {
+            header,
+            csrc,
+            extension,
+            payload,
+        })
+    }
+
+    /// Iterate over the contributing sources.
+    pub fn csrcs(&self) -> impl Iterator<Item = u32> + 'a {
+        self.csrc
+            .chunks_exact(4)
+            .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+    }
+}
+
+/// # RTCP common header
+/// The 4-byte header shared by all RTCP packet types.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct RtcpHeader {
+    pub flags: RtcpFlags,
+    pub packet_type: u8,
+    /// Length in 32-bit words, minus one.
+    pub length: u16_be,
+}
+
+impl RtcpHeader {
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let header = Self {
+            flags: RtcpFlags(reader.read().ok_or(Error::UnexpectedEof)?),
+            packet_type: reader.read_u8().ok_or(Error::UnexpectedEof)?,
+            length: reader.read().ok_or(Error::UnexpectedEof)?,
+        };
+        match header.flags.version() {
+            RTP_VERSION => Ok(header),
+            version => Err(Error::BadVersion(version)),
+        }
+    }
+
+    /// Length of the whole packet in bytes, including this header.
+    pub fn packet_len(&self) -> usize {
+        (usize::from(self.length.to_native()) + 1) * 4
+    }
+}
+
+/// # Compound RTCP iterator
+/// Yields each packet's header and the body following it.
+#[derive(Clone, Debug)]
+pub struct RtcpPackets<'a> {
+    reader: EndianReader<'a>,
+}
+
+impl<'a> RtcpPackets<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            reader: EndianReader::new(bytes),
+        }
+    }
+}
+
+impl<'a> Iterator for RtcpPackets<'a> {
+    type Item = Result<(RtcpHeader, &'a [u8]), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.is_empty() {
+            return None;
+        }
+        let item = RtcpHeader::read(&mut self.reader).and_then(|header| {
+            let body = self
+                .reader
+                .read_bytes(header.packet_len() - 4)
+                .ok_or(Error::UnexpectedEof)?;
+            Ok((header, body))
+        });
+        if item.is_err() {
+            self.reader = EndianReader::new(&[]);
+        }
+        Some(item)
+    }
+}
@@ -0,0 +1,145 @@
+//! # SD card registers
+//!
+//! The 128-bit CID (card identification) and CSD (card specific data) registers of SD cards,
+//! declared with `bitfield!` over a `u128_be`. Cards send them most significant byte first in an
+//! R2 response, and the SD specification numbers their bits from 127 down to 0, so every field
+//! here is given as `start, width` in `Lsb0` order and can be checked against the spec tables.
+//! Bit 0 is always 1 and bits 7 to 1 hold the CRC7; hosts that strip the CRC and shift the
+//! response, as many SDHCI controllers do, must shift it back first.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::sdmmc::*;
+//! let cid = [
+//!     0x03, 0x53, 0x44, 0x53, 0x44, 0x33, 0x32, 0x47, 0x80, 0x12, 0x34, 0x56, 0x78, 0x00, 0xf6,
+//!     0x11,
+//! ];
+//! let cid = EndianReader::new(&cid).read::<Cid>().unwrap();
+//! assert!(cid.mid() == 0x03 && &cid.oid_bytes() == b"SD" && &cid.product_name() == b"SD32G");
+//! assert!(cid.psn() == 0x1234_5678 && cid.manufacture_date() == (2015, 6));
+//!
+//! let csd = [
+//!     0x40, 0x0e, 0x00, 0x32, 0x5b, 0x59, 0x00, 0x00, 0x3b, 0x37, 0x7f, 0x80, 0x0a, 0x40, 0x40,
+//!     0x00,
+//! ];
+//! let csd = EndianReader::new(&csd).read::<Csd>().unwrap();
+//! assert!(csd.csd_structure() == CSD_STRUCTURE_V2 && csd.c_size_v2() == 15159);
+//! assert!(csd.capacity_bytes() == Some(15160 * 512 * 1024));
+//! assert!(csd.tran_speed_bps() == Some(25_000_000));
+//! ```
+use crate::bitorder::Lsb0;
+use crate::types::u128_be;
+
+/// CSD version 1.0, used by standard capacity cards.
+pub const CSD_STRUCTURE_V1: u8 = 0;
+/// CSD version 2.0, used by SDHC and SDXC cards.
+pub const CSD_STRUCTURE_V2: u8 = 1;
+
+bitfield! {
+    /// Card identification register.
+    pub struct Cid(u128_be, Lsb0) {
+        /// Manufacturer ID.
+        pub mid, set_mid: u8 = 120, 8;
+        /// OEM/application ID, two ASCII characters.
+        pub oid, set_oid: u16 = 104, 16;
+        /// Product name, five ASCII characters.
+        pub pnm, set_pnm: u64 = 64, 40;
+        /// Product revision, as major and minor BCD digits.
+        pub prv, set_prv: u8 = 56, 8;
+        /// Product serial number.
+        pub psn, set_psn: u32 = 24, 32;
+        /// Manufacturing date: years since 2000 in the high 8 bits, month in the low 4.
+        pub mdt, set_mdt: u16 = 8, 12;
+        pub crc, set_crc: u8 = 1, 7;
+    }
+}
+
+impl Cid {
+    pub fn oid_bytes(&self) -> [u8; 2] {
+        self.oid().to_be_bytes()
+    }
+
+    pub fn product_name(&self) -> [u8; 5] {
+        let bytes = self.pnm().to_be_bytes();
+        [bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]
+    }
+
+    /// Manufacturing date as `(year, month)`.
+    pub fn manufacture_date(&self) -> (u16, u8) {
+        let mdt = self.mdt();
+        (2000 + (mdt >> 4), (mdt & 0xf) as u8)
+    }
+}
+
+bitfield! {
+    /// Card specific data register, versions 1.0 and 2.0.
+    pub struct Csd(u128_be, Lsb0) {
+        pub csd_structure, set_csd_structure: u8 = 126, 2;
+        /// Asynchronous data access time.
+        pub taac, set_taac: u8 = 112, 8;
+        /// Data access time in units of 100 clock cycles.
+        pub nsac, set_nsac: u8 = 104, 8;
+        /// Maximum transfer rate; see `tran_speed_bps`.
+        pub tran_speed, set_tran_speed: u8 = 96, 8;
+        /// Card command classes.
+        pub ccc, set_ccc: u16 = 84, 12;
+        /// Log2 of the maximum read block length.
+        pub read_bl_len, set_read_bl_len: u8 = 80, 4;
+        pub read_bl_partial, set_read_bl_partial: u8 = 79, 1;
+        pub write_blk_misalign, set_write_blk_misalign: u8 = 78, 1;
+        pub read_blk_misalign, set_read_blk_misalign: u8 = 77, 1;
+        pub dsr_imp, set_dsr_imp: u8 = 76, 1;
+        /// Device size of version 1.0 cards.
+        pub c_size_v1, set_c_size_v1: u16 = 62, 12;
+        /// Device size multiplier of version 1.0 cards.
+        pub c_size_mult, set_c_size_mult: u8 = 47, 3;
+        /// Device size of version 2.0 cards, in 512 KiB units minus one.
+        pub c_size_v2, set_c_size_v2: u32 = 48, 22;
+        pub erase_blk_en, set_erase_blk_en: u8 = 46, 1;
+        pub sector_size, set_sector_size: u8 = 39, 7;
+        pub wp_grp_size, set_wp_grp_size: u8 = 32, 7;
+        pub wp_grp_enable, set_wp_grp_enable: u8 = 31, 1;
+        pub r2w_factor, set_r2w_factor: u8 = 26, 3;
+        /// Log2 of the maximum write block length.
+        pub write_bl_len, set_write_bl_len: u8 = 22, 4;
+        pub write_bl_partial, set_write_bl_partial: u8 = 21, 1;
+        pub file_format_grp, set_file_format_grp: u8 = 15, 1;
+        pub copy, set_copy: u8 = 14, 1;
+        pub perm_write_protect, set_perm_write_protect: u8 = 13, 1;
+        pub tmp_write_protect, set_tmp_write_protect: u8 = 12, 1;
+        pub file_format, set_file_format: u8 = 10, 2;
+        pub crc, set_crc: u8 = 1, 7;
+    }
+}
+
+impl Csd {
+    /// Card capacity in bytes, or `None` for an unknown CSD version.
+    pub fn capacity_bytes(&self) -> Option<u64> {
+        match self.csd_structure() {
+            CSD_STRUCTURE_V1 => {
+                let blocks = u64::from(self.c_size_v1() + 1) << (self.c_size_mult() + 2);
+                Some(blocks << self.read_bl_len())
+            }
+            CSD_STRUCTURE_V2 => Some(u64::from(self.c_size_v2() + 1) << 19),
+            _ => None,
+        }
+    }
+
+    /// Maximum transfer rate in bits per second, or `None` if `TRAN_SPEED` is reserved.
+    pub fn tran_speed_bps(&self) -> Option<u32> {
+        // Time values 1.0 to 8.0, times ten.
+        const TIME_VALUE: [u32; 16] = [
+            0, 10, 12, 13, 15, 20, 25, 30, 35, 40, 45, 50, 55, 60, 70, 80,
+        ];
+        let speed = self.tran_speed();
+        let (unit, value) = (
+            u32::from(speed & 0x7),
+            TIME_VALUE[usize::from(speed >> 3 & 0xf)],
+        );
+        if unit > 3 || value == 0 {
+            return None;
+        }
+        // Rate units start at 100 kbit/s.
+        Some(value * 10_000 * 10u32.pow(unit))
+    }
+}
@@ -0,0 +1,257 @@
+//! # Thrift binary protocol
+//!
+//! Primitives of Thrift's binary protocol: big-endian integers and doubles, `i32`
+//! length-prefixed binaries and strings, and the field, collection and strict message headers.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::thrift::*;
+//! let mut buf = [0u8; 64];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! let header = MessageHeader { name: "ping", message_type: MESSAGE_CALL, seq_id: 7 };
+//! header.write(&mut writer).unwrap();
+//! FieldHeader { field_type: DOUBLE, id: 1 }.write(&mut writer).unwrap();
+//! write_double(&mut writer, 1.5).unwrap();
+//! write_field_stop(&mut writer).unwrap();
+//! let len = writer.position();
+//!
+//! let mut reader = EndianReader::new(&buf[..len]);
+//! assert!(MessageHeader::read(&mut reader).unwrap() == header);
+//! let field = read_field_header(&mut reader).unwrap().unwrap();
+//! assert!(field.field_type == DOUBLE && field.id == 1);
+//! assert!(read_double(&mut reader).unwrap() == 1.5);
+//! assert!(read_field_header(&mut reader).unwrap().is_none());
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::{i16_be, i32_be, i64_be, u32_be, u64_be};
+use core::convert::TryFrom;
+
+pub const STOP: u8 = 0;
+pub const VOID: u8 = 1;
+pub const BOOL: u8 = 2;
+pub const BYTE: u8 = 3;
+pub const DOUBLE: u8 = 4;
+pub const I16: u8 = 6;
+pub const I32: u8 = 8;
+pub const I64: u8 = 10;
+pub const STRING: u8 = 11;
+pub const STRUCT: u8 = 12;
+pub const MAP: u8 = 13;
+pub const SET: u8 = 14;
+pub const LIST: u8 = 15;
+
+pub const MESSAGE_CALL: u8 = 1;
+pub const MESSAGE_REPLY: u8 = 2;
+pub const MESSAGE_EXCEPTION: u8 = 3;
+pub const MESSAGE_ONEWAY: u8 = 4;
+
+/// Version bits of a strict message header.
+pub const VERSION_1: u32 = 0x8001_0000;
+const VERSION_MASK: u32 = 0xffff_0000;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a value.
+    UnexpectedEof,
+    /// A length or collection size was negative.
+    NegativeSize(i32),
+    /// A string was not valid UTF-8.
+    InvalidUtf8,
+    /// A message header did not carry the strict version 1 marker.
+    BadVersion(u32),
+}
+
+fn read<T: crate::decoder::Decode>(reader: &mut EndianReader) -> Result<T, Error> {
+    reader.read().ok_or(Error::UnexpectedEof)
+}
+
+fn read_size(reader: &mut EndianReader) -> Result<usize, Error> {
+    let size = read::<i32_be>(reader)?.to_native();
+    usize::try_from(size).map_err(|_| Error::NegativeSize(size))
+}
+
+fn write_size(writer: &mut EndianWriter, size: usize) -> Option<()> {
+    writer.write(i32_be::from_native(i32::try_from(size).ok()?))
+}
+
+pub fn read_bool(reader: &mut EndianReader) -> Result<bool, Error> {
+    read_byte(reader).map(|b| b != 0)
+}
+
+pub fn write_bool(writer: &mut EndianWriter, value: bool) -> Option<()> {
+    writer.write_u8(value as u8)
+}
+
+pub fn read_byte(reader: &mut EndianReader) -> Result<i8, Error> {
+    reader
+        .read_u8()
+        .map(|b| b as i8)
+        .ok_or(Error::UnexpectedEof)
+}
+
+pub fn write_byte(writer: &mut EndianWriter, value: i8) -> Option<()> {
+    writer.write_u8(value as u8)
+}
+
+pub fn read_i16(reader: &mut EndianReader) -> Result<i16, Error> {
+    read::<i16_be>(reader).map(|v| v.to_native())
+}
+
+pub fn write_i16(writer: &mut EndianWriter, value: i16) -> Option<()> {
+    writer.write(i16_be::from_native(value))
+}
+
+pub fn read_i32(reader: &mut EndianReader) -> Result<i32, Error> {
+    read::<i32_be>(reader).map(|v| v.to_native())
+}
+
+pub fn write_i32(writer: &mut EndianWriter, value: i32) -> Option<()> {
+    writer.write(i32_be::from_native(value))
+}
+
+pub fn read_i64(reader: &mut EndianReader) -> Result<i64, Error> {
+    read::<i64_be>(reader).map(|v| v.to_native())
+}
+
+pub fn write_i64(writer: &mut EndianWriter, value: i64) -> Option<()> {
+    writer.write(i64_be::from_native(value))
+}
+
+pub fn read_double(reader: &mut EndianReader) -> Result<f64, Error> {
+    read::<u64_be>(reader).map(|v| f64::from_bits(v.to_native()))
+}
+
+pub fn write_double(writer: &mut EndianWriter, value: f64) -> Option<()> {
+    writer.write(u64_be::from_native(value.to_bits()))
+}
+
+/// Read an `i32` length-prefixed byte string.
+pub fn read_binary<'a>(reader: &mut EndianReader<'a>) -> Result<&'a [u8], Error> {
+    let len = read_size(reader)?;
+    reader.read_bytes(len).ok_or(Error::UnexpectedEof)
+}
+
+pub fn write_binary(writer: &mut EndianWriter, value: &[u8]) -> Option<()> {
+    write_size(writer, value.len())?;
+    writer.write_bytes(value)
+}
+
+/// Read an `i32` length-prefixed UTF-8 string.
+pub fn read_string<'a>(reader: &mut EndianReader<'a>) -> Result<&'a str, Error> {
+    core::str::from_utf8(read_binary(reader)?).map_err(|_| Error::InvalidUtf8)
+}
+
+pub fn write_string(writer: &mut EndianWriter, value: &str) -> Option<()> {
+    write_binary(writer, value.as_bytes())
+}
+
+/// # Field header
+/// The type and id preceding each field of a struct.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FieldHeader {
+    pub field_type: u8,
+    pub id: i16,
+}
+
+impl FieldHeader {
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write_u8(self.field_type)?;
+        writer.write(i16_be::from_native(self.id))
+    }
+}
+
+/// Read a field header, or `None` at the `STOP` marker ending a struct.
+pub fn read_field_header(reader: &mut EndianReader) -> Result<Option<FieldHeader>, Error> {
+    let field_type = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+    if field_type == STOP {
+        return Ok(None);
+    }
+    let id = read_i16(reader)?;
+    Ok(Some(FieldHeader { field_type, id }))
+}
+
+/// Write the `STOP` marker ending a struct.
+pub fn write_field_stop(writer: &mut EndianWriter) -> Option<()> {
+    writer.write_u8(STOP)
+}
+
+/// # List and set header
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ListHeader {
+    pub element_type: u8,
+    pub size: usize,
+}
+
+impl ListHeader {
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let element_type = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        let size = read_size(reader)?;
+        Ok(Self { element_type, size })
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write_u8(self.element_type)?;
+        write_size(writer, self.size)
+    }
+}
+
+/// # Map header
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MapHeader {
+    pub key_type: u8,
+    pub value_type: u8,
+    pub size: usize,
+}
+
+impl MapHeader {
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let key_type = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        let value_type = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        let size = read_size(reader)?;
+        Ok(Self {
+            key_type,
+            value_type,
+            size,
+        })
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write_u8(self.key_type)?;
+        writer.write_u8(self.value_type)?;
+        write_size(writer, self.size)
+    }
+}
+
+/// # Message header
+/// A strict (versioned) message header.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MessageHeader<'a> {
+    pub name: &'a str,
+    pub message_type: u8,
+    pub seq_id: i32,
+}
+
+impl<'a> MessageHeader<'a> {
+    pub fn read(reader: &mut EndianReader<'a>) -> Result<Self, Error> {
+        let version = read::<u32_be>(reader)?.to_native();
+        if version & VERSION_MASK != VERSION_1 {
+            return Err(Error::BadVersion(version));
+        }
+        let name = read_string(reader)?;
+        let seq_id = read_i32(reader)?;
+        Ok(Self {
+            name,
+            message_type: version as u8,
+            seq_id,
+        })
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write(u32_be::from_native(
+            VERSION_1 | u32::from(self.message_type),
+        ))?;
+        write_string(writer, self.name)?;
+        write_i32(writer, self.seq_id)
+    }
+}
@@ -0,0 +1,134 @@
+//! # TLS record and handshake headers
+//!
+//! The 5-byte record header (content type, big-endian `u16` version and length) and the 4-byte
+//! handshake message header (message type and big-endian 24-bit length), enough to find and
+//! classify handshake messages, e.g. to pull the SNI out of a ClientHello, without a TLS stack.
+//! Record headers are validated so that non-TLS traffic is rejected early.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::tls::*;
+//! let bytes = [0x16, 0x03, 0x01, 0x00, 0x2f, 0x01, 0x00, 0x00, 0x2b];
+//! let mut reader = EndianReader::new(&bytes);
+//! let record = RecordHeader::read(&mut reader).unwrap();
+//! assert!(record.content_type == CONTENT_HANDSHAKE && record.length == 0x2f);
+//! let handshake = HandshakeHeader::read(&mut reader).unwrap();
+//! assert!(handshake.msg_type == HANDSHAKE_CLIENT_HELLO && handshake.length == 0x2b);
+//!
+//! let mut reader = EndianReader::new(b"GET / HTTP/1.1");
+//! assert!(RecordHeader::read(&mut reader) == Err(Error::UnknownContentType(b'G')));
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::u16_be;
+
+pub const CONTENT_CHANGE_CIPHER_SPEC: u8 = 20;
+pub const CONTENT_ALERT: u8 = 21;
+pub const CONTENT_HANDSHAKE: u8 = 22;
+pub const CONTENT_APPLICATION_DATA: u8 = 23;
+pub const CONTENT_HEARTBEAT: u8 = 24;
+
+pub const HANDSHAKE_CLIENT_HELLO: u8 = 1;
+pub const HANDSHAKE_SERVER_HELLO: u8 = 2;
+pub const HANDSHAKE_NEW_SESSION_TICKET: u8 = 4;
+pub const HANDSHAKE_ENCRYPTED_EXTENSIONS: u8 = 8;
+pub const HANDSHAKE_CERTIFICATE: u8 = 11;
+pub const HANDSHAKE_SERVER_KEY_EXCHANGE: u8 = 12;
+pub const HANDSHAKE_CERTIFICATE_REQUEST: u8 = 13;
+pub const HANDSHAKE_SERVER_HELLO_DONE: u8 = 14;
+pub const HANDSHAKE_CERTIFICATE_VERIFY: u8 = 15;
+pub const HANDSHAKE_CLIENT_KEY_EXCHANGE: u8 = 16;
+pub const HANDSHAKE_FINISHED: u8 = 20;
+
+pub const VERSION_TLS10: u16 = 0x0301;
+pub const VERSION_TLS11: u16 = 0x0302;
+pub const VERSION_TLS12: u16 = 0x0303;
+pub const VERSION_TLS13: u16 = 0x0304;
+
+/// Longest record fragment: 2^14 bytes of plaintext plus the largest expansion TLS 1.2 allows.
+pub const MAX_RECORD_LEN: u16 = (1 << 14) + 2048;
+
+/// Largest handshake message length.
+pub const MAX_HANDSHAKE_LEN: u32 = (1 << 24) - 1;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a header.
+    UnexpectedEof,
+    /// The record content type is not one TLS defines.
+    UnknownContentType(u8),
+    /// The record version is not 3.x.
+    BadVersion(u16),
+    /// The record is longer than `MAX_RECORD_LEN`.
+    RecordTooLong(u16),
+}
+
+/// # Record header
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RecordHeader {
+    pub content_type: u8,
+    /// Legacy record version; `VERSION_TLS12` in TLS 1.3 records, and often `VERSION_TLS10` in
+    /// the first ClientHello.
+    pub version: u16,
+    /// Length of the fragment that follows.
+    pub length: u16,
+}
+
+impl RecordHeader {
+    pub const SIZE: usize = 5;
+
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let content_type = reader.read_u8().ok_or(Error::UnexpectedEof)?;
+        if !(CONTENT_CHANGE_CIPHER_SPEC..=CONTENT_HEARTBEAT).contains(&content_type) {
+            return Err(Error::UnknownContentType(content_type));
+        }
+        let version = reader.read::<u16_be>().ok_or(Error::UnexpectedEof)?;
+        let length = reader.read::<u16_be>().ok_or(Error::UnexpectedEof)?;
+        let header = Self {
+            content_type,
+            version: version.to_native(),
+            length: length.to_native(),
+        };
+        if header.version >> 8 != 3 {
+            return Err(Error::BadVersion(header.version));
+        }
+        if header.length > MAX_RECORD_LEN {
+            return Err(Error::RecordTooLong(header.length));
+        }
+        Ok(header)
+    }
+
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write_u8(self.content_type)?;
+        writer.write(u16_be::from_native(self.version))?;
+        writer.write(u16_be::from_native(self.length))
+    }
+}
+
+/// # Handshake message header
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct HandshakeHeader {
+    pub msg_type: u8,
+    /// Length of the message body, at most `MAX_HANDSHAKE_LEN`.
+    pub length: u32,
+}
+
+impl HandshakeHeader {
+    pub const SIZE: usize = 4;
+
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let [msg_type, length @ ..] = reader.read_array::<4>().ok_or(Error::UnexpectedEof)?;
+        let length = length.iter().fold(0, |acc, &b| acc << 8 | u32::from(b));
+        Ok(Self { msg_type, length })
+    }
+
+    /// `None` if the buffer is full or `length` does not fit in 24 bits.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        if self.length > MAX_HANDSHAKE_LEN {
+            return None;
+        }
+        let [_, length @ ..] = self.length.to_be_bytes();
+        writer.write_u8(self.msg_type)?;
+        writer.write_bytes(&length)
+    }
+}
@@ -0,0 +1,150 @@
+//! # WebSocket frame headers
+//!
+//! RFC 6455 frame headers: a byte with the FIN flag, three reserved bits and the opcode, then a
+//! byte with the MASK flag and a 7-bit payload length. Lengths of 126 and 127 announce a
+//! big-endian `u16` or `u64` length instead, and masked frames end the header with a 4-byte
+//! masking key.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::websocket::*;
+//! // A masked "Hello" text frame, as sent by a client.
+//! let mut frame = *b"\x81\x85\x37\xfa\x21\x3d\x7f\x9f\x4d\x51\x58";
+//! let mut reader = EndianReader::new(&frame);
+//! let header = FrameHeader::read(&mut reader).unwrap();
+//! assert!(header.fin && header.opcode == OPCODE_TEXT && header.payload_len == 5);
+//! let start = reader.position();
+//! apply_mask(header.mask.unwrap(), 0, &mut frame[start..]);
+//! assert!(&frame[start..] == b"Hello");
+//!
+//! let mut buf = [0u8; 16];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! FrameHeader::new(OPCODE_BINARY, 300).write(&mut writer).unwrap();
+//! assert!(writer.written() == [0x82, 0x7e, 0x01, 0x2c]);
+//! ```
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::{u16_be, u64_be};
+
+pub const OPCODE_CONTINUATION: u8 = 0x0;
+pub const OPCODE_TEXT: u8 = 0x1;
+pub const OPCODE_BINARY: u8 = 0x2;
+pub const OPCODE_CLOSE: u8 = 0x8;
+pub const OPCODE_PING: u8 = 0x9;
+pub const OPCODE_PONG: u8 = 0xa;
+
+/// Largest size of an encoded header.
+pub const MAX_HEADER_LEN: usize = 14;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of a header.
+    UnexpectedEof,
+    /// The payload length is not in its shortest form.
+    NonMinimalLength,
+    /// The most significant bit of a 64-bit payload length is set.
+    LengthTooLarge,
+}
+
+/// # Frame header
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// Whether this is the final fragment of a message.
+    pub fin: bool,
+    /// The RSV1 to RSV3 bits in the low three bits, used by extensions.
+    pub rsv: u8,
+    pub opcode: u8,
+    /// The masking key, present on frames sent by clients.
+    pub mask: Option<[u8; 4]>,
+    pub payload_len: u64,
+}
+
+impl FrameHeader {
+    /// An unmasked final frame.
+    pub const fn new(opcode: u8, payload_len: u64) -> Self {
+        Self {
+            fin: true,
+            rsv: 0,
+            opcode,
+            mask: None,
+            payload_len,
+        }
+    }
+
+    pub fn read(reader: &mut EndianReader) -> Result<Self, Error> {
+        let [first, second] = reader.read_array().ok_or(Error::UnexpectedEof)?;
+        let payload_len = match second & 0x7f {
+            126 => {
+                let len = reader.read::<u16_be>().ok_or(Error::UnexpectedEof)?;
+                let len = u64::from(len.to_native());
+                if len < 126 {
+                    return Err(Error::NonMinimalLength);
+                }
+                len
+            }
+            127 => {
+                let len = reader.read::<u64_be>().ok_or(Error::UnexpectedEof)?;
+                let len = len.to_native();
+                if len >> 63 != 0 {
+                    return Err(Error::LengthTooLarge);
+                }
+                if len <= u64::from(u16::MAX) {
+                    return Err(Error::NonMinimalLength);
+                }
+                len
+            }
+            len => u64::from(len),
+        };
+        let mask = match second & 0x80 {
+            0 => None,
+            _ => Some(reader.read_array().ok_or(Error::UnexpectedEof)?),
+        };
+        Ok(Self {
+            fin: first & 0x80 != 0,
+            rsv: (first >> 4) & 0x7,
+            opcode: first & 0xf,
+            mask,
+            payload_len,
+        })
+    }
+
+    /// Number of bytes `write` produces.
+    pub fn encoded_len(&self) -> usize {
+        let len = match self.payload_len {
+            0..=125 => 2,
+            126..=0xffff => 4,
+            _ => 10,
+        };
+        len + if self.mask.is_some() { 4 } else { 0 }
+    }
+
+    /// Write the header with the payload length in its shortest form.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        let first = u8::from(self.fin) << 7 | (self.rsv & 0x7) << 4 | (self.opcode & 0xf);
+        let masked = if self.mask.is_some() { 0x80 } else { 0 };
+        writer.write_u8(first)?;
+        match self.payload_len {
+            len @ 0..=125 => writer.write_u8(masked | len as u8)?,
+            len @ 126..=0xffff => {
+                writer.write_u8(masked | 126)?;
+                writer.write(u16_be::from_native(len as u16))?;
+            }
+            len => {
+                writer.write_u8(masked | 127)?;
+                writer.write(u64_be::from_native(len))?;
+            }
+        }
+        match self.mask {
+            Some(mask) => writer.write_bytes(&mask),
+            None => Some(()),
+        }
+    }
+}
+
+/// Mask or unmask `data` in place with `mask`, where `offset` is the position of `data` in the
+/// payload, so that a payload can be processed in pieces.
+pub fn apply_mask(mask: [u8; 4], offset: u64, data: &mut [u8]) {
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte ^= mask[((offset + i as u64) % 4) as usize];
+    }
+}
@@ -0,0 +1,173 @@
+//! # XDR
+//!
+//! External Data Representation (RFC 4506), the encoding of ONC RPC and NFS. Every item is a
+//! multiple of 4 bytes: integers are big-endian 32-bit (`int`, `unsigned int`, `bool`, `enum`)
+//! or 64-bit (`hyper`) words, and opaque data and strings are padded with zeros to the next
+//! 4-byte boundary. Variable-length opaque data, strings and arrays are prefixed with their
+//! length as an `unsigned int`; readers take the maximum length declared in the protocol (`~0`
+//! for unbounded) and reject longer items.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::proto::xdr::*;
+//! let mut buf = [0u8; 32];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! write_u32(&mut writer, 100003).unwrap();
+//! write_string(&mut writer, "nfs").unwrap();
+//! write_bool(&mut writer, true).unwrap();
+//! write_i64(&mut writer, -2).unwrap();
+//! assert!(writer.written()[4..12] == [0, 0, 0, 3, b'n', b'f', b's', 0]);
+//! let len = writer.position();
+//!
+//! let mut reader = EndianReader::new(&buf[..len]);
+//! assert!(read_u32(&mut reader) == Ok(100003));
+//! assert!(read_string(&mut reader, 255) == Ok("nfs"));
+//! assert!(read_bool(&mut reader) == Ok(true));
+//! assert!(read_i64(&mut reader) == Ok(-2));
+//! assert!(reader.is_empty());
+//! ```
+use crate::decoder::Decode;
+use crate::io::{EndianReader, EndianWriter};
+use crate::types::{i32_be, i64_be, u32_be, u64_be};
+use core::convert::TryFrom;
+
+/// Size of an XDR unit; every item is a multiple of it.
+pub const UNIT: usize = 4;
+
+/// # Decoding errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of an item.
+    UnexpectedEof,
+    /// A boolean is neither 0 nor 1.
+    InvalidBool(u32),
+    /// A length prefix is above the maximum allowed.
+    TooLong(u32),
+    /// Padding bytes are not zero.
+    NonZeroPadding,
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+}
+
+/// Number of padding bytes after `len` bytes of opaque data.
+pub const fn padding(len: usize) -> usize {
+    (UNIT - len % UNIT) % UNIT
+}
+
+fn read<T: Decode>(reader: &mut EndianReader) -> Result<T, Error> {
+    reader.read().ok_or(Error::UnexpectedEof)
+}
+
+pub fn read_u32(reader: &mut EndianReader) -> Result<u32, Error> {
+    read::<u32_be>(reader).map(|v| v.to_native())
+}
+
+pub fn read_i32(reader: &mut EndianReader) -> Result<i32, Error> {
+    read::<i32_be>(reader).map(|v| v.to_native())
+}
+
+/// Read an `unsigned hyper`.
+pub fn read_u64(reader: &mut EndianReader) -> Result<u64, Error> {
+    read::<u64_be>(reader).map(|v| v.to_native())
+}
+
+/// Read a `hyper`.
+pub fn read_i64(reader: &mut EndianReader) -> Result<i64, Error> {
+    read::<i64_be>(reader).map(|v| v.to_native())
+}
+
+pub fn read_bool(reader: &mut EndianReader) -> Result<bool, Error> {
+    match read_u32(reader)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        value => Err(Error::InvalidBool(value)),
+    }
+}
+
+pub fn read_f32(reader: &mut EndianReader) -> Result<f32, Error> {
+    read_u32(reader).map(f32::from_bits)
+}
+
+pub fn read_f64(reader: &mut EndianReader) -> Result<f64, Error> {
+    read_u64(reader).map(f64::from_bits)
+}
+
+/// Read `len` bytes of fixed-length opaque data and their padding.
+pub fn read_opaque_fixed<'a>(reader: &mut EndianReader<'a>, len: usize) -> Result<&'a [u8], Error> {
+    let data = reader.read_bytes(len).ok_or(Error::UnexpectedEof)?;
+    let pad = reader
+        .read_bytes(padding(len))
+        .ok_or(Error::UnexpectedEof)?;
+    if pad.iter().any(|&b| b != 0) {
+        return Err(Error::NonZeroPadding);
+    }
+    Ok(data)
+}
+
+/// Read the length of variable-length opaque data or a string, or the element count of a
+/// variable-length array, checking it against `max`.
+pub fn read_len(reader: &mut EndianReader, max: u32) -> Result<usize, Error> {
+    let len = read_u32(reader)?;
+    if len > max {
+        return Err(Error::TooLong(len));
+    }
+    Ok(len as usize)
+}
+
+/// Read variable-length opaque data of at most `max` bytes.
+pub fn read_opaque<'a>(reader: &mut EndianReader<'a>, max: u32) -> Result<&'a [u8], Error> {
+    let len = read_len(reader, max)?;
+    read_opaque_fixed(reader, len)
+}
+
+/// Read a string of at most `max` bytes.
+pub fn read_string<'a>(reader: &mut EndianReader<'a>, max: u32) -> Result<&'a str, Error> {
+    let bytes = read_opaque(reader, max)?;
+    core::str::from_utf8(bytes).map_err(|_| Error::InvalidUtf8)
+}
+
+pub fn write_u32(writer: &mut EndianWriter, value: u32) -> Option<()> {
+    writer.write(u32_be::from_native(value))
+}
+
+pub fn write_i32(writer: &mut EndianWriter, value: i32) -> Option<()> {
+    writer.write(i32_be::from_native(value))
+}
+
+/// Write an `unsigned hyper`.
+pub fn write_u64(writer: &mut EndianWriter, value: u64) -> Option<()> {
+    writer.write(u64_be::from_native(value))
+}
+
+/// Write a `hyper`.
+pub fn write_i64(writer: &mut EndianWriter, value: i64) -> Option<()> {
+    writer.write(i64_be::from_native(value))
+}
+
+pub fn write_bool(writer: &mut EndianWriter, value: bool) -> Option<()> {
+    write_u32(writer, u32::from(value))
+}
+
+pub fn write_f32(writer: &mut EndianWriter, value: f32) -> Option<()> {
+    write_u32(writer, value.to_bits())
+}
+
+pub fn write_f64(writer: &mut EndianWriter, value: f64) -> Option<()> {
+    write_u64(writer, value.to_bits())
+}
+
+/// Write fixed-length opaque data and its padding.
+pub fn write_opaque_fixed(writer: &mut EndianWriter, data: &[u8]) -> Option<()> {
+    writer.write_bytes(data)?;
+    writer.write_bytes(&[0; UNIT][..padding(data.len())])
+}
+
+/// Write variable-length opaque data with its length; `None` if it is longer than `u32::MAX`.
+pub fn write_opaque(writer: &mut EndianWriter, data: &[u8]) -> Option<()> {
+    write_u32(writer, u32::try_from(data.len()).ok()?)?;
+    write_opaque_fixed(writer, data)
+}
+
+pub fn write_string(writer: &mut EndianWriter, value: &str) -> Option<()> {
+    write_opaque(writer, value.as_bytes())
+}
@@ -0,0 +1,154 @@
+//! # Length-prefixed strings
+//!
+//! `PString<L>` is a UTF-8 string view preceded on the wire by its byte length as the endian
+//! integer `L`, the "`u16` big-endian length + UTF-8 bytes" pattern of many protocols and game
+//! formats (`PString<u16_be>`), or Pascal strings with a one-byte length (`PString<u8_le>`).
+//! Class files use a modified UTF-8 that only differs for NUL and supplementary characters; such
+//! strings are rejected by `read`.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::pstring::*;
+//! use endiantype::*;
+//!
+//! let mut buf = [0u8; 16];
+//! let mut writer = EndianWriter::new(&mut buf);
+//! PString::<u16_be>::new("héllo").write(&mut writer).unwrap();
+//! assert!(writer.written() == b"\x00\x06h\xc3\xa9llo");
+//!
+//! let mut reader = EndianReader::new(&buf);
+//! let name = PString::<u16_be>::read(&mut reader).unwrap();
+//! assert!(name.as_str() == "héllo" && name.to_string() == "héllo");
+//!
+//! let mut reader = EndianReader::new(b"\x03ab");
+//! assert!(PString::<u8_le>::read(&mut reader) == Err(Error::UnexpectedEof));
+//! let long = "x".repeat(256);
+//! assert!(PString::<u8_le>::new(&long).write(&mut EndianWriter::new(&mut [0; 300])).is_none());
+//! ```
+use crate::decoder::Decode;
+use crate::io::{Encode, EndianReader, EndianWriter};
+use crate::{BigEndian, LittleEndian};
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// # Parsing errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer ended in the middle of the length or the string.
+    UnexpectedEof,
+    /// The string is not valid UTF-8.
+    InvalidUtf8,
+}
+
+/// # Length prefix
+/// Unsigned endian integers usable as the length of a `PString`.
+pub trait LengthPrefix: Decode + Encode {
+    /// The length, or `None` if it does not fit in a `usize`.
+    fn to_len(self) -> Option<usize>;
+
+    /// The prefix for `len`, or `None` if it does not fit.
+    fn from_len(len: usize) -> Option<Self>;
+}
+
+macro_rules! impl_length_prefix {
+    ($($type_name: ident),*) => {
+        $(
+            impl LengthPrefix for LittleEndian<$type_name> {
+                #[inline]
+                fn to_len(self) -> Option<usize> {
+                    usize::try_from(self.to_native()).ok()
+                }
+
+                #[inline]
+                fn from_len(len: usize) -> Option<Self> {
+                    $type_name::try_from(len).ok().map(Self::from_native)
+                }
+            }
+
+            impl LengthPrefix for BigEndian<$type_name> {
+                #[inline]
+                fn to_len(self) -> Option<usize> {
+                    usize::try_from(self.to_native()).ok()
+                }
+
+                #[inline]
+                fn from_len(len: usize) -> Option<Self> {
+                    $type_name::try_from(len).ok().map(Self::from_native)
+                }
+            }
+        )*
+    };
+}
+
+impl_length_prefix!(u8, u16, u32, u64);
+
+/// # Length-prefixed string
+pub struct PString<'a, L> {
+    value: &'a str,
+    prefix: PhantomData<L>,
+}
+
+impl<'a, L: LengthPrefix> PString<'a, L> {
+    pub fn new(value: &'a str) -> Self {
+        Self {
+            value,
+            prefix: PhantomData,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.value
+    }
+
+    /// Read the length and the string it announces.
+    pub fn read(reader: &mut EndianReader<'a>) -> Result<Self, Error> {
+        let len = reader.read::<L>().ok_or(Error::UnexpectedEof)?;
+        let bytes = len
+            .to_len()
+            .and_then(|len| reader.read_bytes(len))
+            .ok_or(Error::UnexpectedEof)?;
+        let value = core::str::from_utf8(bytes).map_err(|_| Error::InvalidUtf8)?;
+        Ok(Self::new(value))
+    }
+
+    /// Number of bytes `write` produces.
+    pub fn encoded_len(&self) -> usize {
+        <L as Encode>::SIZE + self.value.len()
+    }
+
+    /// Write the length and the string, or `None` if the buffer is full or the length does not
+    /// fit in `L`.
+    pub fn write(&self, writer: &mut EndianWriter) -> Option<()> {
+        writer.write(L::from_len(self.value.len())?)?;
+        writer.write_bytes(self.value.as_bytes())
+    }
+}
+
+impl<L> Clone for PString<'_, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L> Copy for PString<'_, L> {}
+
+impl<L> PartialEq for PString<'_, L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<L> Eq for PString<'_, L> {}
+
+impl<L> fmt::Debug for PString<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.value, f)
+    }
+}
+
+impl<L> fmt::Display for PString<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.value)
+    }
+}
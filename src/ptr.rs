@@ -0,0 +1,166 @@
+//! # On-disk pointers
+//!
+//! File formats link their structures with offsets stored in a fixed byte order and width, e.g.
+//! a big-endian `u32` offset from the start of the image. `EndianPtr<T, O, W>` is such an offset
+//! to a `T`, stored as a `W` in byte order `O`; `resolve` bounds-checks it against the image and
+//! decodes the `T` it points to. It implements `Wire`, so it can be a field of an
+//! `endian_struct!`.
+//! ## Example
+//! ```
+//! use endiantype::prelude::*;
+//! use endiantype::ptr::*;
+//! use endiantype::*;
+//!
+//! endian_struct! {
+//!     pub struct Entry {
+//!         pub id: u16_be,
+//!         pub next: EndianPtr<Entry, BE, u32>,
+//!     }
+//! }
+//!
+//! let image = [
+//!     0, 1, 0, 0, 0, 6, // entry 1 at offset 0, next at 6
+//!     0, 2, 0, 0, 0, 0, // entry 2 at offset 6, end of the list
+//!     0, 3, 0, 0, 0, 64, // entry 3 at offset 12, pointing outside the image
+//! ];
+//! let mut entry = EndianPtr::<Entry, BE, u32>::new(0).resolve(&image).unwrap();
+//! let mut ids = vec![entry.id.to_native()];
+//! while !entry.next.is_null() {
+//!     entry = entry.next.resolve(&image).unwrap();
+//!     ids.push(entry.id.to_native());
+//! }
+//! assert!(ids == [1, 2]);
+//! let broken = EndianPtr::<Entry, BE, u32>::new(12).resolve(&image).unwrap();
+//! assert!(broken.next.resolve(&image) == Err(Error::OutOfBounds(64)));
+//! ```
+use crate::byteorder::ByteOrder;
+use crate::decoder::Decode;
+use crate::endianness::Endianness;
+use crate::io::Encode;
+use crate::wire::Wire;
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// # Resolution errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The `T` at this offset does not fit in the image.
+    OutOfBounds(u64),
+}
+
+/// # On-disk pointer
+/// An offset to a `T`, stored as a `W` in byte order `O`.
+#[repr(transparent)]
+pub struct EndianPtr<T, O: ByteOrder, W> {
+    offset: O::Endian<W>,
+    target: PhantomData<fn() -> T>,
+}
+
+impl<T, O, W> EndianPtr<T, O, W>
+where
+    O: ByteOrder,
+    W: Copy + Into<u64>,
+    O::Endian<W>: Copy + From<W> + Into<W>,
+{
+    pub fn new(offset: W) -> Self {
+        Self::from_endian(O::Endian::<W>::from(offset))
+    }
+
+    pub const fn from_endian(offset: O::Endian<W>) -> Self {
+        Self {
+            offset,
+            target: PhantomData,
+        }
+    }
+
+    pub fn offset(&self) -> u64 {
+        Into::<W>::into(self.offset).into()
+    }
+
+    /// Whether the offset is 0, which many formats use for "none".
+    pub fn is_null(&self) -> bool {
+        self.offset() == 0
+    }
+
+    /// The `len` bytes at the offset.
+    pub fn resolve_bytes<'a>(&self, image: &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+        let offset = self.offset();
+        usize::try_from(offset)
+            .ok()
+            .and_then(|start| image.get(start..start.checked_add(len)?))
+            .ok_or(Error::OutOfBounds(offset))
+    }
+
+    /// Decode the `T` at the offset.
+    pub fn resolve(&self, image: &[u8]) -> Result<T, Error>
+    where
+        T: Decode,
+    {
+        self.resolve_bytes(image, T::SIZE).map(T::decode)
+    }
+}
+
+impl<T, O: ByteOrder, W> Clone for EndianPtr<T, O, W>
+where
+    O::Endian<W>: Copy,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, O: ByteOrder, W> Copy for EndianPtr<T, O, W> where O::Endian<W>: Copy {}
+
+impl<T, O: ByteOrder, W> PartialEq for EndianPtr<T, O, W>
+where
+    O::Endian<W>: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<T, O: ByteOrder, W> fmt::Debug for EndianPtr<T, O, W>
+where
+    O::Endian<W>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("EndianPtr").field(&self.offset).finish()
+    }
+}
+
+impl<T, O: ByteOrder, W> Decode for EndianPtr<T, O, W>
+where
+    O::Endian<W>: Decode,
+{
+    const SIZE: usize = <O::Endian<W> as Decode>::SIZE;
+
+    #[inline]
+    fn decode(bytes: &[u8]) -> Self {
+        Self {
+            offset: Decode::decode(bytes),
+            target: PhantomData,
+        }
+    }
+}
+
+impl<T, O: ByteOrder, W> Encode for EndianPtr<T, O, W>
+where
+    O::Endian<W>: Encode,
+{
+    const SIZE: usize = <O::Endian<W> as Encode>::SIZE;
+
+    #[inline]
+    fn encode(&self, bytes: &mut [u8]) {
+        self.offset.encode(bytes)
+    }
+}
+
+impl<T, O: ByteOrder, W> Wire for EndianPtr<T, O, W>
+where
+    O::Endian<W>: Wire,
+{
+    const ENDIANNESS: Option<Endianness> = <O::Endian<W> as Wire>::ENDIANNESS;
+    const C_TYPE: &'static str = <O::Endian<W> as Wire>::C_TYPE;
+}
@@ -0,0 +1,374 @@
+//! # Python struct formats
+//!
+//! Reads and writes values described by a Python `struct` format string such as `">IHH4s"`, so
+//! binary-parsing scripts can be ported without rewriting their formats. The first character may
+//! pick the byte order: `<` little endian, `>` or `!` big endian, `=` native, and `@` (the
+//! default) native with native sizes and alignment. Every code of the `struct` module is supported
+//! except the half float `e`.
+//!
+//! Values come out as `Value`s: signed codes as `Int`, unsigned ones as `Uint`, `c`, `s` and `p`
+//! as `Bytes`, and floats as `Float`.
+//! ## Example
+//! ```
+//! use endiantype::io::*;
+//! use endiantype::pystruct::*;
+//! let format = Format::parse(">IHH4s").unwrap();
+//! assert!(format.size() == 12 && format.len() == 4);
+//!
+//! let data = b"\x00\x00\x00\x2a\x00\x01\xff\xffabcd";
+//! let mut reader = EndianReader::new(data);
+//! let values: Vec<Value> = format.unpack(&mut reader).unwrap().collect();
+//! assert!(values[0] == Value::Uint(42) && values[2] == Value::Uint(0xffff));
+//! assert!(values[3] == Value::Bytes(b"abcd"));
+//!
+//! let mut buf = [0u8; 12];
+//! format.pack(&mut EndianWriter::new(&mut buf), &values).unwrap();
+//! assert!(&buf == data);
+//! // Values are range checked against their code.
+//! let format = Format::parse("<bh").unwrap();
+//! let result = format.pack(&mut EndianWriter::new(&mut buf), &[Value::Int(-1), Value::Uint(40000)]);
+//! assert!(result == Err(Error::OutOfRange));
+//! ```
+use crate::endianness::Endianness;
+use crate::io::{EndianReader, EndianWriter};
+use core::convert::TryFrom;
+use core::ffi::c_long;
+use core::mem::size_of;
+
+/// # Format errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The format string contains an unknown or unsupported code.
+    InvalidCode(char),
+    /// A repeat count is not followed by a code, or is too large.
+    InvalidCount,
+    /// The buffer ended before all values were read.
+    UnexpectedEof,
+    /// The buffer is too small for the packed values.
+    BufferTooSmall,
+    /// The number of values given to `pack` does not match the format.
+    WrongCount,
+    /// A value does not have the kind its code requires.
+    TypeMismatch,
+    /// An integer does not fit its code, or a byte string is too long.
+    OutOfRange,
+}
+
+/// # Value
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    Int(i64),
+    Uint(u64),
+    Bool(bool),
+    Float(f64),
+    Bytes(&'a [u8]),
+}
+
+/// # Format
+/// A validated format string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Format<'a> {
+    items: &'a [u8],
+    endianness: Endianness,
+    native: bool,
+    size: usize,
+    len: usize,
+}
+
+impl<'a> Format<'a> {
+    pub fn parse(format: &'a str) -> Result<Self, Error> {
+        let bytes = format.as_bytes();
+        let (endianness, native, items) = match bytes.first() {
+            Some(b'<') => (Endianness::Little, false, &bytes[1..]),
+            Some(b'>') | Some(b'!') => (Endianness::Big, false, &bytes[1..]),
+            Some(b'=') => (Endianness::NATIVE, false, &bytes[1..]),
+            Some(b'@') => (Endianness::NATIVE, true, &bytes[1..]),
+            _ => (Endianness::NATIVE, true, bytes),
+        };
+        let mut format = Self {
+            items,
+            endianness,
+            native,
+            size: 0,
+            len: 0,
+        };
+        for item in (Items { rest: items }) {
+            let (count, code) = item?;
+            let size = format.code_size(code)?;
+            format.size = format.align(format.size, code);
+            format.size = count
+                .checked_mul(size)
+                .and_then(|len| format.size.checked_add(len))
+                .ok_or(Error::InvalidCount)?;
+            format.len += match code {
+                b'x' => 0,
+                b's' | b'p' => 1,
+                _ => count,
+            };
+        }
+        Ok(format)
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Number of bytes the format reads or writes, like Python's `struct.calcsize`.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of values the format reads or writes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Take `size()` bytes from `reader` and iterate over the values in them.
+    pub fn unpack<'b>(&self, reader: &mut EndianReader<'b>) -> Result<Values<'a, 'b>, Error> {
+        let bytes = reader.read_bytes(self.size).ok_or(Error::UnexpectedEof)?;
+        Ok(Values {
+            format: *self,
+            items: Items { rest: self.items },
+            reader: EndianReader::new(bytes),
+            code: 0,
+            repeat: 0,
+        })
+    }
+
+    /// Write `values` to `writer`; padding bytes are written as zeros.
+    pub fn pack(&self, writer: &mut EndianWriter, values: &[Value]) -> Result<(), Error> {
+        if values.len() != self.len {
+            return Err(Error::WrongCount);
+        }
+        let out = writer.reserve(self.size).ok_or(Error::BufferTooSmall)?;
+        let mut out = EndianWriter::new(out);
+        let mut values = values.iter();
+        let mut items = Items { rest: self.items };
+        while let Some(Ok((count, code))) = items.next() {
+            let aligned = self.align(out.position(), code);
+            out.reserve(aligned - out.position()).unwrap().fill(0);
+            match code {
+                b'x' => out.reserve(count).unwrap().fill(0),
+                b's' | b'p' => self.pack_bytes(&mut out, count, code, values.next().unwrap())?,
+                _ => {
+                    for value in values.by_ref().take(count) {
+                        self.pack_value(&mut out, code, value)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn code_size(&self, code: u8) -> Result<usize, Error> {
+        Ok(match code {
+            b'x' | b'c' | b'b' | b'B' | b'?' | b's' | b'p' => 1,
+            b'h' | b'H' => 2,
+            b'i' | b'I' | b'f' => 4,
+            b'l' | b'L' if self.native => size_of::<c_long>(),
+            b'l' | b'L' => 4,
+            b'q' | b'Q' | b'd' => 8,
+            b'n' | b'N' | b'P' if self.native => size_of::<usize>(),
+            _ => return Err(Error::InvalidCode(char::from(code))),
+        })
+    }
+
+    /// Offset of the next `code` item at or after `offset`; only native formats are aligned.
+    fn align(&self, offset: usize, code: u8) -> usize {
+        match code {
+            _ if !self.native => offset,
+            b'x' | b's' | b'p' => offset,
+            _ => {
+                let align = self.code_size(code).unwrap();
+                offset.div_ceil(align) * align
+            }
+        }
+    }
+
+    fn read_uint(&self, bytes: &[u8]) -> u64 {
+        match self.endianness {
+            Endianness::Little => bytes
+                .iter()
+                .rev()
+                .fold(0, |acc, &b| acc << 8 | u64::from(b)),
+            Endianness::Big => bytes.iter().fold(0, |acc, &b| acc << 8 | u64::from(b)),
+        }
+    }
+
+    fn write_uint(&self, bytes: &mut [u8], value: u64) {
+        let len = bytes.len();
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let shift = match self.endianness {
+                Endianness::Little => i,
+                Endianness::Big => len - 1 - i,
+            };
+            *byte = (value >> (shift * 8)) as u8;
+        }
+    }
+
+    fn pack_bytes(
+        &self,
+        out: &mut EndianWriter,
+        count: usize,
+        code: u8,
+        value: &Value,
+    ) -> Result<(), Error> {
+        let bytes = match value {
+            Value::Bytes(bytes) => bytes,
+            _ => return Err(Error::TypeMismatch),
+        };
+        let field = out.reserve(count).unwrap();
+        field.fill(0);
+        let data = match code {
+            // Pascal strings start with their length, which is at most 255.
+            b'p' if count > 0 => {
+                let max = (count - 1).min(255);
+                if bytes.len() > max {
+                    return Err(Error::OutOfRange);
+                }
+                field[0] = bytes.len() as u8;
+                &mut field[1..]
+            }
+            _ => field,
+        };
+        if bytes.len() > data.len() {
+            return Err(Error::OutOfRange);
+        }
+        data[..bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn pack_value(&self, out: &mut EndianWriter, code: u8, value: &Value) -> Result<(), Error> {
+        let size = self.code_size(code).unwrap();
+        let bits = match (code, *value) {
+            (b'c', Value::Bytes(&[byte])) => u64::from(byte),
+            (b'?', Value::Bool(value)) => u64::from(value),
+            (b'f', Value::Float(value)) => u64::from((value as f32).to_bits()),
+            (b'd', Value::Float(value)) => value.to_bits(),
+            (b'b' | b'h' | b'i' | b'l' | b'q' | b'n', value) => {
+                let value = match value {
+                    Value::Int(value) => value,
+                    Value::Uint(value) => i64::try_from(value).map_err(|_| Error::OutOfRange)?,
+                    _ => return Err(Error::TypeMismatch),
+                };
+                let bits = size as u32 * 8;
+                let min = i64::MIN >> (64 - bits);
+                if value < min || value > !min {
+                    return Err(Error::OutOfRange);
+                }
+                value as u64
+            }
+            (b'B' | b'H' | b'I' | b'L' | b'Q' | b'N' | b'P', value) => {
+                let value = match value {
+                    Value::Uint(value) => value,
+                    Value::Int(value) => u64::try_from(value).map_err(|_| Error::OutOfRange)?,
+                    _ => return Err(Error::TypeMismatch),
+                };
+                if size < 8 && value >> (size * 8) != 0 {
+                    return Err(Error::OutOfRange);
+                }
+                value
+            }
+            _ => return Err(Error::TypeMismatch),
+        };
+        self.write_uint(out.reserve(size).unwrap(), bits);
+        Ok(())
+    }
+}
+
+/// Yields the `(count, code)` items of a format string, skipping whitespace.
+#[derive(Clone, Debug)]
+struct Items<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for Items<'a> {
+    type Item = Result<(usize, u8), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((&first, rest)) = self.rest.split_first() {
+            if !first.is_ascii_whitespace() {
+                break;
+            }
+            self.rest = rest;
+        }
+        if self.rest.is_empty() {
+            return None;
+        }
+        let digits = self.rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        let count = match digits {
+            0 => Some(1),
+            _ => core::str::from_utf8(&self.rest[..digits])
+                .unwrap()
+                .parse()
+                .ok(),
+        };
+        let code = self.rest.get(digits).copied();
+        self.rest = self.rest.get(digits + 1..).unwrap_or(&[]);
+        match (count, code) {
+            (Some(count), Some(code)) => Some(Ok((count, code))),
+            _ => {
+                self.rest = &[];
+                Some(Err(Error::InvalidCount))
+            }
+        }
+    }
+}
+
+/// # Value iterator
+/// Yields the values read by `Format::unpack`.
+#[derive(Clone, Debug)]
+pub struct Values<'a, 'b> {
+    format: Format<'a>,
+    items: Items<'a>,
+    reader: EndianReader<'b>,
+    code: u8,
+    repeat: usize,
+}
+
+impl<'a, 'b> Iterator for Values<'a, 'b> {
+    type Item = Value<'b>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let format = self.format;
+        while self.repeat == 0 {
+            let (count, code) = self.items.next()?.unwrap();
+            let aligned = format.align(self.reader.position(), code);
+            self.reader.skip(aligned - self.reader.position()).unwrap();
+            match code {
+                b'x' => self.reader.skip(count).unwrap(),
+                b's' => return self.reader.read_bytes(count).map(Value::Bytes),
+                b'p' => {
+                    let field = self.reader.read_bytes(count).unwrap();
+                    let data = field.get(1..).unwrap_or(&[]);
+                    let len = field.first().map_or(0, |&len| usize::from(len));
+                    return Some(Value::Bytes(&data[..len.min(data.len())]));
+                }
+                _ => {
+                    self.code = code;
+                    self.repeat = count;
+                }
+            }
+        }
+        self.repeat -= 1;
+        let size = format.code_size(self.code).unwrap();
+        let bytes = self.reader.read_bytes(size).unwrap();
+        let bits = format.read_uint(bytes);
+        Some(match self.code {
+            b'c' => Value::Bytes(bytes),
+            b'?' => Value::Bool(bits != 0),
+            b'f' => Value::Float(f64::from(f32::from_bits(bits as u32))),
+            b'd' => Value::Float(f64::from_bits(bits)),
+            b'b' | b'h' | b'i' | b'l' | b'q' | b'n' => {
+                // Sign-extend from the top bit of the field.
+                let shift = 64 - size as u32 * 8;
+                Value::Int((bits << shift) as i64 >> shift)
+            }
+            _ => Value::Uint(bits),
+        })
+    }
+}
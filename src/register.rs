@@ -0,0 +1,272 @@
+//! # Foreign-endian peripheral registers
+//!
+//! Some peripherals are documented as big endian but sit on little-endian cores (or the other way
+//! round), so the words a PAC reads from them are in the device's byte order. `EndianRegister`
+//! exposes such a register through an endian type: reads wrap the raw word with `new` and writes
+//! store it with `into_inner`, so the only byte swap happens when the value is converted to or
+//! from native.
+//!
+//! `endian_register!` implements the trait for register types generated by `svd2rust`, which
+//! provide `read().bits()`, `write(|w| w.bits(..))` and `modify`. `Field` gives typed access to a
+//! bit range of such a register.
+//! ## Example
+//! ```
+//! use core::cell::Cell;
+//! use endiantype::register::EndianRegister;
+//! use endiantype::*;
+//!
+//! // A stand-in for an svd2rust register with its reader and writer proxies.
+//! pub struct R(u32);
+//! impl R {
+//!     pub fn bits(&self) -> u32 { self.0 }
+//! }
+//! pub struct W(u32);
+//! impl W {
+//!     pub unsafe fn bits(&mut self, bits: u32) -> &mut Self { self.0 = bits; self }
+//! }
+//! pub struct CTRL(Cell<u32>);
+//! impl CTRL {
+//!     pub fn read(&self) -> R { R(self.0.get()) }
+//!     pub fn write<F: FnOnce(&mut W) -> &mut W>(&self, f: F) {
+//!         let mut w = W(0);
+//!         self.0.set(f(&mut w).0);
+//!     }
+//!     pub fn modify<F: for<'w> FnOnce(&R, &'w mut W) -> &'w mut W>(&self, f: F) {
+//!         let r = self.read();
+//!         let mut w = W(r.0);
+//!         self.0.set(f(&r, &mut w).0);
+//!     }
+//! }
+//!
+//! // SAFETY: every bit pattern of CTRL is valid to write.
+//! endian_register!(unsafe CTRL: u32_be);
+//!
+//! // The device stores 0x12345678 most significant byte first.
+//! let ctrl = CTRL(Cell::new(u32::from_ne_bytes([0x12, 0x34, 0x56, 0x78])));
+//! assert!(ctrl.read_value() == 0x1234_5678);
+//! ctrl.modify_value(|v| v | 0x80);
+//! assert!(ctrl.0.get().to_ne_bytes() == [0x12, 0x34, 0x56, 0xf8]);
+//! ```
+use crate::{BigEndian, LittleEndian};
+use core::marker::PhantomData;
+
+/// # Endian register
+/// A register whose contents are read and written as the endian type `Value`.
+pub trait EndianRegister {
+    type Value: Copy;
+
+    fn read_value(&self) -> Self::Value;
+
+    fn write_value(&self, value: Self::Value);
+
+    /// Read the register, pass the value through `f` and write back the result.
+    fn modify_value<F: FnOnce(Self::Value) -> Self::Value>(&self, f: F) {
+        self.write_value(f(self.read_value()))
+    }
+}
+
+/// # Register adapter declarations
+/// Implements `EndianRegister` for `svd2rust` register types, written as
+/// `unsafe Register: value_type`, e.g. `unsafe pac::eth::CTRL: u32_be`.
+///
+/// # Safety
+/// Writes go through `W::bits`, which `svd2rust` marks unsafe for registers where some bit
+/// patterns must not be written. Each register is prefixed with `unsafe` to assert that every bit
+/// pattern of its word is valid to write, since the generated methods are safe to call.
+/// ## Example
+/// Declaring a register without `unsafe` fails to compile:
+/// ```compile_fail
+/// use endiantype::*;
+/// # pub struct R(u32);
+/// # impl R { pub fn bits(&self) -> u32 { self.0 } }
+/// # pub struct W(u32);
+/// # impl W { pub unsafe fn bits(&mut self, bits: u32) -> &mut Self { self.0 = bits; self } }
+/// # pub struct CTRL;
+/// # impl CTRL {
+/// #     pub fn read(&self) -> R { R(0) }
+/// #     pub fn write<F: FnOnce(&mut W) -> &mut W>(&self, f: F) { f(&mut W(0)); }
+/// #     pub fn modify<F: for<'w> FnOnce(&R, &'w mut W) -> &'w mut W>(&self, f: F) {
+/// #         f(&R(0), &mut W(0));
+/// #     }
+/// # }
+/// endian_register!(CTRL: u32_be);
+/// ```
+#[macro_export]
+macro_rules! endian_register {
+    ($(unsafe $register: ty: $value: ty),* $(,)?) => {
+        $(
+            impl $crate::register::EndianRegister for $register {
+                type Value = $value;
+
+                #[inline]
+                fn read_value(&self) -> Self::Value {
+                    <$value>::new(self.read().bits())
+                }
+
+                #[inline]
+                #[allow(unused_unsafe)]
+                fn write_value(&self, value: Self::Value) {
+                    self.write(|w| unsafe { w.bits(value.into_inner()) })
+                }
+
+                #[inline]
+                #[allow(unused_unsafe)]
+                fn modify_value<F: FnOnce(Self::Value) -> Self::Value>(&self, f: F) {
+                    self.modify(|r, w| unsafe { w.bits(f(<$value>::new(r.bits())).into_inner()) })
+                }
+            }
+        )*
+    };
+}
+
+/// # Register word
+/// Endian unsigned integers whose bit ranges can be accessed through `Field`.
+pub trait FieldWord: Copy {
+    const BITS: u32;
+
+    fn to_bits(self) -> u128;
+
+    fn from_bits(bits: u128) -> Self;
+}
+
+/// # Field value
+/// Types a `Field` can be read as: unsigned integers and `bool`.
+pub trait FieldValue: Copy {
+    fn from_bits(bits: u128) -> Self;
+
+    fn to_bits(self) -> u128;
+}
+
+macro_rules! impl_field_word {
+    ($($type_name: ident),*) => {
+        $(
+            impl FieldWord for LittleEndian<$type_name> {
+                const BITS: u32 = $type_name::BITS;
+
+                #[inline]
+                fn to_bits(self) -> u128 {
+                    self.to_native() as u128
+                }
+
+                #[inline]
+                fn from_bits(bits: u128) -> Self {
+                    Self::from_native(bits as $type_name)
+                }
+            }
+
+            impl FieldWord for BigEndian<$type_name> {
+                const BITS: u32 = $type_name::BITS;
+
+                #[inline]
+                fn to_bits(self) -> u128 {
+                    self.to_native() as u128
+                }
+
+                #[inline]
+                fn from_bits(bits: u128) -> Self {
+                    Self::from_native(bits as $type_name)
+                }
+            }
+
+            impl FieldValue for $type_name {
+                #[inline]
+                fn from_bits(bits: u128) -> Self {
+                    bits as $type_name
+                }
+
+                #[inline]
+                fn to_bits(self) -> u128 {
+                    self as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_field_word!(u8, u16, u32, u64, u128, usize);
+
+impl FieldValue for bool {
+    #[inline]
+    fn from_bits(bits: u128) -> Self {
+        bits != 0
+    }
+
+    #[inline]
+    fn to_bits(self) -> u128 {
+        self as u128
+    }
+}
+
+/// # Register field
+/// The bits `MSB:LSB` (inclusive, bit 0 least significant, as in register manuals) of an endian
+/// register word, read as a `T`. Fields are zero-sized types, so a register's layout can be
+/// written down as type aliases. Using a field with a word it does not fit in, or with `MSB`
+/// below `LSB`, fails to compile.
+/// ## Example
+/// ```
+/// use endiantype::register::Field;
+/// use endiantype::*;
+///
+/// type Enable = Field<0, 0, bool>;
+/// type Prescaler = Field<11, 4, u8>;
+///
+/// let mut ctrl = u32_be::from_native(0x0000_0a01);
+/// assert!(Enable::get(ctrl) && Prescaler::get(ctrl) == 0xa0);
+/// Prescaler::set(&mut ctrl, 0x3c);
+/// Enable::set(&mut ctrl, false);
+/// assert!(ctrl == 0x0000_03c0);
+/// ```
+pub struct Field<const MSB: u32, const LSB: u32, T>(PhantomData<T>);
+
+struct FieldRange<W, const MSB: u32, const LSB: u32>(PhantomData<W>);
+
+impl<W: FieldWord, const MSB: u32, const LSB: u32> FieldRange<W, MSB, LSB> {
+    const MASK: u128 = {
+        assert!(
+            LSB <= MSB && MSB < W::BITS,
+            "field does not fit in the register"
+        );
+        (!0 >> (127 - (MSB - LSB))) << LSB
+    };
+}
+
+impl<const MSB: u32, const LSB: u32, T: FieldValue> Field<MSB, LSB, T> {
+    /// Width of the field in bits.
+    pub const WIDTH: u32 = MSB - LSB + 1;
+
+    #[inline]
+    pub fn get<W: FieldWord>(word: W) -> T {
+        T::from_bits((word.to_bits() & FieldRange::<W, MSB, LSB>::MASK) >> LSB)
+    }
+
+    /// Replace the field with the low bits of `value`.
+    #[inline]
+    pub fn set<W: FieldWord>(word: &mut W, value: T) {
+        *word = Self::with(*word, value);
+    }
+
+    /// `word` with the field replaced by the low bits of `value`.
+    #[inline]
+    pub fn with<W: FieldWord>(word: W, value: T) -> W {
+        let mask = FieldRange::<W, MSB, LSB>::MASK;
+        W::from_bits(word.to_bits() & !mask | (value.to_bits() << LSB) & mask)
+    }
+
+    /// Read the field from a register.
+    #[inline]
+    pub fn read<R: EndianRegister>(register: &R) -> T
+    where
+        R::Value: FieldWord,
+    {
+        Self::get(register.read_value())
+    }
+
+    /// Replace the field in a register, leaving the other bits as they are.
+    #[inline]
+    pub fn modify<R: EndianRegister>(register: &R, value: T)
+    where
+        R::Value: FieldWord,
+    {
+        register.modify_value(|word| Self::with(word, value))
+    }
+}
@@ -0,0 +1,164 @@
+//! # Descriptor rings
+//!
+//! NIC and DMA engine rings are fixed-size arrays of descriptors, often laid out in the device's
+//! byte order, with a producer index and a consumer index. `DescriptorRing` keeps the array and
+//! both indices together and accesses the descriptors with volatile reads and writes.
+//!
+//! The ring does not talk to the device itself. After `publish`, the caller tells the device about
+//! the new descriptors by writing `producer_index` to its doorbell (tail) register, and must
+//! first issue the platform's DMA write barrier (`dma_wmb` in Linux, `dmb oshst` on AArch64, a
+//! compiler fence on x86 with cache-coherent DMA) so the descriptor reaches memory before the
+//! doorbell does. Likewise, before `consume` reads a descriptor the device has completed, as
+//! reported by a status bit or head register, the caller must issue the DMA read barrier
+//! (`dma_rmb`). `core::sync::atomic` fences only order memory between CPUs and are not enough.
+//! ## Example
+//! ```
+//! use endiantype::ring::DescriptorRing;
+//! use endiantype::*;
+//!
+//! #[derive(Copy, Clone, Default)]
+//! #[repr(C)]
+//! struct TxDescriptor {
+//!     addr: u64_le,
+//!     len: u16_le,
+//!     flags: u16_le,
+//!     status: u32_le,
+//! }
+//!
+//! let mut ring = DescriptorRing::<TxDescriptor, 4>::new();
+//! let slot = ring
+//!     .publish(TxDescriptor {
+//!         addr: 0x8000_0000.into(),
+//!         len: 64.into(),
+//!         ..Default::default()
+//!     })
+//!     .ok()
+//!     .unwrap();
+//! assert!(slot == 0 && ring.len() == 1);
+//! // dma_wmb(); then write ring.producer_index() to the doorbell register.
+//! assert!(ring.producer_index() == 1);
+//! // The device writes back completion status in place.
+//! let mut done = ring.read(slot);
+//! done.status = 1.into();
+//! ring.write(slot, done);
+//! // dma_rmb(); after seeing the completion, before reading the descriptor.
+//! let completed = ring.consume().unwrap();
+//! assert!(completed.len == 64 && completed.status == 1);
+//! assert!(ring.is_empty());
+//! ```
+use core::ptr;
+
+/// # Descriptor ring
+/// A ring of `N` descriptors of type `D`, laid out as a plain `[D; N]` array so its address can
+/// be handed to a device.
+///
+/// `producer` and `consumer` count published and consumed descriptors; the slot of a count is the
+/// count modulo `N`. `N` must be a power of two, so that slots stay in sequence when the counts
+/// wrap; other sizes, including 0, fail to compile.
+/// ```compile_fail
+/// use endiantype::ring::DescriptorRing;
+/// let ring = DescriptorRing::<u32, 3>::new();
+/// ```
+#[repr(C)]
+pub struct DescriptorRing<D, const N: usize> {
+    descriptors: [D; N],
+    producer: usize,
+    consumer: usize,
+}
+
+impl<D: Copy + Default, const N: usize> DescriptorRing<D, N> {
+    const POWER_OF_TWO: () = assert!(N.is_power_of_two(), "ring size must be a power of two");
+
+    pub fn new() -> Self {
+        let () = Self::POWER_OF_TWO;
+        Self {
+            descriptors: [D::default(); N],
+            producer: 0,
+            consumer: 0,
+        }
+    }
+}
+
+impl<D: Copy + Default, const N: usize> Default for DescriptorRing<D, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Copy, const N: usize> DescriptorRing<D, N> {
+    /// Slot index that the next published descriptor will occupy; the value for the doorbell
+    /// register after `publish`.
+    pub const fn producer_index(&self) -> usize {
+        self.producer % N
+    }
+
+    /// Slot index of the next descriptor to consume.
+    pub const fn consumer_index(&self) -> usize {
+        self.consumer % N
+    }
+
+    /// Number of published descriptors that have not been consumed yet.
+    pub const fn len(&self) -> usize {
+        self.producer.wrapping_sub(self.consumer)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Base address of the descriptor array, for programming the device.
+    ///
+    /// The device keeps using this address, so the ring must not move, e.g. by being returned by
+    /// value or stored in a growing `Vec`, for as long as the device may access it. Keep it in a
+    /// `static`, a leaked `Box` or other pinned DMA memory.
+    pub fn as_ptr(&self) -> *const D {
+        self.descriptors.as_ptr()
+    }
+
+    /// Write `descriptor` into the next free slot and advance the producer index. The device sees
+    /// it only once the caller rings the doorbell, after a DMA write barrier; see the module
+    /// documentation.
+    ///
+    /// Returns the slot index, or the descriptor back if the ring is full.
+    pub fn publish(&mut self, descriptor: D) -> Result<usize, D> {
+        if self.is_full() {
+            return Err(descriptor);
+        }
+        let slot = self.producer_index();
+        self.write(slot, descriptor);
+        self.producer = self.producer.wrapping_add(1);
+        Ok(slot)
+    }
+
+    /// Read the oldest published descriptor with a volatile read, observing any device
+    /// write-back, and advance the consumer index. Call it only for descriptors the device has
+    /// reported complete, after a DMA read barrier; see the module documentation.
+    pub fn consume(&mut self) -> Option<D> {
+        if self.is_empty() {
+            return None;
+        }
+        let descriptor = self.read(self.consumer_index());
+        self.consumer = self.consumer.wrapping_add(1);
+        Some(descriptor)
+    }
+
+    /// Volatile read of the descriptor in `slot`.
+    ///
+    /// Panics if `slot` is out of range.
+    pub fn read(&self, slot: usize) -> D {
+        // SAFETY: the reference is valid and aligned; indexing checked the bounds.
+        unsafe { ptr::read_volatile(&self.descriptors[slot]) }
+    }
+
+    /// Volatile write of the descriptor in `slot`.
+    ///
+    /// Panics if `slot` is out of range.
+    pub fn write(&mut self, slot: usize, descriptor: D) {
+        // SAFETY: the reference is valid and aligned; indexing checked the bounds.
+        unsafe { ptr::write_volatile(&mut self.descriptors[slot], descriptor) }
+    }
+}
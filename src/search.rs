@@ -0,0 +1,50 @@
+//! # Byte order aware search
+//!
+//! Searching a buffer for a value means searching for its encoding in a particular byte order:
+//! `0xcafebabe` appears as `ca fe ba be` in a big-endian file and as `be ba fe ca` in a
+//! little-endian one. `find_value` and `rfind_value` take the endian type to search for, so the
+//! byte order is stated once at the call site, e.g. when carving files by their magic numbers.
+//! Matches may start at any offset, not just aligned ones.
+//! ## Example
+//! ```
+//! use endiantype::search::*;
+//! use endiantype::*;
+//! let image = b"junk\xca\xfe\xba\xbe\x00\x00\x00\x02...\xbe\xba\xfe\xca";
+//! assert!(find_value::<u32_be>(image, 0xcafebabe) == Some(4));
+//! assert!(find_value::<u32_le>(image, 0xcafebabe) == Some(15));
+//! assert!(rfind_value::<u16_be>(image, 0x0002) == Some(10));
+//! assert!(find_value::<u64_be>(image, 0xcafebabe).is_none());
+//! ```
+
+/// Offset of the first occurrence of `needle`, encoded as `T`, in `haystack`.
+pub fn find_value<T: AsRef<[u8]>>(haystack: &[u8], needle: impl Into<T>) -> Option<usize> {
+    let needle = needle.into();
+    let needle = needle.as_ref();
+    let first = *needle.first()?;
+    let mut start = 0;
+    while start + needle.len() <= haystack.len() {
+        // Skip ahead to the next candidate first byte before comparing the whole value.
+        let candidate = start + haystack[start..].iter().position(|&b| b == first)?;
+        if haystack.get(candidate..candidate + needle.len())? == needle {
+            return Some(candidate);
+        }
+        start = candidate + 1;
+    }
+    None
+}
+
+/// Offset of the last occurrence of `needle`, encoded as `T`, in `haystack`.
+pub fn rfind_value<T: AsRef<[u8]>>(haystack: &[u8], needle: impl Into<T>) -> Option<usize> {
+    let needle = needle.into();
+    let needle = needle.as_ref();
+    let last = *needle.last()?;
+    let mut end = haystack.len();
+    while end >= needle.len() {
+        let candidate = haystack[..end].iter().rposition(|&b| b == last)? + 1;
+        if candidate >= needle.len() && &haystack[candidate - needle.len()..candidate] == needle {
+            return Some(candidate - needle.len());
+        }
+        end = candidate - 1;
+    }
+    None
+}
@@ -0,0 +1,94 @@
+//! # Typed slice views
+//!
+//! `cast_slice` views a byte buffer as a slice of endian values in place, checking that it is
+//! aligned for the element type and a whole number of elements long. A memory-mapped file or DMA
+//! buffer of little-endian records can then be indexed as `&[u32_le]` without copying it, and
+//! `cast_slice_mut` fills one in place through a `&mut [u16_be]`.
+//! ## Example
+//! ```
+//! use endiantype::slice::*;
+//! use endiantype::*;
+//!
+//! #[repr(align(4))]
+//! struct Buffer([u8; 12]);
+//! let buf = Buffer([1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+//!
+//! let records = cast_slice::<u32_le>(&buf.0).unwrap();
+//! assert!(records.len() == 3 && records[2] == 3);
+//! assert!(cast_slice::<u32_le>(&buf.0[1..5]) == Err(CastError::Misaligned));
+//! assert!(cast_slice::<u32_le>(&buf.0[..6]) == Err(CastError::Length));
+//!
+//! let mut packet = Buffer([0; 12]);
+//! let fields = cast_slice_mut::<u16_be>(&mut packet.0[..4]).unwrap();
+//! fields[0] = u16_be::from_native(0x0800);
+//! fields[1] += 20;
+//! assert!(packet.0[..4] == [0x08, 0x00, 0x00, 20]);
+//! ```
+use crate::{BigEndian, LittleEndian};
+use core::fmt;
+
+/// # Cast errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CastError {
+    /// The bytes do not start at a multiple of the alignment of the element type.
+    Misaligned,
+    /// The length is not a multiple of the size of the element type.
+    Length,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CastError::Misaligned => "bytes are not aligned for the element type",
+            CastError::Length => "length is not a multiple of the element size",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CastError {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// # Slice element
+/// The endian types, which have no padding and for which every bit pattern is valid.
+pub trait Element: Copy + sealed::Sealed {}
+
+macro_rules! impl_element {
+    ($type_name: ident) => {
+        impl sealed::Sealed for LittleEndian<$type_name> {}
+        impl Element for LittleEndian<$type_name> {}
+        impl sealed::Sealed for BigEndian<$type_name> {}
+        impl Element for BigEndian<$type_name> {}
+    };
+}
+
+for_each_primitive!(impl_element);
+
+fn check<T: Element>(bytes: &[u8]) -> Result<usize, CastError> {
+    if !(bytes.as_ptr() as usize).is_multiple_of(core::mem::align_of::<T>()) {
+        return Err(CastError::Misaligned);
+    }
+    if !bytes.len().is_multiple_of(core::mem::size_of::<T>()) {
+        return Err(CastError::Length);
+    }
+    Ok(bytes.len() / core::mem::size_of::<T>())
+}
+
+/// View `bytes` as a slice of `T` in place.
+pub fn cast_slice<T: Element>(bytes: &[u8]) -> Result<&[T], CastError> {
+    let len = check::<T>(bytes)?;
+    // SAFETY: `T` has no padding and every bit pattern is valid, and `bytes` is aligned for `T`
+    // and exactly `len` elements long.
+    Ok(unsafe { core::slice::from_raw_parts(bytes.as_ptr() as *const T, len) })
+}
+
+/// View `bytes` as a mutable slice of `T` in place, so values written through it land in the
+/// buffer.
+pub fn cast_slice_mut<T: Element>(bytes: &mut [u8]) -> Result<&mut [T], CastError> {
+    let len = check::<T>(bytes)?;
+    // SAFETY: as for `cast_slice`; any `T` written through the slice is valid bytes.
+    Ok(unsafe { core::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, len) })
+}
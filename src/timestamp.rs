@@ -0,0 +1,143 @@
+//! # Timestamp types
+//!
+//! Fixed-width counters as found in log and capture formats, stored in a fixed byte order.
+//!
+//! `Timestamp<T>` counts seconds and `Timestamp64<T>` counts nanoseconds. Arithmetic with
+//! `Duration` never wraps silently: the `checked_*` methods return `None` when the result does not
+//! fit in the field, and `wrapping_duration_since` is available for counters that roll over.
+//! ## Example
+//! ```
+//! use core::time::Duration;
+//! use endiantype::timestamp::*;
+//! use endiantype::*;
+//! let start = Timestamp::<u32_be>::from_secs(100);
+//! let end = start.checked_add(Duration::from_secs(20)).unwrap();
+//! assert!(end.secs() == 120);
+//! assert!(end.checked_duration_since(start) == Some(Duration::from_secs(20)));
+//! assert!(start.checked_duration_since(end).is_none());
+//!
+//! let t = Timestamp64::<u64_le>::from_nanos(1_500);
+//! assert!(t.checked_sub(Duration::from_nanos(500)).unwrap().nanos() == 1_000);
+//! ```
+use crate::{BigEndian, LittleEndian};
+use core::convert::TryFrom;
+use core::time::Duration;
+
+/// # Seconds timestamp
+/// A seconds counter stored as an endian-aware integer, e.g. `Timestamp<u32_be>`.
+///
+/// Sub-second parts of a `Duration` are ignored when adding to or subtracting from it.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Timestamp<T>(T);
+
+/// # Nanoseconds timestamp
+/// A 64-bit nanoseconds counter stored as an endian-aware integer, e.g. `Timestamp64<u64_le>`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Timestamp64<T>(T);
+
+macro_rules! impl_timestamp_secs {
+    ($type_name: ident, $endian_name: ident) => {
+        impl Timestamp<$endian_name<$type_name>> {
+            pub const fn new(data: $endian_name<$type_name>) -> Self {
+                Self(data)
+            }
+
+            pub const fn from_secs(secs: $type_name) -> Self {
+                Self($endian_name::<$type_name>::from_native(secs))
+            }
+
+            pub const fn get(self) -> $endian_name<$type_name> {
+                self.0
+            }
+
+            pub fn secs(&self) -> $type_name {
+                self.0.to_native()
+            }
+
+            pub fn checked_duration_since(&self, earlier: Self) -> Option<Duration> {
+                self.secs()
+                    .checked_sub(earlier.secs())
+                    .map(|secs| Duration::from_secs(u64::from(secs)))
+            }
+
+            pub fn wrapping_duration_since(&self, earlier: Self) -> Duration {
+                Duration::from_secs(u64::from(self.secs().wrapping_sub(earlier.secs())))
+            }
+
+            pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+                let secs = $type_name::try_from(duration.as_secs()).ok()?;
+                self.secs().checked_add(secs).map(Self::from_secs)
+            }
+
+            pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+                let secs = $type_name::try_from(duration.as_secs()).ok()?;
+                self.secs().checked_sub(secs).map(Self::from_secs)
+            }
+        }
+
+        impl From<$endian_name<$type_name>> for Timestamp<$endian_name<$type_name>> {
+            #[inline]
+            fn from(data: $endian_name<$type_name>) -> Self {
+                Self(data)
+            }
+        }
+    };
+}
+
+macro_rules! impl_timestamp_nanos {
+    ($endian_name: ident) => {
+        impl Timestamp64<$endian_name<u64>> {
+            pub const fn new(data: $endian_name<u64>) -> Self {
+                Self(data)
+            }
+
+            pub const fn from_nanos(nanos: u64) -> Self {
+                Self($endian_name::<u64>::from_native(nanos))
+            }
+
+            pub const fn get(self) -> $endian_name<u64> {
+                self.0
+            }
+
+            pub fn nanos(&self) -> u64 {
+                self.0.to_native()
+            }
+
+            pub fn checked_duration_since(&self, earlier: Self) -> Option<Duration> {
+                self.nanos()
+                    .checked_sub(earlier.nanos())
+                    .map(Duration::from_nanos)
+            }
+
+            pub fn wrapping_duration_since(&self, earlier: Self) -> Duration {
+                Duration::from_nanos(self.nanos().wrapping_sub(earlier.nanos()))
+            }
+
+            pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+                let nanos = u64::try_from(duration.as_nanos()).ok()?;
+                self.nanos().checked_add(nanos).map(Self::from_nanos)
+            }
+
+            pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+                let nanos = u64::try_from(duration.as_nanos()).ok()?;
+                self.nanos().checked_sub(nanos).map(Self::from_nanos)
+            }
+        }
+
+        impl From<$endian_name<u64>> for Timestamp64<$endian_name<u64>> {
+            #[inline]
+            fn from(data: $endian_name<u64>) -> Self {
+                Self(data)
+            }
+        }
+    };
+}
+
+impl_timestamp_secs!(u32, LittleEndian);
+impl_timestamp_secs!(u32, BigEndian);
+impl_timestamp_secs!(u64, LittleEndian);
+impl_timestamp_secs!(u64, BigEndian);
+impl_timestamp_nanos!(LittleEndian);
+impl_timestamp_nanos!(BigEndian);
@@ -0,0 +1,52 @@
+//! # UTF-16 text
+//!
+//! Decoding and encoding of UTF-16 text stored as `&[u16_be]` or `&[u16_le]`, as found in NTFS,
+//! Windows resources and JVM class files. Surrogate pairs are handled by `core::char`.
+//! ## Example
+//! ```
+//! use endiantype::utf16;
+//! use endiantype::*;
+//! let units: Vec<u16_be> = utf16::encode("a😀").collect();
+//! assert!(units.len() == 3 && units[1] == 0xd83d);
+//! let text: String = utf16::decode(&units).map(|c| c.unwrap()).collect();
+//! assert!(text == "a😀");
+//!
+//! let mut buf = [u16_le::default(); 2];
+//! assert!(utf16::encode_into("abc", &mut buf).is_none());
+//! assert!(utf16::encode_into("😀", &mut buf) == Some(2));
+//! let lossy: String = utf16::decode_lossy(&buf[..1]).collect();
+//! assert!(lossy == "\u{fffd}");
+//! ```
+use core::char::{DecodeUtf16, REPLACEMENT_CHARACTER};
+use core::iter::{Copied, Map};
+use core::slice::Iter;
+use core::str::EncodeUtf16;
+
+/// Iterator returned by `decode`.
+pub type Decode<'a, E> = DecodeUtf16<Map<Copied<Iter<'a, E>>, fn(E) -> u16>>;
+
+/// Decode UTF-16 code units into `char`s, reporting unpaired surrogates as errors.
+pub fn decode<E: Copy + Into<u16>>(units: &[E]) -> Decode<'_, E> {
+    core::char::decode_utf16(units.iter().copied().map(Into::into as fn(E) -> u16))
+}
+
+/// Decode UTF-16 code units into `char`s, replacing unpaired surrogates with U+FFFD.
+pub fn decode_lossy<E: Copy + Into<u16>>(units: &[E]) -> impl Iterator<Item = char> + '_ {
+    decode(units).map(|c| c.unwrap_or(REPLACEMENT_CHARACTER))
+}
+
+/// Encode `text` as UTF-16 code units.
+pub fn encode<E: From<u16>>(text: &str) -> Map<EncodeUtf16<'_>, fn(u16) -> E> {
+    text.encode_utf16().map(E::from)
+}
+
+/// Encode `text` into `buf`, returning the number of code units written, or `None` if `buf` is
+/// too short.
+pub fn encode_into<E: From<u16>>(text: &str, buf: &mut [E]) -> Option<usize> {
+    let mut len = 0;
+    for unit in text.encode_utf16() {
+        *buf.get_mut(len)? = E::from(unit);
+        len += 1;
+    }
+    Some(len)
+}
@@ -0,0 +1,111 @@
+//! # Vector conversions
+//!
+//! Converts between `Vec<T>` and `Vec` of an endian `T` by swapping the elements in place and
+//! reusing the allocation, so large buffers can be prepared for writing without doubling peak
+//! memory. Needs the `alloc` feature.
+//!
+//! `native_slice` and `from_native_slice` borrow a slice as the other type when the byte order
+//! is the target's, and only copy it otherwise. Arrow and most columnar formats keep their
+//! buffers little endian, so on little-endian hosts a `&[i32_le]` or `&[f64_le]` column can be
+//! handed to them without a copy.
+//! ## Example
+//! ```
+//! use endiantype::*;
+//! let samples: Vec<u32> = (0..1000).collect();
+//! let capacity = samples.capacity();
+//! let wire = u32_be::from_native_vec(samples);
+//! assert!(wire.capacity() == capacity && wire[1] == 1);
+//! assert!(wire[1].as_ref() == [0, 0, 0, 1]);
+//! let samples = u32_be::into_native_vec(wire);
+//! assert!(samples[999] == 999);
+//!
+//! let column = [i32_le::from_native(-1), i32_le::from_native(7)];
+//! let native = i32_le::native_slice(&column);
+//! assert!(*native == [-1, 7]);
+//! #[cfg(target_endian = "little")]
+//! assert!(matches!(native, std::borrow::Cow::Borrowed(_)));
+//! let readings = f64_le::from_native_slice(&[0.5, -1.25]);
+//! assert!(f64_le::native_slice(&readings)[1] == -1.25);
+//! ```
+use crate::endianness::Endianness;
+use crate::{BigEndian, LittleEndian};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::mem::ManuallyDrop;
+
+/// Reinterpret a vector as one of a `repr(transparent)` wrapper type or its inner type.
+///
+/// # Safety
+/// `T` and `U` must have the same size and alignment, and every `T` must be a valid `U`.
+unsafe fn cast_vec<T, U>(vec: Vec<T>) -> Vec<U> {
+    let mut vec = ManuallyDrop::new(vec);
+    Vec::from_raw_parts(vec.as_mut_ptr() as *mut U, vec.len(), vec.capacity())
+}
+
+macro_rules! impl_endian_vec {
+    ($type_name: ident, $endian_name: ident, $endianness: ident) => {
+        impl $endian_name<$type_name> {
+            /// Convert a vector of native values in place.
+            pub fn from_native_vec(mut vec: Vec<$type_name>) -> Vec<Self> {
+                for value in vec.iter_mut() {
+                    *value = Self::from_native(*value).0;
+                }
+                // SAFETY: `Self` is `repr(transparent)` over the native type.
+                unsafe { cast_vec(vec) }
+            }
+
+            /// Convert a vector of endian values to native values in place.
+            pub fn into_native_vec(vec: Vec<Self>) -> Vec<$type_name> {
+                // SAFETY: `Self` is `repr(transparent)` over the native type.
+                let mut vec: Vec<$type_name> = unsafe { cast_vec(vec) };
+                for value in vec.iter_mut() {
+                    *value = Self::new(*value).to_native();
+                }
+                vec
+            }
+
+            /// The values of `slice` in native order, borrowed if no conversion is needed.
+            pub fn native_slice(slice: &[Self]) -> Cow<'_, [$type_name]> {
+                if Endianness::$endianness.is_native() {
+                    // SAFETY: `Self` is `repr(transparent)` over the native type, and the stored
+                    // values are already in native order.
+                    Cow::Borrowed(unsafe {
+                        core::slice::from_raw_parts(
+                            slice.as_ptr() as *const $type_name,
+                            slice.len(),
+                        )
+                    })
+                } else {
+                    Cow::Owned(slice.iter().map(Self::to_native).collect())
+                }
+            }
+
+            /// `slice` converted to endian values, borrowed if no conversion is needed.
+            pub fn from_native_slice(slice: &[$type_name]) -> Cow<'_, [Self]> {
+                if Endianness::$endianness.is_native() {
+                    // SAFETY: `Self` is `repr(transparent)` over the native type, and native
+                    // values are already in its byte order.
+                    Cow::Borrowed(unsafe {
+                        core::slice::from_raw_parts(slice.as_ptr() as *const Self, slice.len())
+                    })
+                } else {
+                    Cow::Owned(
+                        slice
+                            .iter()
+                            .map(|&value| Self::from_native(value))
+                            .collect(),
+                    )
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_endian_vec_both {
+    ($type_name: ident) => {
+        impl_endian_vec!($type_name, LittleEndian, Little);
+        impl_endian_vec!($type_name, BigEndian, Big);
+    };
+}
+
+for_each_primitive!(impl_endian_vec_both);
@@ -0,0 +1,112 @@
+//! # WebAssembly linear memory
+//!
+//! Host functions of a wasm runtime receive `u32` offsets into the guest's linear memory, which
+//! the host sees as a byte slice. `GuestMemory` reads and writes values at such offsets with every
+//! access bounds-checked, returning an `Error` for the host function to turn into a trap instead
+//! of panicking. Wasm memory is little endian, so guest structs are `endian_struct!`s of `_le`
+//! fields, and pointers inside them are `u32_le`.
+//! ## Example
+//! ```
+//! use endiantype::wasm::*;
+//! use endiantype::*;
+//!
+//! endian_struct! {
+//!     /// A `(ptr, len)` string slice as laid out by the guest.
+//!     pub struct GuestStr {
+//!         pub ptr: u32_le,
+//!         pub len: u32_le,
+//!     }
+//! }
+//!
+//! let mut bytes = [0u8; 64];
+//! let mut memory = GuestMemory::new(&mut bytes[..]);
+//! memory.write_bytes(32, b"hello").unwrap();
+//! memory.write(8, GuestStr { ptr: 32.into(), len: 5.into() }).unwrap();
+//!
+//! let arg: GuestStr = memory.read(8).unwrap();
+//! assert!(memory.read_str(arg.ptr.to_native(), arg.len.to_native()) == Ok("hello"));
+//! memory.write(60, u32_le::from_native(7)).unwrap();
+//! assert!(memory.write(61, u32_le::from_native(7)) == Err(Error::OutOfBounds(61)));
+//! assert!(memory.read_bytes(u32::MAX, 2) == Err(Error::OutOfBounds(u32::MAX)));
+//! ```
+use crate::decoder::Decode;
+use crate::io::Encode;
+use core::convert::TryFrom;
+
+/// # Access errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The access starting at this offset runs past the end of memory.
+    OutOfBounds(u32),
+    /// The bytes of a string are not UTF-8.
+    InvalidUtf8,
+}
+
+/// # Guest memory
+/// A view of wasm linear memory, borrowed (`&[u8]`, `&mut [u8]`) or owned (`Vec<u8>`).
+#[derive(Debug)]
+pub struct GuestMemory<M> {
+    memory: M,
+}
+
+impl<M: AsRef<[u8]>> GuestMemory<M> {
+    pub fn new(memory: M) -> Self {
+        Self { memory }
+    }
+
+    /// Size of the memory in bytes.
+    pub fn len(&self) -> usize {
+        self.memory.as_ref().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn into_inner(self) -> M {
+        self.memory
+    }
+
+    /// The `len` bytes at `offset`.
+    pub fn read_bytes(&self, offset: u32, len: u32) -> Result<&[u8], Error> {
+        let start = offset as usize;
+        start
+            .checked_add(len as usize)
+            .and_then(|end| self.memory.as_ref().get(start..end))
+            .ok_or(Error::OutOfBounds(offset))
+    }
+
+    pub fn read<T: Decode>(&self, offset: u32) -> Result<T, Error> {
+        let size = u32::try_from(T::SIZE).map_err(|_| Error::OutOfBounds(offset))?;
+        self.read_bytes(offset, size).map(T::decode)
+    }
+
+    /// The UTF-8 string of `len` bytes at `offset`.
+    pub fn read_str(&self, offset: u32, len: u32) -> Result<&str, Error> {
+        core::str::from_utf8(self.read_bytes(offset, len)?).map_err(|_| Error::InvalidUtf8)
+    }
+}
+
+impl<M: AsRef<[u8]> + AsMut<[u8]>> GuestMemory<M> {
+    /// The `len` bytes at `offset`, for the host to fill in.
+    pub fn bytes_mut(&mut self, offset: u32, len: u32) -> Result<&mut [u8], Error> {
+        let start = offset as usize;
+        let memory = self.memory.as_mut();
+        start
+            .checked_add(len as usize)
+            .and_then(move |end| memory.get_mut(start..end))
+            .ok_or(Error::OutOfBounds(offset))
+    }
+
+    pub fn write_bytes(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Error> {
+        let len = u32::try_from(bytes.len()).map_err(|_| Error::OutOfBounds(offset))?;
+        self.bytes_mut(offset, len)?.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    pub fn write<T: Encode>(&mut self, offset: u32, value: T) -> Result<(), Error> {
+        let size = u32::try_from(T::SIZE).map_err(|_| Error::OutOfBounds(offset))?;
+        value.encode(self.bytes_mut(offset, size)?);
+        Ok(())
+    }
+}
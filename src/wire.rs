@@ -0,0 +1,272 @@
+//! # Wire structs
+//!
+//! `endian_struct!` declares a struct of endian fields that is read and written field by field,
+//! in declaration order and without padding, by implementing `Decode` and `Encode` for it. It
+//! also implements `Wire`, whose `layout` describes the name, offset, width and byte order of
+//! every field, so hexdump annotators, diff tools and debug UIs can render any wire struct
+//! without per-type code. With the `std` feature, `c_header` turns the same description into a C
+//! declaration for firmware that shares the format.
+//! ## Example
+//! ```
+//! use endiantype::io::EndianReader;
+//! use endiantype::wire::*;
+//! use endiantype::*;
+//!
+//! endian_struct! {
+//!     /// An Ethernet II header.
+//!     pub struct EthernetHeader {
+//!         pub dst: [u8; 6],
+//!         pub src: [u8; 6],
+//!         pub ethertype: u16_be,
+//!     }
+//! }
+//!
+//! let frame = [
+//!     0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02, 0x00, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06,
+//! ];
+//! let header = EndianReader::new(&frame).read::<EthernetHeader>().unwrap();
+//! assert!(header.ethertype == 0x0806 && header.src[5] == 1);
+//!
+//! let ethertype = EthernetHeader::layout()[2];
+//! assert!(ethertype.name == "ethertype" && ethertype.offset == 12 && ethertype.width == 2);
+//! assert!(ethertype.endianness == Some(Endianness::Big));
+//! ```
+use crate::decoder::Decode;
+use crate::endianness::Endianness;
+use crate::io::Encode;
+use crate::{BigEndian, LittleEndian};
+
+/// # Field description
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FieldDesc {
+    pub name: &'static str,
+    /// Offset of the field in the packed encoding, which may differ from its offset in memory.
+    pub offset: usize,
+    /// Encoded size of the field in bytes.
+    pub width: usize,
+    /// Byte order of the field, or `None` for byte arrays and nested structs.
+    pub endianness: Option<Endianness>,
+    /// C type of the field, or of the elements of a byte array.
+    pub c_type: &'static str,
+}
+
+impl FieldDesc {
+    pub const fn new<T: Wire>(name: &'static str, offset: usize) -> Self {
+        Self {
+            name,
+            offset,
+            width: <T as Decode>::SIZE,
+            endianness: T::ENDIANNESS,
+            c_type: T::C_TYPE,
+        }
+    }
+}
+
+/// # Wire type
+/// Types that can be fields of an `endian_struct!`.
+pub trait Wire: Decode + Encode {
+    /// Byte order of the whole value, or `None` if it has none or mixes several.
+    const ENDIANNESS: Option<Endianness>;
+
+    /// The equivalent C type, e.g. `uint32_t` or `struct EthernetHeader`.
+    const C_TYPE: &'static str;
+
+    /// Descriptions of the fields of a struct; empty for other types.
+    fn layout() -> &'static [FieldDesc] {
+        &[]
+    }
+}
+
+/// The C type of each primitive type.
+macro_rules! c_type {
+    (u8) => {
+        "uint8_t"
+    };
+    (u16) => {
+        "uint16_t"
+    };
+    (u32) => {
+        "uint32_t"
+    };
+    (u64) => {
+        "uint64_t"
+    };
+    (u128) => {
+        "unsigned __int128"
+    };
+    (usize) => {
+        "size_t"
+    };
+    (i8) => {
+        "int8_t"
+    };
+    (i16) => {
+        "int16_t"
+    };
+    (i32) => {
+        "int32_t"
+    };
+    (i64) => {
+        "int64_t"
+    };
+    (i128) => {
+        "__int128"
+    };
+    (isize) => {
+        "ptrdiff_t"
+    };
+    (f32) => {
+        "float"
+    };
+    (f64) => {
+        "double"
+    };
+}
+
+macro_rules! impl_wire {
+    ($type_name: ident) => {
+        impl Wire for LittleEndian<$type_name> {
+            const ENDIANNESS: Option<Endianness> = Some(Endianness::Little);
+            const C_TYPE: &'static str = c_type!($type_name);
+        }
+
+        impl Wire for BigEndian<$type_name> {
+            const ENDIANNESS: Option<Endianness> = Some(Endianness::Big);
+            const C_TYPE: &'static str = c_type!($type_name);
+        }
+    };
+}
+
+for_each_primitive!(impl_wire);
+
+impl<const N: usize> Wire for [u8; N] {
+    const ENDIANNESS: Option<Endianness> = None;
+    const C_TYPE: &'static str = "uint8_t";
+}
+
+/// # Wire struct declarations
+/// Declares a struct whose fields are encoded one after another in declaration order, with no
+/// padding between them. Every field type must implement `Wire`: endian integers, byte arrays,
+/// `bitfield!` types and other `endian_struct!` types.
+///
+/// The struct derives `Copy`, `Clone`, `Debug` and `PartialEq`; add other derives as attributes.
+/// It is `#[repr(C)]`, so its in-memory layout may have padding between fields that the encoding
+/// does not: read and write it with `Decode` and `Encode` rather than casting bytes to it. The
+/// offsets in `layout` are those of the packed encoding.
+#[macro_export]
+macro_rules! endian_struct {
+    (
+        $(#[$attr: meta])*
+        $vis: vis struct $name: ident {
+            $(
+                $(#[$field_attr: meta])*
+                $field_vis: vis $field: ident: $field_type: ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        #[repr(C)]
+        $vis struct $name {
+            $(
+                $(#[$field_attr])*
+                $field_vis $field: $field_type,
+            )*
+        }
+
+        impl $crate::decoder::Decode for $name {
+            const SIZE: usize = 0 $(+ <$field_type as $crate::decoder::Decode>::SIZE)*;
+
+            #[inline]
+            fn decode(bytes: &[u8]) -> Self {
+                let mut reader = $crate::io::EndianReader::new(bytes);
+                Self {
+                    $($field: reader.read().unwrap(),)*
+                }
+            }
+        }
+
+        impl $crate::io::Encode for $name {
+            const SIZE: usize = <Self as $crate::decoder::Decode>::SIZE;
+
+            #[inline]
+            fn encode(&self, bytes: &mut [u8]) {
+                let mut writer = $crate::io::EndianWriter::new(bytes);
+                $(writer.write(self.$field).unwrap();)*
+            }
+        }
+
+        impl $crate::wire::Wire for $name {
+            const ENDIANNESS: Option<$crate::Endianness> = None;
+            const C_TYPE: &'static str = concat!("struct ", stringify!($name));
+
+            fn layout() -> &'static [$crate::wire::FieldDesc] {
+                #[allow(unused_assignments)]
+                const LAYOUT: &[$crate::wire::FieldDesc] = &{
+                    let mut offset = 0;
+                    [$({
+                        let field = $crate::wire::FieldDesc::new::<$field_type>(stringify!($field), offset);
+                        offset += field.width;
+                        field
+                    }),*]
+                };
+                LAYOUT
+            }
+        }
+    };
+}
+
+/// Emit a C declaration of the wire layout of `T`, packed so that it matches the encoding, with
+/// the byte order of each multi-byte field in a comment. Nested structs are referred to by name
+/// and must be emitted first.
+/// ## Example
+/// ```
+/// use endiantype::wire::*;
+/// use endiantype::*;
+///
+/// endian_struct! {
+///     pub struct Record {
+///         pub magic: [u8; 4],
+///         pub len: u32_le,
+///         pub crc: u16_be,
+///     }
+/// }
+///
+/// let header = c_header::<Record>();
+/// assert!(header.contains("struct Record {\n"));
+/// assert!(header.contains("    uint8_t magic[4];\n"));
+/// assert!(header.contains("    uint32_t len; /* little endian */\n"));
+/// assert!(header.contains("} __attribute__((packed));\n"));
+/// assert!(header.contains("_Static_assert(sizeof(struct Record) == 10"));
+/// ```
+#[cfg(feature = "std")]
+pub fn c_header<T: Wire>() -> String {
+    use std::fmt::Write;
+
+    let mut header = String::new();
+    header.push_str("#pragma pack(push, 1)\n");
+    writeln!(header, "{} {{", T::C_TYPE).unwrap();
+    for field in T::layout() {
+        let is_array = field.endianness.is_none() && field.c_type == "uint8_t";
+        write!(header, "    {} {}", field.c_type, field.name).unwrap();
+        if is_array {
+            write!(header, "[{}]", field.width).unwrap();
+        }
+        header.push(';');
+        match field.endianness {
+            Some(Endianness::Little) if field.width > 1 => header.push_str(" /* little endian */"),
+            Some(Endianness::Big) if field.width > 1 => header.push_str(" /* big endian */"),
+            _ => {}
+        }
+        header.push('\n');
+    }
+    header.push_str("} __attribute__((packed));\n#pragma pack(pop)\n");
+    writeln!(
+        header,
+        "_Static_assert(sizeof({}) == {}, \"wire size\");",
+        T::C_TYPE,
+        <T as Decode>::SIZE
+    )
+    .unwrap();
+    header
+}